@@ -0,0 +1,173 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::snapshot::Snapshot;
+
+/// Give up on a server that does not answer within this long
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Base URL of an inventory server, e.g. `http://inventory.local:8080`
+///
+/// Only plain HTTP is supported; put the server behind a TLS proxy to push across networks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushUrl {
+    host: String,
+    port: u16,
+    /// Path prefix without trailing slash, empty for the server root
+    prefix: String,
+}
+
+impl PushUrl {
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let invalid = |why: &str| format!("Invalid push URL {}: {}", url, why);
+        let rest = url.trim().strip_prefix("http://").ok_or_else(|| invalid("must start with http://"))?;
+        let (authority, prefix) = match rest.find('/') {
+            Some(i) => (&rest[..i], rest[i..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        // IPv6 literals are bracketed, "[::1]:8080"
+        let (host, port) = match authority.strip_prefix('[').and_then(|a| a.split_once(']')) {
+            Some((host, "")) => (host, None),
+            Some((host, port)) => (host, Some(port.strip_prefix(':').ok_or_else(|| invalid("invalid port"))?)),
+            None => match authority.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = match port {
+            Some(port) => port.parse().map_err(|_| invalid("invalid port"))?,
+            None => 80,
+        };
+        if host.is_empty() {
+            return Err(invalid("no host"));
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            prefix: prefix.to_string(),
+        })
+    }
+
+    /// Endpoint snapshots are posted to
+    fn snapshots_path(&self) -> String {
+        format!("{}/api/snapshots", self.prefix)
+    }
+
+    /// Host and port as written in a URL or `Host` header
+    fn authority(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+impl fmt::Display for PushUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "http://{}{}", self.authority(), self.prefix)
+    }
+}
+
+/// POST a snapshot to the server, returning the id it was stored under
+pub fn push(url: &PushUrl, snapshot: &Snapshot) -> Result<String, String> {
+    let body = serde_json::to_vec(snapshot).map_err(|e| e.to_string())?;
+    let failed = |e: std::io::Error| format!("Cannot push to {}: {}", url, e);
+
+    let addr = (url.host.as_str(), url.port)
+        .to_socket_addrs()
+        .map_err(failed)?
+        .next()
+        .ok_or_else(|| format!("Cannot resolve {}", url.host))?;
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT).map_err(failed)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(failed)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(failed)?;
+
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        url.snapshots_path(),
+        url.authority(),
+        body.len()
+    )
+    .map_err(failed)?;
+    stream.write_all(&body).map_err(failed)?;
+
+    // The server closes the connection after one response
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(failed)?;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status: u16 = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("Invalid response from {}", url))?;
+
+    let json: Value = serde_json::from_str(body).unwrap_or_default();
+    if !(200..300).contains(&status) {
+        let error = json.get("error").and_then(Value::as_str).unwrap_or(body.trim());
+        return Err(format!("{} rejected the snapshot ({}): {}", url, status, error));
+    }
+    Ok(json.get("id").and_then(Value::as_str).unwrap_or("?").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(text: &str) -> PushUrl {
+        PushUrl::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parses_host_port_and_prefix() {
+        let plain = url("http://inventory.local:8080");
+        assert_eq!((plain.host.as_str(), plain.port, plain.prefix.as_str()), ("inventory.local", 8080, ""));
+        assert_eq!(plain.snapshots_path(), "/api/snapshots");
+
+        let default_port = url("  http://10.0.0.2  ");
+        assert_eq!((default_port.host.as_str(), default_port.port), ("10.0.0.2", 80));
+
+        for text in ["http://inventory.local:8080/fleet/", "http://inventory.local:8080/fleet"] {
+            let prefixed = url(text);
+            assert_eq!(prefixed.prefix, "/fleet");
+            assert_eq!(prefixed.snapshots_path(), "/fleet/api/snapshots");
+            assert_eq!(prefixed.to_string(), "http://inventory.local:8080/fleet");
+        }
+        assert_eq!(url("http://inventory.local/").prefix, "");
+    }
+
+    #[test]
+    fn parses_ipv6_literals() {
+        let v6 = url("http://[fd00::10]:8080/fleet");
+        assert_eq!((v6.host.as_str(), v6.port, v6.prefix.as_str()), ("fd00::10", 8080, "/fleet"));
+        assert_eq!(v6.authority(), "[fd00::10]:8080");
+        assert_eq!(v6.to_string(), "http://[fd00::10]:8080/fleet");
+
+        let default_port = url("http://[::1]");
+        assert_eq!((default_port.host.as_str(), default_port.port), ("::1", 80));
+    }
+
+    #[test]
+    fn rejects_bad_urls() {
+        let cases = [
+            ("inventory.local:8080", "must start with http://"),
+            ("https://inventory.local", "must start with http://"),
+            ("http://", "no host"),
+            ("http://:8080", "no host"),
+            ("http://[]:8080", "no host"),
+            ("http://inventory.local:", "invalid port"),
+            ("http://inventory.local:http", "invalid port"),
+            ("http://inventory.local:65536", "invalid port"),
+            ("http://[::1]8080", "invalid port"),
+            ("http://[::1", "invalid port"),
+        ];
+        for (text, why) in cases {
+            assert_eq!(PushUrl::parse(text), Err(format!("Invalid push URL {}: {}", text, why)));
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::fs::{self, File};
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use crate::collector::{Collected, CollectionRun, Collector, CollectorReport, CollectorStatus, Completed};
 use crate::info::{
     system::SystemInfo,
     bios::BiosInfo,
@@ -12,7 +15,19 @@ use crate::info::{
     monitor::MonitorInfo,
     gpu::GpuInfo,
     advanced::{LockedMotherboardInfo, PreviousSerials},
+    virtualization::VirtualizationInfo,
+    firmware::FirmwareStatus,
+    tpm::TpmInfo,
+    CollectOptions,
 };
+use crate::export::{host_name, ExportTarget, ExportWriter, NameFields};
+use crate::keys::Keymap;
+use crate::policy::{ComplianceReport, Policy};
+use crate::redact::{mask_file_name, Redactor};
+use crate::sign::ExportSigner;
+use crate::snapshot::{Baseline, Change, Snapshot};
+use crate::ui::Theme;
+use crate::watch::{WatchOptions, Watcher};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
@@ -25,6 +40,8 @@ pub enum Tab {
     Network,
     Monitor,
     Gpu,
+    Watch,
+    Diff,
     Advanced,
 }
 
@@ -40,6 +57,8 @@ impl Tab {
             Tab::Network,
             Tab::Monitor,
             Tab::Gpu,
+            Tab::Watch,
+            Tab::Diff,
             Tab::Advanced,
         ]
     }
@@ -55,10 +74,46 @@ impl Tab {
             Tab::Network => "Network",
             Tab::Monitor => "Monitor",
             Tab::Gpu => "GPU",
+            Tab::Watch => "Watch",
+            Tab::Diff => "Diff",
             Tab::Advanced => "Advanced",
         }
     }
 
+    /// Collectors whose data the tab shows, the first one is the tab's own
+    pub fn collectors(&self) -> &'static [Collector] {
+        match self {
+            Tab::System => &[Collector::System],
+            Tab::Bios => &[Collector::Bios, Collector::Tpm],
+            Tab::Baseboard => &[Collector::Baseboard],
+            Tab::Disk => &[Collector::Disk],
+            Tab::Processor => &[Collector::Processor],
+            Tab::Chassis => &[Collector::Chassis],
+            Tab::Network => &[Collector::Network],
+            Tab::Monitor => &[Collector::Monitor],
+            Tab::Gpu => &[Collector::Gpu],
+            Tab::Watch | Tab::Diff => &[],
+            Tab::Advanced => &[Collector::Platform],
+        }
+    }
+
+    /// Snapshot fields shown on the tab, used to place changes between collections
+    pub fn sections(&self) -> &'static [&'static str] {
+        match self {
+            Tab::System => &["system"],
+            Tab::Bios => &["bios", "firmware", "tpm"],
+            Tab::Baseboard => &["baseboard"],
+            Tab::Disk => &["disks"],
+            Tab::Processor => &["processor"],
+            Tab::Chassis => &["chassis"],
+            Tab::Network => &["network"],
+            Tab::Monitor => &["monitors"],
+            Tab::Gpu => &["gpus"],
+            Tab::Watch | Tab::Diff => &[],
+            Tab::Advanced => &["locked", "virtualization"],
+        }
+    }
+
     pub fn icon(&self) -> &'static str {
         match self {
             Tab::System => "💻",
@@ -70,17 +125,37 @@ impl Tab {
             Tab::Network => "🌐",
             Tab::Monitor => "🖥️",
             Tab::Gpu => "🎮",
+            Tab::Watch => "👁",
+            Tab::Diff => "🔀",
             Tab::Advanced => "🔬",
         }
     }
 }
 
+/// Where the `S` key writes the current snapshot
+pub const SNAPSHOT_FILE: &str = "snapshot.json";
+
+/// Progress of the collectors behind a tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TabState {
+    Loading,
+    Ready,
+    /// The tab's own collector failed, there is nothing real to show
+    Failed(Vec<String>),
+    /// A supporting collector failed, the rest of the tab is valid
+    Degraded(Vec<String>),
+}
+
 pub struct App {
+    /// Tabs shown in the sidebar, `current_tab` indexes into them
+    pub tabs: Vec<Tab>,
     pub current_tab: usize,
     pub scroll_offset: u16,
     pub status_message: Option<String>,
     pub system_info: SystemInfo,
     pub bios_info: BiosInfo,
+    pub firmware_status: FirmwareStatus,
+    pub tpm_info: TpmInfo,
     pub baseboard_info: BaseboardInfo,
     pub disk_info: DiskInfo,
     pub processor_info: ProcessorInfo,
@@ -90,65 +165,413 @@ pub struct App {
     pub gpu_info: GpuInfo,
     // Advanced mode fields
     pub locked_info: LockedMotherboardInfo,
+    pub virtualization_info: VirtualizationInfo,
     pub previous_serials: Option<PreviousSerials>,
+    /// Export the previous serials were read from
+    pub previous_serials_path: Option<PathBuf>,
+    pub export_target: ExportTarget,
+    pub compliance: Option<ComplianceReport>,
+    pub collection: Vec<CollectorReport>,
+    /// Advanced on every UI tick, drives the loading spinner
+    pub tick: usize,
+    /// Values that differ from the collection before the last refresh
+    pub changes: Vec<Change>,
+    options: CollectOptions,
+    run: Option<CollectionRun>,
+    policy: Option<Policy>,
+    /// Data as it was when the running refresh started
+    before_refresh: Option<Snapshot>,
+    pub watch_options: WatchOptions,
+    /// Set while watch mode is on
+    pub watch: Option<Watcher>,
+    /// What the Diff tab compares against
+    pub baseline: Option<Baseline>,
+    /// A baseline loaded from a file stays until another is loaded
+    baseline_pinned: bool,
+    pub diff_hide_unchanged: bool,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// Identifiers are drawn with all but their last characters hidden
+    pub masked: bool,
+    /// Set when exports replace identifiers with salted hashes
+    pub redactor: Option<Redactor>,
+    pub signer: Option<ExportSigner>,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let locked_info = LockedMotherboardInfo::detect();
-        let previous_serials = Self::load_previous_serials();
-        
-        Self {
+    pub fn new(options: &CollectOptions) -> Self {
+        let system_info = SystemInfo::default();
+        let bios_info = BiosInfo::default();
+        let baseboard_info = BaseboardInfo::default();
+        let disk_info = DiskInfo::default();
+        let network_info = NetworkInfo::default();
+        let firmware_status = FirmwareStatus::assess(&bios_info, &system_info, &baseboard_info, &options.firmware);
+        let virtualization_info = VirtualizationInfo::assess(
+            &system_info,
+            &bios_info,
+            &baseboard_info,
+            &network_info,
+            &disk_info,
+        );
+
+        let mut app = Self {
+            tabs: Tab::all().to_vec(),
             current_tab: 0,
             scroll_offset: 0,
             status_message: None,
-            system_info: SystemInfo::collect(),
-            bios_info: BiosInfo::collect(),
-            baseboard_info: BaseboardInfo::collect(),
-            disk_info: DiskInfo::collect(),
-            processor_info: ProcessorInfo::collect(),
-            chassis_info: ChassisInfo::collect(),
-            network_info: NetworkInfo::collect(),
-            monitor_info: MonitorInfo::collect(),
-            gpu_info: GpuInfo::collect(),
-            locked_info,
-            previous_serials,
+            system_info,
+            bios_info,
+            firmware_status,
+            tpm_info: TpmInfo::default(),
+            baseboard_info,
+            disk_info,
+            processor_info: ProcessorInfo::default(),
+            chassis_info: ChassisInfo::default(),
+            network_info,
+            monitor_info: MonitorInfo::default(),
+            gpu_info: GpuInfo::default(),
+            locked_info: LockedMotherboardInfo::default(),
+            virtualization_info,
+            previous_serials: None,
+            previous_serials_path: None,
+            export_target: ExportTarget::default(),
+            compliance: None,
+            collection: Vec::new(),
+            tick: 0,
+            options: options.clone(),
+            run: Some(CollectionRun::start(&Collector::ALL, options)),
+            policy: None,
+            changes: Vec::new(),
+            before_refresh: None,
+            watch_options: WatchOptions::default(),
+            watch: None,
+            baseline: None,
+            baseline_pinned: false,
+            diff_hide_unchanged: false,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            masked: false,
+            redactor: None,
+            signer: None,
+        };
+        app.reload_previous_serials();
+        app
+    }
+
+    /// Collect everything before returning, for headless commands
+    pub fn collect(options: &CollectOptions) -> Self {
+        let mut app = Self::new(options);
+        if let Some(run) = app.run.take() {
+            for completed in run.wait() {
+                app.apply(completed);
+            }
+            app.finish_collection();
         }
+        app
     }
-    
-    /// Load previous serials from export file if it exists
-    fn load_previous_serials() -> Option<PreviousSerials> {
-        fs::read_to_string("serials_export.txt")
-            .ok()
-            .map(|content| PreviousSerials::parse(&content))
+
+    pub fn is_collecting(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Apply whatever collectors finished within `wait`, returns true when data changed
+    pub fn poll_collection(&mut self, wait: Duration) -> bool {
+        let Some(run) = self.run.as_mut() else {
+            return false;
+        };
+
+        let completed = run.poll(wait);
+        let finished = run.is_finished();
+        let changed = !completed.is_empty();
+        for c in completed {
+            self.apply(c);
+        }
+        if finished {
+            self.run = None;
+            self.finish_collection();
+        }
+        changed || finished
+    }
+
+    /// Re-run `collectors` in the background, keeping their current values until new ones arrive
+    pub fn refresh(&mut self, collectors: &[Collector]) {
+        if self.is_collecting() {
+            self.set_status("Collection already in progress".to_string());
+            return;
+        }
+
+        self.before_refresh = Some(Snapshot::from_app(self));
+        self.collection.retain(|r| !collectors.contains(&r.collector));
+        self.run = Some(CollectionRun::start(collectors, &self.options));
+        self.clear_status();
+    }
+
+    /// Compare against a snapshot saved earlier instead of the previous collection
+    pub fn load_baseline(&mut self, path: &Path) {
+        match Baseline::load(path) {
+            Ok(baseline) => {
+                self.baseline = Some(baseline);
+                self.baseline_pinned = true;
+            }
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// Save the current data as snapshot JSON, usable later with --baseline
+    pub fn save_snapshot(&mut self) {
+        let path = Path::new(SNAPSHOT_FILE);
+        match Snapshot::from_app(self).save(path) {
+            Ok(()) => self.set_status(format!("Snapshot saved to {}", SNAPSHOT_FILE)),
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    pub fn toggle_mask(&mut self) {
+        self.masked = !self.masked;
+        let state = if self.masked { "hidden" } else { "shown" };
+        self.set_status(format!("Identifiers {}", state));
+    }
+
+    pub fn toggle_diff_unchanged(&mut self) {
+        self.diff_hide_unchanged = !self.diff_hide_unchanged;
+        self.scroll_offset = 0;
+    }
+
+    /// Start or stop re-collecting everything on the watch interval
+    pub fn toggle_watch(&mut self) {
+        if self.watch.take().is_some() {
+            self.set_status("Watch stopped".to_string());
+            return;
+        }
+
+        let mut watcher = Watcher::new(self.watch_options.clone());
+        // The data on screen is the baseline, or the running collection once it finishes
+        if !self.is_collecting() {
+            let _ = watcher.observe(Snapshot::from_app(self));
+        }
+        self.set_status(format!(
+            "Watching every {}s, logging to {}",
+            watcher.options.interval.as_secs(),
+            watcher.options.log_path.display()
+        ));
+        self.watch = Some(watcher);
+    }
+
+    /// Start the next watch collection once the interval has passed
+    pub fn poll_watch(&mut self) {
+        if self.watch.as_ref().is_some_and(|w| w.is_due()) && !self.is_collecting() {
+            self.refresh(&Collector::ALL);
+        }
+    }
+
+    /// Changes that belong on `tab`
+    pub fn changes_for(&self, tab: Tab) -> Vec<&Change> {
+        self.changes
+            .iter()
+            .filter(|c| tab.sections().contains(&c.section()))
+            .collect()
+    }
+
+    /// Loading, ready or failed, judged by the collectors behind the tab
+    pub fn tab_state(&self, tab: Tab) -> TabState {
+        let mut errors = Vec::new();
+        let mut own_failed = false;
+        for (i, collector) in tab.collectors().iter().enumerate() {
+            match self.collection.iter().find(|r| r.collector == *collector) {
+                None => return TabState::Loading,
+                Some(report) if report.status != CollectorStatus::Ok => {
+                    own_failed |= i == 0;
+                    errors.push(format!("{} collector: {}", collector.label(), report.status.label()));
+                }
+                Some(_) => {}
+            }
+        }
+
+        match (errors.is_empty(), own_failed) {
+            (true, _) => TabState::Ready,
+            (false, true) => TabState::Failed(errors),
+            (false, false) => TabState::Degraded(errors),
+        }
+    }
+
+    /// Store one collector's result (a timed out or failed collector keeps the previous values)
+    fn apply(&mut self, completed: Completed) {
+        let collector = completed.report.collector;
+        self.collection.retain(|r| r.collector != collector);
+        self.collection.push(completed.report);
+        self.collection.sort_by_key(|r| Collector::ALL.iter().position(|c| *c == r.collector));
+
+        match completed.data {
+            Some(Collected::System(info)) => self.system_info = info,
+            Some(Collected::Bios(info)) => self.bios_info = info,
+            Some(Collected::Baseboard(info)) => self.baseboard_info = info,
+            Some(Collected::Disk(info)) => self.disk_info = info,
+            Some(Collected::Processor(info)) => self.processor_info = info,
+            Some(Collected::Chassis(info)) => self.chassis_info = info,
+            Some(Collected::Network(info)) => self.network_info = info,
+            Some(Collected::Monitor(info)) => self.monitor_info = info,
+            Some(Collected::Gpu(info)) => self.gpu_info = info,
+            Some(Collected::Tpm(info)) => self.tpm_info = info,
+            Some(Collected::Platform(info)) => self.locked_info = info,
+            None => {}
+        }
+        self.assess();
+    }
+
+    /// Evaluate a policy loaded during collection and report any collectors that did not finish
+    fn finish_collection(&mut self) {
+        // The export name may use the system UUID, which is only known now
+        self.reload_previous_serials();
+
+        if let Some(policy) = self.policy.take() {
+            self.evaluate_policy(&policy);
+            self.policy = Some(policy);
+        }
+
+        if self.watch.is_some() {
+            let snapshot = Snapshot::from_app(self);
+            if let Some(Err(e)) = self.watch.as_mut().map(|w| w.observe(snapshot)) {
+                self.set_status(e);
+            }
+        }
+
+        if let Some(before) = self.before_refresh.take() {
+            self.changes = Snapshot::from_app(self).diff(&before);
+            if !self.baseline_pinned {
+                self.baseline = Some(Baseline::from_snapshot(
+                    &before,
+                    format!("Previous collection ({})", before.generated),
+                ));
+            }
+            self.set_status(match self.changes.len() {
+                0 => "Refreshed, nothing changed".to_string(),
+                1 => "Refreshed, 1 value changed".to_string(),
+                n => format!("Refreshed, {} values changed", n),
+            });
+        }
+
+        let failed: Vec<&str> = self
+            .collection
+            .iter()
+            .filter(|r| r.status != CollectorStatus::Ok)
+            .map(|r| r.collector.label())
+            .collect();
+        if !failed.is_empty() {
+            self.set_status(format!("Collection incomplete: {}", failed.join(", ")));
+        }
+    }
+
+    /// Recompute the findings derived from several collectors
+    fn assess(&mut self) {
+        self.firmware_status = FirmwareStatus::assess(
+            &self.bios_info,
+            &self.system_info,
+            &self.baseboard_info,
+            &self.options.firmware,
+        );
+        self.virtualization_info = VirtualizationInfo::assess(
+            &self.system_info,
+            &self.bios_info,
+            &self.baseboard_info,
+            &self.network_info,
+            &self.disk_info,
+        );
+    }
+
+    /// Load a posture policy and evaluate it against the collected data
+    ///
+    /// While collectors are still running the policy is held until they finish.
+    pub fn load_policy(&mut self, path: &Path) {
+        match Policy::load(path) {
+            Ok(policy) => {
+                if !self.is_collecting() {
+                    self.evaluate_policy(&policy);
+                }
+                // Kept so a refresh re-evaluates it
+                self.policy = Some(policy);
+            }
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    fn evaluate_policy(&mut self, policy: &Policy) {
+        let report = policy.evaluate(&Snapshot::from_app(self));
+        self.set_status(format!("Policy {}", report.summary()));
+        self.compliance = Some(report);
     }
     
-    /// Reload previous serials (call after export)
+    /// Export to a different directory or file name, comparing against exports found there
+    pub fn set_export_target(&mut self, target: ExportTarget) {
+        self.export_target = target;
+        self.reload_previous_serials();
+    }
+
+    /// Redact exports with `redactor`, or write them in the clear with `None`
+    pub fn set_redactor(&mut self, redactor: Option<Redactor>) {
+        self.redactor = redactor;
+        self.reload_previous_serials();
+    }
+
+    /// Export value for an identifier, its salted hash when redacting
+    pub fn export_identifier(&self, value: &str) -> String {
+        match &self.redactor {
+            Some(redactor) => redactor.redact(value),
+            None => value.to_string(),
+        }
+    }
+
+    /// File name fields for this machine, with the host and UUID hashed when redacting
+    fn name_fields(&self) -> NameFields {
+        let mut fields = NameFields::now(&self.system_info.uuid);
+        fields.host = self.export_identifier(&fields.host);
+        fields.uuid = self.export_identifier(&fields.uuid);
+        fields
+    }
+
+    /// Reload previous serials from the latest export of this machine (call after export)
     pub fn reload_previous_serials(&mut self) {
-        self.previous_serials = Self::load_previous_serials();
+        let fields = self.name_fields();
+        self.previous_serials_path = self.export_target.latest(&fields);
+        self.previous_serials = self
+            .previous_serials_path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| PreviousSerials::parse(&content));
     }
     
-    /// Jump to Advanced tab
-    pub fn goto_advanced(&mut self) {
+    /// Jump to Advanced tab, false when it is not shown
+    pub fn goto_advanced(&mut self) -> bool {
         // Find the index of Advanced tab
-        if let Some(idx) = Tab::all().iter().position(|t| *t == Tab::Advanced) {
+        if let Some(idx) = self.tabs.iter().position(|t| *t == Tab::Advanced) {
             self.current_tab = idx;
             self.scroll_offset = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Show only these tabs, in this order
+    pub fn set_tabs(&mut self, tabs: Vec<Tab>) {
+        if tabs.is_empty() {
+            return;
         }
+        self.tabs = tabs;
+        self.current_tab = 0;
+        self.scroll_offset = 0;
     }
 
     pub fn current_tab(&self) -> Tab {
-        Tab::all()[self.current_tab]
+        self.tabs[self.current_tab]
     }
 
     pub fn next_tab(&mut self) {
-        self.current_tab = (self.current_tab + 1) % Tab::all().len();
+        self.current_tab = (self.current_tab + 1) % self.tabs.len();
         self.scroll_offset = 0;
     }
 
     pub fn previous_tab(&mut self) {
         if self.current_tab == 0 {
-            self.current_tab = Tab::all().len() - 1;
+            self.current_tab = self.tabs.len() - 1;
         } else {
             self.current_tab -= 1;
         }
@@ -173,72 +596,116 @@ impl App {
         self.status_message = None;
     }
 
-    pub fn export_serials(&self) -> Result<String, std::io::Error> {
-        let mut content = String::new();
-        
-        content.push_str("=== SERIAL EXPORT ===\n");
-        content.push_str(&format!("Generated: {}\n\n", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")));
-        
-        // System Info
-        content.push_str("=== SYSTEM ===\n");
-        content.push_str(&format!("Serial Number: {}\n", self.system_info.serial_number));
-        content.push_str(&format!("UUID: {}\n", self.system_info.uuid));
-        content.push_str(&format!("SKU: {}\n\n", self.system_info.sku));
-        
-        // Baseboard Info
-        content.push_str("=== BASEBOARD ===\n");
-        content.push_str(&format!("Serial Number: {}\n", self.baseboard_info.serial_number));
-        content.push_str(&format!("Asset Tag: {}\n\n", self.baseboard_info.asset_tag));
-        
-        // Processor Info
-        content.push_str("=== PROCESSOR ===\n");
-        content.push_str(&format!("Serial Number: {}\n", self.processor_info.serial_number));
-        content.push_str(&format!("Part Number: {}\n\n", self.processor_info.part_number));
-        
-        // Chassis Info
-        content.push_str("=== CHASSIS ===\n");
-        content.push_str(&format!("Serial Number: {}\n", self.chassis_info.serial_number));
-        content.push_str(&format!("Asset Tag: {}\n", self.chassis_info.asset_tag));
-        content.push_str(&format!("SKU: {}\n\n", self.chassis_info.sku));
-        
-        // Disk Info
-        content.push_str("=== DISKS ===\n");
+    pub fn export_serials(&self) -> Result<PathBuf, std::io::Error> {
+        let generated = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let id = |value: &str| self.export_identifier(value);
+        let junk = |path: &str| {
+            self.collection
+                .iter()
+                .flat_map(|report| &report.junk)
+                .find(|junk| junk.path == path)
+                .map(|junk| junk.value.as_str())
+        };
+        let mut export = ExportWriter::new(&generated, &id(&host_name()));
+        if self.redactor.is_some() {
+            export.field("redacted", true);
+        }
+
+        export
+            .section("system")
+            .field("serial_number", id(&self.system_info.serial_number))
+            .placeholder("serial_number", junk("system.serial_number"))
+            .field("uuid", id(&self.system_info.uuid))
+            .placeholder("uuid", junk("system.uuid"))
+            .field("sku", &self.system_info.sku);
+
+        export
+            .section("baseboard")
+            .field("serial_number", id(&self.baseboard_info.serial_number))
+            .placeholder("serial_number", junk("baseboard.serial_number"))
+            .field("asset_tag", id(&self.baseboard_info.asset_tag));
+
+        export
+            .section("processor")
+            .field("serial_number", id(&self.processor_info.serial_number))
+            .placeholder("serial_number", junk("processor.serial_number"))
+            .field("part_number", &self.processor_info.part_number);
+
+        export
+            .section("chassis")
+            .field("serial_number", id(&self.chassis_info.serial_number))
+            .placeholder("serial_number", junk("chassis.serial_number"))
+            .field("asset_tag", id(&self.chassis_info.asset_tag))
+            .field("sku", &self.chassis_info.sku);
+
         for (i, disk) in self.disk_info.disks.iter().enumerate() {
-            content.push_str(&format!("Disk {}: {}\n", i + 1, disk.model));
-            content.push_str(&format!("  Serial (Storage Query): {}\n", disk.storage_query));
-            content.push_str(&format!("  WWN: {}\n", disk.wwn));
+            export
+                .item("disk", i)
+                .field("model", &disk.model)
+                .field("storage_query", id(&disk.storage_query))
+                .placeholder("storage_query", junk(&format!("disks.{}.storage_query", i)))
+                .field("wwn", id(&disk.wwn));
         }
-        content.push('\n');
-        
-        // Network Info
-        content.push_str("=== NETWORK ===\n");
-        for iface in &self.network_info.interfaces {
-            content.push_str(&format!("{}: {}\n", iface.name, iface.mac_address));
+
+        for (i, iface) in self.network_info.interfaces.iter().enumerate() {
+            export
+                .item("network", i)
+                .field("name", &iface.name)
+                .field("mac_address", id(&iface.mac_address))
+                .placeholder("mac_address", junk(&format!("network.{}.mac_address", i)));
         }
-        content.push('\n');
-        
-        // Monitor Info
-        content.push_str("=== MONITORS ===\n");
-        for monitor in &self.monitor_info.monitors {
-            content.push_str(&format!("{}: {}\n", monitor.display_name, monitor.model));
-            content.push_str(&format!("  Serial Number: {}\n", monitor.serial_number));
-            content.push_str(&format!("  ID Serial: {}\n", monitor.id_serial));
-        }
-        content.push('\n');
-        
-        // GPU Info
-        content.push_str("=== GPU ===\n");
-        for gpu in &self.gpu_info.gpus {
-            content.push_str(&format!("{}\n", gpu.name));
-            content.push_str(&format!("  PCI Device: {}\n", gpu.pci_device));
-            content.push_str(&format!("  GUID: {}\n", gpu.guid));
+
+        for (i, monitor) in self.monitor_info.monitors.iter().enumerate() {
+            export
+                .item("monitor", i)
+                .field("display_name", &monitor.display_name)
+                .field("model", &monitor.model)
+                .field("serial_number", id(&monitor.serial_number))
+                .placeholder("serial_number", junk(&format!("monitors.{}.serial_number", i)))
+                .field("id_serial", id(&monitor.id_serial));
         }
-        
+
+        for (i, gpu) in self.gpu_info.gpus.iter().enumerate() {
+            export
+                .item("gpu", i)
+                .field("name", &gpu.name)
+                .field("pci_device", id(&gpu.pci_device))
+                .field("vendor_device", &gpu.vendor_device)
+                .field("subsystem", &gpu.subsystem)
+                .field("board_serial", id(&gpu.board_serial))
+                .field("class_guid", &gpu.class_guid);
+        }
+
+        let virtualization = &self.virtualization_info;
+        export
+            .section("virtualization")
+            .field("verdict", virtualization.verdict.label())
+            .field("hypervisor", &virtualization.hypervisor)
+            .field("cpuid_hypervisor_bit", virtualization.cpuid_hypervisor_bit)
+            .field("cpuid_vendor", &virtualization.cpuid_vendor);
+        for (i, evidence) in virtualization.evidence.iter().enumerate() {
+            export.field(&format!("evidence.{}", i), evidence);
+        }
+        let content = match &self.signer {
+            Some(signer) => signer.sign(export.finish()),
+            None => export.finish(),
+        };
+
         // Write to file
-        let filename = "serials_export.txt";
-        let mut file = File::create(filename)?;
+        let path = self.export_target.path(&self.name_fields());
+        fs::create_dir_all(&self.export_target.dir)?;
+        let mut file = File::create(&path)?;
         file.write_all(content.as_bytes())?;
         
-        Ok(filename.to_string())
+        Ok(path)
+    }
+
+    /// A path as shown in the UI, with the file name masked while identifiers are hidden
+    pub fn display_path(&self, path: &Path) -> String {
+        if self.masked {
+            mask_file_name(path)
+        } else {
+            path.display().to_string()
+        }
     }
 }
@@ -0,0 +1,259 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use ed25519_dalek::VerifyingKey;
+
+use crate::agent::PushUrl;
+use crate::export::ExportTarget;
+use crate::info::firmware::DEFAULT_MAX_AGE_DAYS;
+use crate::sign;
+use crate::watch::{DEFAULT_INTERVAL_SECS, DEFAULT_LOG};
+
+/// Exit code for a command line that cannot be parsed (EX_USAGE), distinct from every verdict
+pub const EXIT_USAGE: i32 = 64;
+
+pub const USAGE: &str = "\
+Usage: serial-checker [OPTIONS] [COMMAND]
+
+Commands:
+  policy <FILE>            Evaluate a posture policy headless and exit with its verdict
+  watch                    Re-collect on an interval and log hardware changes headless
+  config check             Validate the config file and exit (1 when it has problems)
+  fleet <DIR>              Find identifiers shared by several hosts in a directory of exports
+  serve                    Run an inventory server that stores snapshots pushed by agents
+  agent                    Push a snapshot to the inventory server on an interval
+  verify <FILE>            Check the signature of an export and report any tampering
+
+Options:
+  --config <FILE>          Placeholders, OEM vendors, keys, colors, tabs, export, server, agent
+                           (defaults to config.json in the user config directory)
+  --policy <FILE>          Show compliance against FILE in the Advanced tab
+                           (defaults to ./policy.json when present)
+  --bios-max-age <DAYS>    Flag firmware older than DAYS (default 730)
+  --bios-catalog <FILE>    Latest known BIOS versions per model
+                           (defaults to ./bios_catalog.json when present)
+  --tpm-ek-cert <FILE>     NV index dump of the TPM endorsement key certificate
+  --disk-image <FILE>      Add a disk image to the Disk tab (repeatable)
+  --nvme-identify <FILE>   Saved NVMe Identify Controller data (nvme id-ctrl -b)
+  --nvme-namespace <FILE>  Saved NVMe Identify Namespace data (nvme id-ns -b)
+  --timeout <SECS>         Give up on a collector after SECS (default 15)
+  --interval <SECS>        Time between watch collections and agent pushes (default 60)
+  --watch-log <FILE>       JSON-lines change log (default ./hardware_changes.jsonl)
+  --baseline <FILE>        Snapshot JSON for the Diff tab to compare against
+  --export-dir <DIR>       Directory for serial exports, overrides the config
+                           (default: current directory)
+  --export-name <TEMPLATE> Export file name, may use {host}, {uuid}, {date}, {time},
+                           overrides the config (default serials_export.txt)
+  --redact                 Replace identifiers in exports with salted hashes
+  --sign                   Sign exports with this machine's Ed25519 key
+  --public-key <HEX>       Only key verify trusts, instead of this machine's key and
+                           export.trusted_keys in the config
+  --mask                   Start with identifiers masked (toggle with m)
+  --listen <ADDR>          Address the inventory server listens on, overrides the config
+                           (default 127.0.0.1:8080)
+  --data-dir <DIR>         Where the inventory server stores snapshots, overrides the config
+                           (default ./inventory)
+  --push-url <URL>         Inventory server the agent pushes to, e.g. http://inventory:8080,
+                           overrides the config
+  --once                   Push one snapshot and exit instead of repeating every --interval
+  -h, --help               Print this help
+
+Policy exit codes: 0 compliant, 1 warnings only, 2 non-compliant, 3 policy error
+Fleet exit codes: 0 no collisions, 1 collisions found, 2 directory error
+Verify exit codes: 0 valid, 1 tampered, 2 not signed, 3 file error, 4 untrusted signer
+Every command exits with 64 when its command line is invalid";
+
+/// What the user asked the binary to do
+#[derive(Debug, Clone)]
+pub enum Command {
+    Tui,
+    Policy { path: PathBuf },
+    Watch,
+    ConfigCheck,
+    Fleet { dir: PathBuf },
+    Verify { path: PathBuf },
+    Serve,
+    Agent,
+    Help,
+}
+
+/// Options shared by every command
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub config: Option<PathBuf>,
+    pub policy: Option<PathBuf>,
+    pub bios_max_age_days: i64,
+    pub bios_catalog: Option<PathBuf>,
+    pub tpm_ek_cert: Option<PathBuf>,
+    pub disk_images: Vec<PathBuf>,
+    pub nvme_identify: Option<PathBuf>,
+    pub nvme_namespace: Option<PathBuf>,
+    pub timeout_secs: Option<u64>,
+    pub watch_interval_secs: u64,
+    pub watch_log: PathBuf,
+    pub baseline: Option<PathBuf>,
+    pub export_dir: Option<PathBuf>,
+    pub export_name: Option<String>,
+    pub redact: bool,
+    pub sign: bool,
+    pub public_key: Option<VerifyingKey>,
+    pub mask: bool,
+    pub listen: Option<SocketAddr>,
+    pub data_dir: Option<PathBuf>,
+    pub push_url: Option<PushUrl>,
+    pub once: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            config: None,
+            policy: None,
+            bios_max_age_days: DEFAULT_MAX_AGE_DAYS,
+            bios_catalog: None,
+            tpm_ek_cert: None,
+            disk_images: Vec::new(),
+            nvme_identify: None,
+            nvme_namespace: None,
+            timeout_secs: None,
+            watch_interval_secs: DEFAULT_INTERVAL_SECS,
+            watch_log: PathBuf::from(DEFAULT_LOG),
+            baseline: None,
+            export_dir: None,
+            export_name: None,
+            redact: false,
+            sign: false,
+            public_key: None,
+            mask: false,
+            listen: None,
+            data_dir: None,
+            push_url: None,
+            once: false,
+        }
+    }
+}
+
+impl Command {
+    /// Parse arguments (without the program name)
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<(Self, Options), String> {
+        let mut command = Command::Tui;
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok((Command::Help, options)),
+                "--config" => {
+                    let path = args.next().ok_or("--config requires a file")?;
+                    options.config = Some(PathBuf::from(path));
+                }
+                "--policy" => {
+                    let path = args.next().ok_or("--policy requires a file")?;
+                    options.policy = Some(PathBuf::from(path));
+                }
+                "--bios-max-age" => {
+                    let days = args.next().ok_or("--bios-max-age requires a number of days")?;
+                    options.bios_max_age_days = days
+                        .parse()
+                        .map_err(|_| format!("Invalid --bios-max-age: {}", days))?;
+                }
+                "--bios-catalog" => {
+                    let path = args.next().ok_or("--bios-catalog requires a file")?;
+                    options.bios_catalog = Some(PathBuf::from(path));
+                }
+                "--tpm-ek-cert" => {
+                    let path = args.next().ok_or("--tpm-ek-cert requires a file")?;
+                    options.tpm_ek_cert = Some(PathBuf::from(path));
+                }
+                "--disk-image" => {
+                    let path = args.next().ok_or("--disk-image requires a file")?;
+                    options.disk_images.push(PathBuf::from(path));
+                }
+                "--nvme-identify" => {
+                    let path = args.next().ok_or("--nvme-identify requires a file")?;
+                    options.nvme_identify = Some(PathBuf::from(path));
+                }
+                "--nvme-namespace" => {
+                    let path = args.next().ok_or("--nvme-namespace requires a file")?;
+                    options.nvme_namespace = Some(PathBuf::from(path));
+                }
+                "--timeout" => {
+                    let secs = args.next().ok_or("--timeout requires a number of seconds")?;
+                    let secs: u64 = secs
+                        .parse()
+                        .ok()
+                        .filter(|&s| s > 0)
+                        .ok_or_else(|| format!("Invalid --timeout: {}", secs))?;
+                    options.timeout_secs = Some(secs);
+                }
+                "--interval" => {
+                    let secs = args.next().ok_or("--interval requires a number of seconds")?;
+                    options.watch_interval_secs = secs
+                        .parse()
+                        .ok()
+                        .filter(|&s| s > 0)
+                        .ok_or_else(|| format!("Invalid --interval: {}", secs))?;
+                }
+                "--watch-log" => {
+                    let path = args.next().ok_or("--watch-log requires a file")?;
+                    options.watch_log = PathBuf::from(path);
+                }
+                "--baseline" => {
+                    let path = args.next().ok_or("--baseline requires a file")?;
+                    options.baseline = Some(PathBuf::from(path));
+                }
+                "--export-dir" => {
+                    let dir = args.next().ok_or("--export-dir requires a directory")?;
+                    options.export_dir = Some(PathBuf::from(dir));
+                }
+                "--export-name" => {
+                    let template = args.next().ok_or("--export-name requires a file name template")?;
+                    ExportTarget::validate_template(&template)?;
+                    options.export_name = Some(template);
+                }
+                "--redact" => options.redact = true,
+                "--sign" => options.sign = true,
+                "--public-key" => {
+                    let key = args.next().ok_or("--public-key requires a hex key")?;
+                    options.public_key = Some(sign::parse_public_key(&key)?);
+                }
+                "--mask" => options.mask = true,
+                "--listen" => {
+                    let addr = args.next().ok_or("--listen requires an address")?;
+                    options.listen = Some(addr.parse().map_err(|_| format!("Invalid --listen: {}", addr))?);
+                }
+                "--data-dir" => {
+                    let path = args.next().ok_or("--data-dir requires a directory")?;
+                    options.data_dir = Some(PathBuf::from(path));
+                }
+                "--push-url" => {
+                    let url = args.next().ok_or("--push-url requires a URL")?;
+                    options.push_url = Some(PushUrl::parse(&url)?);
+                }
+                "--once" => options.once = true,
+                "policy" => {
+                    let path = args.next().ok_or("policy requires a file")?;
+                    command = Command::Policy { path: PathBuf::from(path) };
+                }
+                "watch" => command = Command::Watch,
+                "serve" => command = Command::Serve,
+                "agent" => command = Command::Agent,
+                "fleet" => {
+                    let dir = args.next().ok_or("fleet requires a directory")?;
+                    command = Command::Fleet { dir: PathBuf::from(dir) };
+                }
+                "verify" => {
+                    let path = args.next().ok_or("verify requires a file")?;
+                    command = Command::Verify { path: PathBuf::from(path) };
+                }
+                "config" => match args.next().as_deref() {
+                    Some("check") => command = Command::ConfigCheck,
+                    Some(other) => return Err(format!("Unknown config command: {}", other)),
+                    None => return Err("config requires a command: check".to_string()),
+                },
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        Ok((command, options))
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::info::{
+    system::SystemInfo,
+    bios::BiosInfo,
+    baseboard::BaseboardInfo,
+    disk::DiskInfo,
+    processor::ProcessorInfo,
+    chassis::ChassisInfo,
+    network::NetworkInfo,
+    monitor::MonitorInfo,
+    gpu::GpuInfo,
+    advanced::LockedMotherboardInfo,
+    tpm::TpmInfo,
+    placeholder::JunkValue,
+    CollectOptions,
+};
+
+/// Time a single collector may run before it is abandoned
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Worker threads sharing the collector queue (each keeps its own WMI sessions)
+const MAX_WORKERS: usize = 4;
+
+/// Name given to worker threads so the panic hook can recognise them
+const WORKER_NAME: &str = "collector";
+
+/// One independent source of information
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Collector {
+    System,
+    Bios,
+    Baseboard,
+    Disk,
+    Processor,
+    Chassis,
+    Network,
+    Monitor,
+    Gpu,
+    Tpm,
+    Platform,
+}
+
+impl Collector {
+    pub const ALL: [Collector; 11] = [
+        Collector::System,
+        Collector::Bios,
+        Collector::Baseboard,
+        Collector::Disk,
+        Collector::Processor,
+        Collector::Chassis,
+        Collector::Network,
+        Collector::Monitor,
+        Collector::Gpu,
+        Collector::Tpm,
+        Collector::Platform,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Collector::System => "System",
+            Collector::Bios => "BIOS",
+            Collector::Baseboard => "Baseboard",
+            Collector::Disk => "Disk",
+            Collector::Processor => "Processor",
+            Collector::Chassis => "Chassis",
+            Collector::Network => "Network",
+            Collector::Monitor => "Monitor",
+            Collector::Gpu => "GPU",
+            Collector::Tpm => "TPM",
+            Collector::Platform => "Platform security",
+        }
+    }
+
+    fn run(self, options: &CollectOptions) -> Collected {
+        match self {
+            Collector::System => Collected::System(SystemInfo::collect()),
+            Collector::Bios => Collected::Bios(BiosInfo::collect()),
+            Collector::Baseboard => Collected::Baseboard(BaseboardInfo::collect()),
+            Collector::Disk => Collected::Disk(DiskInfo::collect(options)),
+            Collector::Processor => Collected::Processor(ProcessorInfo::collect()),
+            Collector::Chassis => Collected::Chassis(ChassisInfo::collect()),
+            Collector::Network => Collected::Network(NetworkInfo::collect()),
+            Collector::Monitor => Collected::Monitor(MonitorInfo::collect()),
+            Collector::Gpu => Collected::Gpu(GpuInfo::collect()),
+            Collector::Tpm => Collected::Tpm(TpmInfo::collect(options.tpm_ek_cert.as_deref())),
+            Collector::Platform => Collected::Platform(LockedMotherboardInfo::detect(&options.oem_vendors)),
+        }
+    }
+}
+
+/// Output of one collector
+#[derive(Debug, Clone)]
+pub enum Collected {
+    System(SystemInfo),
+    Bios(BiosInfo),
+    Baseboard(BaseboardInfo),
+    Disk(DiskInfo),
+    Processor(ProcessorInfo),
+    Chassis(ChassisInfo),
+    Network(NetworkInfo),
+    Monitor(MonitorInfo),
+    Gpu(GpuInfo),
+    Tpm(TpmInfo),
+    Platform(LockedMotherboardInfo),
+}
+
+impl Collected {
+    /// Identifier fields checked for placeholder values, keyed by their snapshot path
+    fn identifiers(&mut self) -> Vec<(String, &mut String)> {
+        fn field<'a>(path: &str, value: &'a mut String) -> (String, &'a mut String) {
+            (path.to_string(), value)
+        }
+
+        match self {
+            Collected::System(s) => vec![
+                field("system.manufacturer", &mut s.manufacturer),
+                field("system.product_name", &mut s.product_name),
+                field("system.version", &mut s.version),
+                field("system.serial_number", &mut s.serial_number),
+                field("system.uuid", &mut s.uuid),
+                field("system.family", &mut s.family),
+                field("system.sku", &mut s.sku),
+            ],
+            Collected::Bios(b) => vec![
+                field("bios.vendor", &mut b.vendor),
+                field("bios.version", &mut b.version),
+            ],
+            Collected::Baseboard(b) => vec![
+                field("baseboard.manufacturer", &mut b.manufacturer),
+                field("baseboard.product_name", &mut b.product_name),
+                field("baseboard.version", &mut b.version),
+                field("baseboard.serial_number", &mut b.serial_number),
+                field("baseboard.asset_tag", &mut b.asset_tag),
+            ],
+            Collected::Processor(p) => vec![
+                field("processor.serial_number", &mut p.serial_number),
+                field("processor.part_number", &mut p.part_number),
+                field("processor.asset_tag", &mut p.asset_tag),
+            ],
+            Collected::Chassis(c) => vec![
+                field("chassis.manufacturer", &mut c.manufacturer),
+                field("chassis.version", &mut c.version),
+                field("chassis.serial_number", &mut c.serial_number),
+                field("chassis.asset_tag", &mut c.asset_tag),
+                field("chassis.sku", &mut c.sku),
+            ],
+            Collected::Disk(d) => d
+                .disks
+                .iter_mut()
+                .enumerate()
+                .flat_map(|(i, disk)| {
+                    [
+                        (format!("disks.{}.model", i), &mut disk.model),
+                        (format!("disks.{}.storage_query", i), &mut disk.storage_query),
+                        (format!("disks.{}.smart_data", i), &mut disk.smart_data),
+                        (format!("disks.{}.wwn", i), &mut disk.wwn),
+                        (format!("disks.{}.scsi", i), &mut disk.scsi),
+                        (format!("disks.{}.ata", i), &mut disk.ata),
+                    ]
+                })
+                .collect(),
+            Collected::Network(n) => n
+                .interfaces
+                .iter_mut()
+                .enumerate()
+                .map(|(i, interface)| (format!("network.{}.mac_address", i), &mut interface.mac_address))
+                .collect(),
+            Collected::Monitor(m) => m
+                .monitors
+                .iter_mut()
+                .enumerate()
+                .flat_map(|(i, monitor)| {
+                    [
+                        (format!("monitors.{}.manufacturer", i), &mut monitor.manufacturer),
+                        (format!("monitors.{}.model", i), &mut monitor.model),
+                        (format!("monitors.{}.serial_number", i), &mut monitor.serial_number),
+                        (format!("monitors.{}.id_serial", i), &mut monitor.id_serial),
+                    ]
+                })
+                .collect(),
+            Collected::Gpu(g) => g
+                .gpus
+                .iter_mut()
+                .enumerate()
+                .flat_map(|(i, gpu)| {
+                    [
+                        (format!("gpus.{}.subsystem", i), &mut gpu.subsystem),
+                        (format!("gpus.{}.board_serial", i), &mut gpu.board_serial),
+                    ]
+                })
+                .collect(),
+            Collected::Tpm(_) | Collected::Platform(_) => Vec::new(),
+        }
+    }
+}
+
+/// How a collector ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectorStatus {
+    Ok,
+    TimedOut,
+    Panicked(String),
+}
+
+impl CollectorStatus {
+    pub fn label(&self) -> String {
+        match self {
+            CollectorStatus::Ok => "OK".to_string(),
+            CollectorStatus::TimedOut => "Timed out".to_string(),
+            CollectorStatus::Panicked(message) => format!("Failed: {}", message),
+        }
+    }
+}
+
+/// Duration and outcome of one collector
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectorReport {
+    pub collector: Collector,
+    pub status: CollectorStatus,
+    pub duration_ms: u64,
+    /// Values replaced with N/A because they were placeholders
+    pub junk: Vec<JunkValue>,
+}
+
+/// A finished (or abandoned) collector, with its data when it produced any
+#[derive(Debug, Clone)]
+pub struct Completed {
+    pub report: CollectorReport,
+    pub data: Option<Collected>,
+}
+
+enum Message {
+    Started(Collector),
+    Finished(Collector, Box<thread::Result<Collected>>, Duration),
+}
+
+/// Collectors running on a small worker pool, each with its own deadline
+pub struct CollectionRun {
+    rx: Receiver<Message>,
+    tx: Sender<Message>,
+    queue: Arc<Mutex<VecDeque<Collector>>>,
+    options: Arc<CollectOptions>,
+    waiting: Vec<Collector>,
+    running: Vec<(Collector, Instant)>,
+    timeout: Duration,
+}
+
+impl CollectionRun {
+    pub fn start(collectors: &[Collector], options: &CollectOptions) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let run = Self {
+            rx,
+            tx,
+            queue: Arc::new(Mutex::new(collectors.iter().copied().collect())),
+            options: Arc::new(options.clone()),
+            waiting: collectors.to_vec(),
+            running: Vec::new(),
+            timeout: options.timeout.unwrap_or(DEFAULT_TIMEOUT),
+        };
+
+        for _ in 0..collectors.len().min(MAX_WORKERS) {
+            run.spawn_worker();
+        }
+        run
+    }
+
+    /// Run collectors to completion (or timeout) and return every result
+    pub fn wait(mut self) -> Vec<Completed> {
+        let mut completed = Vec::new();
+        while !self.is_finished() {
+            completed.extend(self.poll(Duration::from_millis(100)));
+        }
+        completed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.waiting.is_empty() && self.running.is_empty()
+    }
+
+    /// Wait up to `wait` for progress, returning the collectors that finished or timed out
+    pub fn poll(&mut self, wait: Duration) -> Vec<Completed> {
+        let deadline = Instant::now() + wait;
+        let mut done = Vec::new();
+
+        loop {
+            while let Ok(message) = self.rx.try_recv() {
+                self.handle(message, &mut done);
+            }
+            self.expire(&mut done);
+            let now = Instant::now();
+            if self.is_finished() || !done.is_empty() || now >= deadline {
+                break;
+            }
+
+            let next_expiry = self
+                .running
+                .iter()
+                .map(|(_, started)| *started + self.timeout)
+                .min()
+                .unwrap_or(deadline)
+                .min(deadline);
+
+            match self.rx.recv_timeout(next_expiry.saturating_duration_since(now)) {
+                Ok(message) => self.handle(message, &mut done),
+                Err(RecvTimeoutError::Timeout) => {}
+                // Unreachable while we hold a sender, kept so a logic error cannot spin
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        done
+    }
+
+    fn handle(&mut self, message: Message, done: &mut Vec<Completed>) {
+        match message {
+            Message::Started(collector) => {
+                self.waiting.retain(|c| *c != collector);
+                self.running.push((collector, Instant::now()));
+            }
+            Message::Finished(collector, result, duration) => {
+                // Results arriving after the deadline are dropped, the timeout was already reported
+                let Some(pos) = self.running.iter().position(|(c, _)| *c == collector) else {
+                    return;
+                };
+                self.running.remove(pos);
+
+                let (status, data, junk) = match *result {
+                    Ok(mut data) => {
+                        let junk = self.options.placeholders.scrub(data.identifiers());
+                        (CollectorStatus::Ok, Some(data), junk)
+                    }
+                    Err(panic) => (CollectorStatus::Panicked(panic_message(&panic)), None, Vec::new()),
+                };
+                done.push(Completed {
+                    report: CollectorReport {
+                        collector,
+                        status,
+                        duration_ms: duration.as_millis() as u64,
+                        junk,
+                    },
+                    data,
+                });
+            }
+        }
+    }
+
+    /// Abandon collectors past their deadline and replace the worker stuck on each
+    fn expire(&mut self, done: &mut Vec<Completed>) {
+        let now = Instant::now();
+        let timeout = self.timeout;
+        let (expired, running): (Vec<_>, Vec<_>) = self
+            .running
+            .drain(..)
+            .partition(|(_, started)| now.duration_since(*started) >= timeout);
+        self.running = running;
+
+        for (collector, _) in expired {
+            done.push(Completed {
+                report: CollectorReport {
+                    collector,
+                    status: CollectorStatus::TimedOut,
+                    duration_ms: timeout.as_millis() as u64,
+                    junk: Vec::new(),
+                },
+                data: None,
+            });
+            if !self.waiting.is_empty() {
+                self.spawn_worker();
+            }
+        }
+    }
+
+    fn spawn_worker(&self) {
+        let queue = Arc::clone(&self.queue);
+        let options = Arc::clone(&self.options);
+        let tx = self.tx.clone();
+
+        thread::Builder::new().name(WORKER_NAME.to_string()).spawn(move || {
+            while let Some(collector) = queue.lock().ok().and_then(|mut q| q.pop_front()) {
+                if tx.send(Message::Started(collector)).is_err() {
+                    break;
+                }
+
+                let started = Instant::now();
+                let result = panic::catch_unwind(AssertUnwindSafe(|| collector.run(&options)));
+                if tx.send(Message::Finished(collector, Box::new(result), started.elapsed())).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn collector thread");
+    }
+}
+
+/// Keep collector panics off the terminal, they are reported in the collector status instead
+pub fn quiet_worker_panics() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(WORKER_NAME) {
+            default_hook(info);
+        }
+    }));
+}
+
+fn panic_message(panic: &Box<dyn std::any::Any + Send>) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "collector panicked".to_string())
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::VerifyingKey;
+use serde::Deserialize;
+
+use crate::agent::PushUrl;
+use crate::app::Tab;
+use crate::export::ExportTarget;
+use crate::info::advanced::OemVendor;
+use crate::info::placeholder::Placeholders;
+use crate::keys::{parse_key, Action, Keymap};
+use crate::sign;
+use crate::ui::Theme;
+
+/// Name of the config file inside the user config directory
+pub const CONFIG_FILE: &str = "config.json";
+
+/// Directory under the user config directory
+const APP_DIR: &str = "serial-checker";
+
+/// The file as written, every field optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    placeholders: Option<Vec<String>>,
+    oem_vendors: Option<Vec<OemVendor>>,
+    keys: BTreeMap<String, Vec<String>>,
+    colors: BTreeMap<String, String>,
+    tabs: Option<Vec<String>>,
+    export: ExportConfig,
+    server: ServerConfig,
+    agent: AgentConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ExportConfig {
+    dir: Option<PathBuf>,
+    name: Option<String>,
+    redact: bool,
+    salt: Option<String>,
+    sign: bool,
+    trusted_keys: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerConfig {
+    listen: Option<String>,
+    data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AgentConfig {
+    url: Option<String>,
+}
+
+/// Settings from the config file, defaults for anything it leaves out
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub placeholders: Placeholders,
+    pub oem_vendors: Vec<OemVendor>,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub tabs: Vec<Tab>,
+    pub export_dir: Option<PathBuf>,
+    pub export_name: Option<String>,
+    pub redact: bool,
+    pub redaction_salt: Option<String>,
+    pub sign: bool,
+    pub trusted_keys: Vec<VerifyingKey>,
+    pub listen: Option<SocketAddr>,
+    pub data_dir: Option<PathBuf>,
+    pub push_url: Option<PushUrl>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            placeholders: Placeholders::default(),
+            oem_vendors: OemVendor::defaults(),
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            tabs: Tab::all().to_vec(),
+            export_dir: None,
+            export_name: None,
+            redact: false,
+            redaction_salt: None,
+            sign: false,
+            trusted_keys: Vec::new(),
+            listen: None,
+            data_dir: None,
+            push_url: None,
+        }
+    }
+}
+
+impl Config {
+    /// `%APPDATA%\serial-checker\config.json` on Windows,
+    /// `$XDG_CONFIG_HOME/serial-checker/config.json` or `~/.config/serial-checker/config.json` elsewhere
+    pub fn default_path() -> Option<PathBuf> {
+        Self::dir().map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    /// Directory holding the config file and files the tool generates for the user
+    pub fn dir() -> Option<PathBuf> {
+        let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        let base = if cfg!(windows) {
+            var("APPDATA")
+        } else {
+            var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+        };
+        base.map(|dir| dir.join(APP_DIR))
+    }
+
+    /// Read and validate a config file, listing every problem found
+    pub fn load(path: &Path) -> Result<Self, Vec<String>> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| vec![format!("Cannot read config {}: {}", path.display(), e)])?;
+        let file: ConfigFile = serde_json::from_str(&content)
+            .map_err(|e| vec![format!("Invalid config {}: {}", path.display(), e)])?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: ConfigFile) -> Result<Self, Vec<String>> {
+        let mut config = Self::default();
+        let mut errors = Vec::new();
+
+        if let Some(patterns) = file.placeholders {
+            for (i, pattern) in patterns.iter().enumerate() {
+                if pattern.trim().is_empty() {
+                    errors.push(format!("placeholders[{}] is empty", i));
+                } else if pattern.trim().chars().all(|c| c == '*') {
+                    errors.push(format!("placeholders[{}] \"{}\" would hide every value", i, pattern));
+                }
+            }
+            config.placeholders = Placeholders::new(patterns);
+        }
+
+        if let Some(vendors) = file.oem_vendors {
+            for (i, vendor) in vendors.iter().enumerate() {
+                if vendor.pattern.trim().is_empty() {
+                    errors.push(format!("oem_vendors[{}].match is empty", i));
+                }
+                if vendor.name.trim().is_empty() {
+                    errors.push(format!("oem_vendors[{}].name is empty", i));
+                }
+            }
+            config.oem_vendors = vendors
+                .into_iter()
+                .map(|vendor| OemVendor {
+                    pattern: vendor.pattern.trim().to_lowercase(),
+                    ..vendor
+                })
+                .collect();
+        }
+
+        for (name, keys) in &file.keys {
+            let Some(action) = Action::from_name(name) else {
+                let names: Vec<&str> = Action::ALL.iter().map(Action::name).collect();
+                errors.push(format!("keys: unknown action \"{}\", expected one of {}", name, names.join(", ")));
+                continue;
+            };
+            if action == Action::Quit && keys.is_empty() {
+                errors.push("keys.quit needs at least one key".to_string());
+            }
+            let mut codes = Vec::new();
+            for key in keys {
+                match parse_key(key) {
+                    Ok(code) => codes.push(code),
+                    Err(e) => errors.push(format!("keys.{}: {}", name, e)),
+                }
+            }
+            config.keymap.bind(action, codes);
+        }
+        for conflict in config.keymap.conflicts() {
+            errors.push(format!("keys: {} share a key", conflict));
+        }
+
+        for (role, color) in &file.colors {
+            if let Err(e) = config.theme.set(role, color) {
+                errors.push(format!("colors: {}", e));
+            }
+        }
+
+        if let Some(names) = file.tabs {
+            let mut tabs: Vec<Tab> = Vec::new();
+            for name in &names {
+                match Tab::all().iter().find(|tab| tab.label().eq_ignore_ascii_case(name)) {
+                    Some(tab) if tabs.contains(tab) => errors.push(format!("tabs: \"{}\" is listed twice", name)),
+                    Some(tab) => tabs.push(*tab),
+                    None => {
+                        let labels: Vec<String> = Tab::all().iter().map(|t| t.label().to_lowercase()).collect();
+                        errors.push(format!("tabs: unknown tab \"{}\", expected one of {}", name, labels.join(", ")));
+                    }
+                }
+            }
+            if names.is_empty() {
+                errors.push("tabs must list at least one tab".to_string());
+            }
+            if !tabs.is_empty() {
+                config.tabs = tabs;
+            }
+        }
+
+        if let Some(name) = &file.export.name {
+            if let Err(e) = ExportTarget::validate_template(name) {
+                errors.push(format!("export.name: {}", e));
+            }
+        }
+        config.export_dir = file.export.dir;
+        config.export_name = file.export.name;
+        if file.export.salt.as_deref().is_some_and(|salt| salt.trim().is_empty()) {
+            errors.push("export.salt is empty".to_string());
+        }
+        config.redact = file.export.redact;
+        config.redaction_salt = file.export.salt.map(|salt| salt.trim().to_string());
+        config.sign = file.export.sign;
+        for (i, key) in file.export.trusted_keys.iter().enumerate() {
+            match sign::parse_public_key(key) {
+                Ok(key) => config.trusted_keys.push(key),
+                Err(e) => errors.push(format!("export.trusted_keys[{}]: {}", i, e)),
+            }
+        }
+
+        if let Some(listen) = &file.server.listen {
+            match listen.parse() {
+                Ok(addr) => config.listen = Some(addr),
+                Err(_) => errors.push(format!("server.listen: invalid address \"{}\", expected IP:PORT", listen)),
+            }
+        }
+        config.data_dir = file.server.data_dir;
+        if let Some(url) = &file.agent.url {
+            match PushUrl::parse(url) {
+                Ok(url) => config.push_url = Some(url),
+                Err(e) => errors.push(format!("agent.url: {}", e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<Config, Vec<String>> {
+        let file: ConfigFile = serde_json::from_str(json).map_err(|e| vec![e.to_string()])?;
+        Config::from_file(file)
+    }
+
+    fn errors(json: &str) -> Vec<String> {
+        parse(json).expect_err("config should be rejected")
+    }
+
+    fn public_key_hex() -> String {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1; 32]).verifying_key();
+        key.to_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn accepts_a_full_config() {
+        let json = format!(
+            r#"{{
+                "keys": {{"export": ["e", "F5"], "quit": ["Q"]}},
+                "tabs": ["system", "Disk", "gpu"],
+                "export": {{"name": "{{host}}_{{date}}.txt", "salt": " pepper ", "trusted_keys": ["{}"]}},
+                "server": {{"listen": "0.0.0.0:8080"}},
+                "agent": {{"url": "http://inventory:8080/fleet"}}
+            }}"#,
+            public_key_hex()
+        );
+        let config = parse(&json).unwrap();
+        assert_eq!(config.tabs, vec![Tab::System, Tab::Disk, Tab::Gpu]);
+        assert_eq!(config.keymap.hint(Action::Export), "e/F5");
+        assert_eq!(config.redaction_salt.as_deref(), Some("pepper"));
+        assert_eq!(config.trusted_keys.len(), 1);
+        assert_eq!(config.listen, Some("0.0.0.0:8080".parse().unwrap()));
+        assert!(config.push_url.is_some());
+    }
+
+    #[test]
+    fn empty_config_is_the_default() {
+        let config = parse("{}").unwrap();
+        assert_eq!(config.tabs, Tab::all().to_vec());
+        assert!(config.keymap.conflicts().is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(errors(r#"{"colours": {}}"#)[0].contains("unknown field `colours`"));
+        assert!(errors(r#"{"export": {"directory": "x"}}"#)[0].contains("unknown field `directory`"));
+    }
+
+    #[test]
+    fn rejects_bad_key_bindings() {
+        let errors = errors(r#"{"keys": {"jump": ["x"], "quit": [], "refresh_tab": ["Foo", "F13"]}}"#);
+        assert!(errors[0].starts_with("keys: unknown action \"jump\", expected one of quit,"), "{:?}", errors);
+        assert!(errors.contains(&"keys.quit needs at least one key".to_string()), "{:?}", errors);
+        assert!(errors.contains(&"keys.refresh_tab: Unknown key \"Foo\"".to_string()), "{:?}", errors);
+        assert!(errors.contains(&"keys.refresh_tab: Unknown key \"F13\"".to_string()), "{:?}", errors);
+    }
+
+    #[test]
+    fn rejects_conflicting_keys() {
+        let errors = errors(r#"{"keys": {"export": ["q"]}}"#);
+        assert_eq!(errors, vec!["keys: q: quit, export share a key".to_string()]);
+    }
+
+    #[test]
+    fn rejects_bad_tabs() {
+        let errors = errors(r#"{"tabs": ["disk", "Disk", "sensors"]}"#);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(errors[0], "tabs: \"Disk\" is listed twice");
+        assert!(errors[1].starts_with("tabs: unknown tab \"sensors\", expected one of system,"));
+        assert_eq!(self::errors(r#"{"tabs": []}"#), vec!["tabs must list at least one tab".to_string()]);
+    }
+
+    #[test]
+    fn rejects_bad_export_settings() {
+        let name = errors(r#"{"export": {"name": "{serial}.txt"}}"#);
+        assert!(name[0].starts_with("export.name: Unknown placeholder {serial}"), "{:?}", name);
+        let path = errors(r#"{"export": {"name": "exports/{host}.txt"}}"#);
+        assert!(path[0].starts_with("export.name: Export name must be a file name"), "{:?}", path);
+        assert_eq!(errors(r#"{"export": {"salt": "  "}}"#), vec!["export.salt is empty".to_string()]);
+
+        let short = &public_key_hex()[..62];
+        let keys = errors(&format!(r#"{{"export": {{"trusted_keys": ["{}", "{}"]}}}}"#, public_key_hex(), short));
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].starts_with("export.trusted_keys[1]: Invalid public key"), "{:?}", keys);
+    }
+
+    #[test]
+    fn rejects_bad_listen_address() {
+        for listen in ["8080", "localhost:8080", "0.0.0.0:99999"] {
+            let errors = errors(&format!(r#"{{"server": {{"listen": "{}"}}}}"#, listen));
+            assert_eq!(
+                errors,
+                vec![format!("server.listen: invalid address \"{}\", expected IP:PORT", listen)]
+            );
+        }
+    }
+
+    #[test]
+    fn lists_every_problem() {
+        let errors = errors(r#"{"placeholders": ["*"], "colors": {"accent": "mauve"}, "agent": {"url": "ftp://x"}}"#);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+    }
+}
@@ -0,0 +1,466 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+
+/// Version written in the header of every export
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Version 1 is the unversioned `=== SECTION ===` layout of older builds
+const FIRST_VERSION: u32 = 2;
+
+/// First line of a versioned export, followed by the version number
+const HEADER: &str = "# serial-checker export v";
+
+/// Export file name used when no template is given, and by older builds
+pub const DEFAULT_TEMPLATE: &str = "serials_export.txt";
+
+/// Appended to a field key for the placeholder text it was collected with
+pub const PLACEHOLDER_SUFFIX: &str = ".placeholder";
+
+/// Placeholders a file name template may use
+const PLACEHOLDERS: [&str; 4] = ["host", "uuid", "date", "time"];
+
+/// Values substituted into the file name template
+#[derive(Debug, Clone)]
+pub struct NameFields {
+    pub host: String,
+    pub uuid: String,
+    pub date: String,
+    pub time: String,
+}
+
+impl NameFields {
+    /// Fields for this machine at the current time
+    pub fn now(uuid: &str) -> Self {
+        let now = chrono::Local::now();
+        Self {
+            host: host_name(),
+            uuid: uuid.to_string(),
+            date: now.format("%Y-%m-%d").to_string(),
+            time: now.format("%H%M%S").to_string(),
+        }
+    }
+}
+
+/// Where exports are written and where the previous one is looked up
+#[derive(Debug, Clone)]
+pub struct ExportTarget {
+    pub dir: PathBuf,
+    pub template: String,
+}
+
+impl Default for ExportTarget {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("."),
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// Piece of a parsed file name template
+enum NamePart<'a> {
+    Text(&'a str),
+    Field(&'a str),
+}
+
+impl ExportTarget {
+    /// Check a template such as "{host}_{date}.txt" before using it
+    pub fn validate_template(template: &str) -> Result<(), String> {
+        if template.contains(['/', '\\']) {
+            return Err(format!("Export name must be a file name, not a path: {}", template));
+        }
+        for part in name_parts(template)? {
+            if let NamePart::Field(name) = part {
+                if !PLACEHOLDERS.contains(&name) {
+                    return Err(format!(
+                        "Unknown placeholder {{{}}} in export name, expected one of {{{}}}",
+                        name,
+                        PLACEHOLDERS.join("}, {")
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Path of a new export
+    pub fn path(&self, fields: &NameFields) -> PathBuf {
+        let name: String = name_parts(&self.template)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|part| match part {
+                NamePart::Text(text) => text.to_string(),
+                NamePart::Field(name) => field_value(name, fields),
+            })
+            .collect();
+        self.dir.join(name)
+    }
+
+    /// Most recent export of this machine
+    ///
+    /// `{host}` and `{uuid}` must match this machine, `{date}` and `{time}` match any value.
+    pub fn latest(&self, fields: &NameFields) -> Option<PathBuf> {
+        let parts = name_parts(&self.template).ok()?;
+        let pattern: Vec<Option<String>> = parts
+            .into_iter()
+            .map(|part| match part {
+                NamePart::Text(text) => Some(text.to_string()),
+                NamePart::Field("date" | "time") => None,
+                NamePart::Field(name) => Some(field_value(name, fields)),
+            })
+            .collect();
+
+        fs::read_dir(&self.dir)
+            .ok()?
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .filter(|entry| entry.file_name().to_str().is_some_and(|name| matches(&pattern, name)))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .max()
+            .map(|(_, path)| path)
+    }
+}
+
+/// Split a template into text and `{placeholder}` parts
+fn name_parts(template: &str) -> Result<Vec<NamePart<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let close = rest[open..]
+            .find('}')
+            .map(|i| open + i)
+            .ok_or_else(|| format!("Unclosed {{ in export name: {}", template))?;
+        if open > 0 {
+            parts.push(NamePart::Text(&rest[..open]));
+        }
+        parts.push(NamePart::Field(&rest[open + 1..close]));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(NamePart::Text(rest));
+    }
+    Ok(parts)
+}
+
+/// Placeholder value made safe for a file name
+fn field_value(name: &str, fields: &NameFields) -> String {
+    let value = match name {
+        "host" => &fields.host,
+        "uuid" => &fields.uuid,
+        "date" => &fields.date,
+        "time" => &fields.time,
+        _ => "",
+    };
+    let value = value.trim();
+    if value.is_empty() || value == "N/A" {
+        return "unknown".to_string();
+    }
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect()
+}
+
+/// Match a file name against template parts, `None` standing for any text
+fn matches(pattern: &[Option<String>], name: &str) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((Some(text), rest)) => name.strip_prefix(text.as_str()).is_some_and(|name| matches(rest, name)),
+        Some((None, rest)) => name
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([name.len()])
+            .any(|i| matches(rest, &name[i..])),
+    }
+}
+
+/// Name of this machine, "unknown-host" when it cannot be found
+pub fn host_name() -> String {
+    let from_env = ["COMPUTERNAME", "HOSTNAME"].iter().find_map(|var| std::env::var(var).ok());
+    from_env
+        .or_else(|| fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown-host".to_string())
+}
+
+/// Builds the versioned text export
+///
+/// The format is line based: `[section]` or `[section.N]` opens a block, `key = value`
+/// sets a field in it and `#` starts a comment. Keys are fixed identifiers, values are
+/// escaped so they can hold any text, including `:`, `=` and line breaks.
+pub struct ExportWriter {
+    out: String,
+}
+
+impl ExportWriter {
+    pub fn new(generated: &str, host: &str) -> Self {
+        let mut writer = Self {
+            out: format!("{}{}\n", HEADER, FORMAT_VERSION),
+        };
+        writer.section("export").field("generated", generated).field("host", host);
+        writer
+    }
+
+    /// Start a section that occurs once
+    pub fn section(&mut self, name: &str) -> &mut Self {
+        self.out.push_str(&format!("\n[{}]\n", name));
+        self
+    }
+
+    /// Start one entry of a list, numbered from 0
+    pub fn item(&mut self, name: &str, index: usize) -> &mut Self {
+        self.out.push_str(&format!("\n[{}.{}]\n", name, index));
+        self
+    }
+
+    pub fn field(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.out.push_str(&format!("{} = {}\n", key, escape(&value.to_string())));
+        self
+    }
+
+    /// Keep the text a field held before it was replaced with N/A as placeholder junk
+    ///
+    /// Template text identifies nothing, so it is written as collected even when redacting;
+    /// `fleet` groups hosts by it.
+    pub fn placeholder(&mut self, key: &str, value: Option<&str>) -> &mut Self {
+        match value {
+            Some(value) => self.field(&format!("{}{}", key, PLACEHOLDER_SUFFIX), value),
+            None => self,
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// One `[section]` or `[section.N]` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSection {
+    pub name: String,
+    pub index: Option<usize>,
+    pub fields: Vec<(String, String)>,
+}
+
+impl ExportSection {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+/// A parsed versioned export
+#[derive(Debug, Clone, PartialEq)]
+pub struct Export {
+    pub version: u32,
+    pub sections: Vec<ExportSection>,
+}
+
+impl Export {
+    /// Parse a versioned export, `None` when the content predates the format
+    ///
+    /// Newer versions are read as far as the sections and keys are understood.
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines().map(|line| line.trim_end_matches('\r'));
+        let version = lines
+            .by_ref()
+            .find(|line| !line.trim().is_empty())?
+            .strip_prefix(HEADER)?
+            .trim()
+            .parse()
+            .ok()
+            .filter(|version| *version >= FIRST_VERSION)?;
+
+        let mut sections: Vec<ExportSection> = Vec::new();
+        for line in lines {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = trimmed.strip_prefix('[').and_then(|h| h.trim_end().strip_suffix(']')) {
+                let (name, index) = match header.rsplit_once('.') {
+                    Some((name, index)) if index.parse::<usize>().is_ok() => (name, index.parse().ok()),
+                    _ => (header, None),
+                };
+                sections.push(ExportSection {
+                    name: name.to_string(),
+                    index,
+                    fields: Vec::new(),
+                });
+                continue;
+            }
+
+            // Fields before the first section have nowhere to go
+            let (Some(section), Some((key, value))) = (sections.last_mut(), trimmed.split_once('=')) else {
+                continue;
+            };
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            section.fields.push((key.trim().to_string(), unescape(value)));
+        }
+
+        Some(Self { version, sections })
+    }
+
+    /// Every section with this name, in file order
+    pub fn sections<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ExportSection> + 'a {
+        self.sections.iter().filter(move |s| s.name == name)
+    }
+
+    pub fn section(&self, name: &str) -> Option<&ExportSection> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+}
+
+/// Backslash-escape the characters that would break a `key = value` line
+pub fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Reverse of `escape`, unknown escapes are kept as written
+pub fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AWKWARD: [&str; 8] = [
+        "Ethernet: 2",
+        "key = value",
+        r"C:\Windows\System32",
+        "line one\nline two",
+        "carriage\rreturn",
+        "tab\tseparated",
+        r"trailing backslash \",
+        r"literal \n not a break",
+    ];
+
+    #[test]
+    fn escape_round_trips() {
+        for value in AWKWARD {
+            assert_eq!(unescape(&escape(value)), value);
+            assert!(!escape(value).contains(['\n', '\r']));
+        }
+    }
+
+    /// Values built from the characters the format has to survive, from a fixed seed
+    fn generated_values(count: usize) -> Vec<String> {
+        const ALPHABET: [char; 18] = [
+            'a', 'Z', '0', ' ', '\\', 'n', 'r', '\n', '\r', '\t', '=', '[', ']', '#', ':', 'é', '✓', '\u{1F5A5}',
+        ];
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..count)
+            .map(|_| {
+                let len = next() % 24;
+                (0..len).map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize]).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generated_values_round_trip() {
+        let values = generated_values(500);
+        let mut writer = ExportWriter::new("2026-10-18 14:25:00", "host");
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(&unescape(&escape(value)), value);
+            writer.item("disk", i).field("model", value).field("serial_number", value);
+        }
+
+        let export = Export::parse(&writer.finish()).unwrap();
+        let disks: Vec<&ExportSection> = export.sections("disk").collect();
+        assert_eq!(disks.len(), values.len());
+        for (section, value) in disks.iter().zip(&values) {
+            assert_eq!(section.get("model"), Some(value.as_str()));
+            assert_eq!(section.get("serial_number"), Some(value.as_str()));
+        }
+    }
+
+    #[test]
+    fn writer_output_parses_back() {
+        let mut writer = ExportWriter::new("2026-10-18 14:25:00", "host=1");
+        writer.section("system").field("serial_number", "ABC123");
+        for (i, value) in AWKWARD.iter().enumerate() {
+            writer.item("network", i).field("name", value).field("index", i);
+        }
+
+        let export = Export::parse(&writer.finish()).unwrap();
+        assert_eq!(export.version, FORMAT_VERSION);
+        assert_eq!(export.section("export").unwrap().get("host"), Some("host=1"));
+        assert_eq!(export.section("system").unwrap().get("serial_number"), Some("ABC123"));
+
+        let networks: Vec<&ExportSection> = export.sections("network").collect();
+        assert_eq!(networks.len(), AWKWARD.len());
+        for (i, (section, value)) in networks.iter().zip(AWKWARD).enumerate() {
+            assert_eq!(section.index, Some(i));
+            assert_eq!(section.get("name"), Some(value));
+            assert_eq!(section.get("index"), Some(i.to_string().as_str()));
+        }
+    }
+
+    #[test]
+    fn parses_section_indices() {
+        let export = Export::parse("# serial-checker export v2\n[disk.12]\nmodel = X\n[ip.v4]\na = b\n").unwrap();
+        assert_eq!((export.sections[0].name.as_str(), export.sections[0].index), ("disk", Some(12)));
+        // Only a numeric suffix is an index
+        assert_eq!((export.sections[1].name.as_str(), export.sections[1].index), ("ip.v4", None));
+    }
+
+    #[test]
+    fn skips_comments_and_crlf() {
+        let export = Export::parse("\r\n# serial-checker export v2\r\n# note\r\n[system]\r\nuuid = 1234\r\n").unwrap();
+        assert_eq!(export.section("system").unwrap().get("uuid"), Some("1234"));
+    }
+
+    #[test]
+    fn legacy_exports_are_not_parsed() {
+        let legacy = "=== SYSTEM INFORMATION ===\nSerial Number: ABC123\nUUID: 1234\n";
+        assert_eq!(Export::parse(legacy), None);
+        assert_eq!(Export::parse("# serial-checker export v1\n[system]\nserial_number = A\n"), None);
+        assert_eq!(Export::parse(""), None);
+    }
+
+    #[test]
+    fn newer_versions_are_read() {
+        let export = Export::parse("# serial-checker export v3\n[system]\nserial_number = A\n").unwrap();
+        assert_eq!(export.version, 3);
+    }
+}
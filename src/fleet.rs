@@ -0,0 +1,519 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::export::{Export, PLACEHOLDER_SUFFIX};
+use crate::info::advanced::PreviousSerials;
+use crate::info::placeholder::Placeholders;
+
+/// Exit codes for `serial-checker fleet`
+pub const EXIT_NO_COLLISIONS: i32 = 0;
+pub const EXIT_COLLISIONS: i32 = 1;
+pub const EXIT_FLEET_ERROR: i32 = 2;
+
+/// How much damage a shared identifier does to an inventory keyed on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Impact {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Impact {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Impact::Low => "low",
+            Impact::Medium => "medium",
+            Impact::High => "high",
+            Impact::Critical => "critical",
+        }
+    }
+}
+
+/// Identifier compared across hosts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IdentifierKind {
+    SystemUuid,
+    SystemSerial,
+    BaseboardSerial,
+    ChassisSerial,
+    MacAddress,
+    DiskSerial,
+    ProcessorSerial,
+    MonitorSerial,
+}
+
+impl IdentifierKind {
+    pub const ALL: [IdentifierKind; 8] = [
+        IdentifierKind::SystemUuid,
+        IdentifierKind::SystemSerial,
+        IdentifierKind::BaseboardSerial,
+        IdentifierKind::ChassisSerial,
+        IdentifierKind::MacAddress,
+        IdentifierKind::DiskSerial,
+        IdentifierKind::ProcessorSerial,
+        IdentifierKind::MonitorSerial,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IdentifierKind::SystemUuid => "System UUID",
+            IdentifierKind::SystemSerial => "System serial",
+            IdentifierKind::BaseboardSerial => "Baseboard serial",
+            IdentifierKind::ChassisSerial => "Chassis serial",
+            IdentifierKind::MacAddress => "MAC address",
+            IdentifierKind::DiskSerial => "Disk serial",
+            IdentifierKind::ProcessorSerial => "Processor serial",
+            IdentifierKind::MonitorSerial => "Monitor serial",
+        }
+    }
+
+    /// UUIDs and system serials are what MDM, licensing and asset tools key machines on;
+    /// monitors legitimately move between desks, so a shared one matters least
+    pub fn impact(&self) -> Impact {
+        match self {
+            IdentifierKind::SystemUuid | IdentifierKind::SystemSerial => Impact::Critical,
+            IdentifierKind::BaseboardSerial | IdentifierKind::ChassisSerial | IdentifierKind::MacAddress => Impact::High,
+            IdentifierKind::DiskSerial | IdentifierKind::ProcessorSerial => Impact::Medium,
+            IdentifierKind::MonitorSerial => Impact::Low,
+        }
+    }
+
+    /// Section and key the identifier is exported under
+    fn export_field(&self) -> (&'static str, &'static str) {
+        match self {
+            IdentifierKind::SystemUuid => ("system", "uuid"),
+            IdentifierKind::SystemSerial => ("system", "serial_number"),
+            IdentifierKind::BaseboardSerial => ("baseboard", "serial_number"),
+            IdentifierKind::ChassisSerial => ("chassis", "serial_number"),
+            IdentifierKind::MacAddress => ("network", "mac_address"),
+            IdentifierKind::DiskSerial => ("disk", "storage_query"),
+            IdentifierKind::ProcessorSerial => ("processor", "serial_number"),
+            IdentifierKind::MonitorSerial => ("monitor", "serial_number"),
+        }
+    }
+
+    /// Comparison key, ignoring case and the separators different tools put in
+    fn key(&self, value: &str) -> String {
+        let value = value.trim().to_uppercase();
+        match self {
+            IdentifierKind::MacAddress => value.chars().filter(char::is_ascii_hexdigit).collect(),
+            IdentifierKind::SystemUuid => value.chars().filter(|c| !matches!(c, '-' | '{' | '}')).collect(),
+            _ => value,
+        }
+    }
+}
+
+/// Identifiers read from one or more exports of the same host
+#[derive(Debug, Clone)]
+pub struct HostRecord {
+    pub host: String,
+    pub exports: usize,
+    pub identifiers: Vec<(IdentifierKind, String)>,
+}
+
+impl HostRecord {
+    fn has(&self, kind: IdentifierKind) -> bool {
+        self.identifiers.iter().any(|(k, _)| *k == kind)
+    }
+}
+
+/// One identifier value found on more than one host
+#[derive(Debug, Clone)]
+pub struct Collision {
+    pub kind: IdentifierKind,
+    pub value: String,
+    pub hosts: Vec<String>,
+    /// Why the value looks like a placeholder, when it does
+    pub placeholder: Option<&'static str>,
+}
+
+/// How far one identifier kind can be trusted as a primary key
+#[derive(Debug, Clone)]
+pub struct KindSummary {
+    pub kind: IdentifierKind,
+    /// Hosts that reported a value
+    pub present: usize,
+    /// Hosts sharing a value with another host
+    pub colliding: usize,
+}
+
+impl KindSummary {
+    /// Present on every host and never shared
+    pub fn trusted(&self, hosts: usize) -> bool {
+        self.present == hosts && self.colliding == 0
+    }
+}
+
+/// Every export in a directory, grouped by host
+#[derive(Debug, Clone)]
+pub struct Fleet {
+    pub dir: String,
+    pub hosts: Vec<HostRecord>,
+    /// Files that were not exports
+    pub skipped: usize,
+}
+
+impl Fleet {
+    /// Read every export in `dir`, versioned or legacy, merging files of the same host
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let entries = fs::read_dir(dir).map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?;
+
+        let mut hosts: BTreeMap<String, HostRecord> = BTreeMap::new();
+        let mut skipped = 0;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Some((host, identifiers)) = fs::read_to_string(&path).ok().and_then(|c| read_export(&path, &c)) else {
+                skipped += 1;
+                continue;
+            };
+
+            let record = hosts.entry(host.to_lowercase()).or_insert_with(|| HostRecord {
+                host,
+                exports: 0,
+                identifiers: Vec::new(),
+            });
+            record.exports += 1;
+            for (kind, value) in identifiers {
+                if !record.identifiers.iter().any(|(k, v)| *k == kind && kind.key(v) == kind.key(&value)) {
+                    record.identifiers.push((kind, value));
+                }
+            }
+        }
+
+        Ok(Self {
+            dir: dir.display().to_string(),
+            hosts: hosts.into_values().collect(),
+            skipped,
+        })
+    }
+
+    pub fn report(&self, placeholders: &Placeholders) -> FleetReport {
+        // kind -> comparison key -> (value as first seen, hosts)
+        let mut seen: BTreeMap<IdentifierKind, BTreeMap<String, (String, BTreeSet<String>)>> = BTreeMap::new();
+        for record in &self.hosts {
+            for (kind, value) in &record.identifiers {
+                seen.entry(*kind)
+                    .or_default()
+                    .entry(kind.key(value))
+                    .or_insert_with(|| (value.clone(), BTreeSet::new()))
+                    .1
+                    .insert(record.host.clone());
+            }
+        }
+
+        let mut collisions: Vec<Collision> = seen
+            .iter()
+            .flat_map(|(kind, values)| {
+                values
+                    .values()
+                    .filter(|(_, hosts)| hosts.len() > 1)
+                    .map(|(value, hosts)| Collision {
+                        kind: *kind,
+                        value: value.clone(),
+                        hosts: hosts.iter().cloned().collect(),
+                        placeholder: placeholders.classify(value).map(|reason| reason.label()),
+                    })
+            })
+            .collect();
+        collisions.sort_by(|a, b| {
+            (b.kind.impact(), b.hosts.len(), a.kind, &a.value).cmp(&(a.kind.impact(), a.hosts.len(), b.kind, &b.value))
+        });
+
+        let kinds = IdentifierKind::ALL
+            .iter()
+            .map(|kind| {
+                let colliding: BTreeSet<&String> = collisions
+                    .iter()
+                    .filter(|c| c.kind == *kind)
+                    .flat_map(|c| &c.hosts)
+                    .collect();
+                KindSummary {
+                    kind: *kind,
+                    present: self.hosts.iter().filter(|h| h.has(*kind)).count(),
+                    colliding: colliding.len(),
+                }
+            })
+            .collect();
+
+        FleetReport {
+            dir: self.dir.clone(),
+            hosts: self.hosts.len(),
+            exports: self.hosts.iter().map(|h| h.exports).sum(),
+            skipped: self.skipped,
+            kinds,
+            collisions,
+        }
+    }
+}
+
+/// Host name and identifiers of one export, `None` when the file is not an export
+fn read_export(path: &Path, content: &str) -> Option<(String, Vec<(IdentifierKind, String)>)> {
+    let serials = PreviousSerials::parse(content);
+    let single = [
+        (IdentifierKind::SystemUuid, &serials.system_uuid),
+        (IdentifierKind::SystemSerial, &serials.system_serial),
+        (IdentifierKind::BaseboardSerial, &serials.baseboard_serial),
+        (IdentifierKind::ChassisSerial, &serials.chassis_serial),
+        (IdentifierKind::ProcessorSerial, &serials.processor_serial),
+    ];
+    let lists = [
+        (IdentifierKind::MacAddress, &serials.network_macs),
+        (IdentifierKind::DiskSerial, &serials.disk_serials),
+        (IdentifierKind::MonitorSerial, &serials.monitor_serials),
+    ];
+
+    let mut identifiers: Vec<(IdentifierKind, String)> = single
+        .into_iter()
+        .filter_map(|(kind, value)| value.clone().map(|v| (kind, v)))
+        .chain(lists.into_iter().flat_map(|(kind, values)| values.iter().map(move |v| (kind, v.clone()))))
+        .collect();
+
+    // Placeholder junk is exported as N/A with the collected text beside it, and hosts
+    // sharing "Default string" are exactly the collisions worth reporting
+    let export = Export::parse(content);
+    for kind in IdentifierKind::ALL {
+        let (section, key) = kind.export_field();
+        let key = format!("{}{}", key, PLACEHOLDER_SUFFIX);
+        let sections = export.iter().flat_map(|export| export.sections(section));
+        identifiers.extend(sections.filter_map(|s| s.get(&key)).map(|value| (kind, value.to_string())));
+    }
+    if export.is_none() && identifiers.is_empty() {
+        return None;
+    }
+
+    // Exports from older builds carry no host name, the file name is the best guess
+    let host = export
+        .as_ref()
+        .and_then(|e| e.section("export")?.get("host").map(str::trim).map(str::to_string))
+        .filter(|host| !host.is_empty())
+        .or_else(|| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "unknown-host".to_string());
+    Some((host, identifiers))
+}
+
+/// Collisions across the fleet and how much each identifier can be trusted
+#[derive(Debug, Clone)]
+pub struct FleetReport {
+    pub dir: String,
+    pub hosts: usize,
+    pub exports: usize,
+    pub skipped: usize,
+    pub kinds: Vec<KindSummary>,
+    /// Worst first
+    pub collisions: Vec<Collision>,
+}
+
+impl FleetReport {
+    pub fn exit_code(&self) -> i32 {
+        if self.collisions.is_empty() {
+            EXIT_NO_COLLISIONS
+        } else {
+            EXIT_COLLISIONS
+        }
+    }
+}
+
+impl fmt::Display for FleetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fleet: {} hosts from {} exports in {}", self.hosts, self.exports, self.dir)?;
+        if self.skipped > 0 {
+            write!(f, " ({} other files skipped)", self.skipped)?;
+        }
+        writeln!(f)?;
+        writeln!(f)?;
+
+        writeln!(f, "{:<18} {:>7} {:>9} {:>10}  Primary key", "Identifier", "Hosts", "Missing", "Colliding")?;
+        for summary in &self.kinds {
+            let verdict = if summary.trusted(self.hosts) {
+                "yes"
+            } else if summary.colliding > 0 {
+                "no, shared"
+            } else if summary.present == 0 {
+                "no, never reported"
+            } else {
+                "no, missing on some hosts"
+            };
+            writeln!(
+                f,
+                "{:<18} {:>7} {:>9} {:>10}  {}",
+                summary.kind.label(),
+                summary.present,
+                self.hosts - summary.present,
+                summary.colliding,
+                verdict
+            )?;
+        }
+        writeln!(f)?;
+
+        if self.collisions.is_empty() {
+            return write!(f, "No identifier is shared by more than one host");
+        }
+        writeln!(f, "Collisions ({}):", self.collisions.len())?;
+        for collision in &self.collisions {
+            let placeholder = collision.placeholder.map(|p| format!(" ({})", p)).unwrap_or_default();
+            writeln!(
+                f,
+                "[{:<8}] {} \"{}\"{} shared by {} hosts:",
+                collision.kind.impact().label(),
+                collision.kind.label(),
+                collision.value,
+                placeholder,
+                collision.hosts.len()
+            )?;
+            writeln!(f, "           {}", collision.hosts.join(", "))?;
+        }
+        let worst = self.collisions.iter().map(|c| c.kind.impact()).max().unwrap_or(Impact::Low);
+        write!(f, "Result: {} collisions, worst impact {}", self.collisions.len(), worst.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportWriter;
+
+    /// An export of `host` with a system section and one network item per MAC
+    fn export(host: &str, uuid: &str, serial: &str, macs: &[&str]) -> ExportWriter {
+        let mut export = ExportWriter::new("2026-10-18 14:25:00", host);
+        export.section("system").field("serial_number", serial).field("uuid", uuid);
+        for (i, mac) in macs.iter().enumerate() {
+            export.item("network", i).field("name", "eth0").field("mac_address", mac);
+        }
+        export
+    }
+
+    fn fleet_dir(name: &str, files: Vec<(&str, ExportWriter)>) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("serial-checker-fleet-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, export) in files {
+            fs::write(dir.join(file), export.finish()).unwrap();
+        }
+        fs::write(dir.join("notes.md"), "not an export").unwrap();
+        dir
+    }
+
+    fn report(name: &str, files: Vec<(&str, ExportWriter)>) -> FleetReport {
+        let dir = fleet_dir(name, files);
+        let fleet = Fleet::load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        fleet.report(&Placeholders::default())
+    }
+
+    #[test]
+    fn groups_hosts_sharing_a_value() {
+        let uuid = "4C4C4544-0042-3510-8048-B4C04F4E3732";
+        let report = report(
+            "groups",
+            vec![
+                ("a.txt", export("ws-001", uuid, "PF2ABC01", &["00:1A:2B:3C:4D:01"])),
+                ("b.txt", export("ws-002", uuid, "PF2ABC02", &["00:1A:2B:3C:4D:02"])),
+                ("c.txt", export("ws-003", uuid, "PF2ABC03", &["00:1A:2B:3C:4D:03"])),
+                ("d.txt", export("ws-004", "9F0E1D2C-3B4A-5968-7766-554433221100", "PF2ABC04", &["00:1A:2B:3C:4D:04"])),
+            ],
+        );
+        assert_eq!((report.hosts, report.exports, report.skipped), (4, 4, 1));
+        assert_eq!(report.collisions.len(), 1);
+        let collision = &report.collisions[0];
+        assert_eq!(collision.kind, IdentifierKind::SystemUuid);
+        assert_eq!(collision.hosts, ["ws-001", "ws-002", "ws-003"]);
+        assert_eq!(collision.placeholder, None);
+        assert_eq!(report.exit_code(), EXIT_COLLISIONS);
+
+        let summary = |kind| report.kinds.iter().find(|s| s.kind == kind).unwrap();
+        assert!(!summary(IdentifierKind::SystemUuid).trusted(report.hosts));
+        assert!(summary(IdentifierKind::SystemSerial).trusted(report.hosts));
+        assert_eq!(summary(IdentifierKind::DiskSerial).present, 0);
+    }
+
+    #[test]
+    fn orders_collisions_by_impact_then_hosts() {
+        let mut monitors = Vec::new();
+        for (file, host) in [("a.txt", "ws-001"), ("b.txt", "ws-002"), ("c.txt", "ws-003")] {
+            let mut e = export(host, &format!("UUID-{}", host), "SHARED-SN", &[]);
+            e.item("monitor", 0).field("serial_number", "MON-1");
+            if host != "ws-003" {
+                e.item("network", 0).field("mac_address", "00:1A:2B:3C:4D:5E");
+            }
+            monitors.push((file, e));
+        }
+        let report = report("impact", monitors);
+        let order: Vec<(IdentifierKind, usize)> = report.collisions.iter().map(|c| (c.kind, c.hosts.len())).collect();
+        assert_eq!(
+            order,
+            [
+                (IdentifierKind::SystemSerial, 3),
+                (IdentifierKind::MacAddress, 2),
+                (IdentifierKind::MonitorSerial, 3),
+            ]
+        );
+        assert!(report.to_string().ends_with("Result: 3 collisions, worst impact critical"));
+    }
+
+    #[test]
+    fn normalizes_mac_and_uuid_keys() {
+        let uuid = "4c4c4544-0042-3510-8048-b4c04f4e3732";
+        let report = report(
+            "keys",
+            vec![
+                ("a.txt", export("ws-001", uuid, "SN1", &["00:1a:2b:3c:4d:5e"])),
+                ("b.txt", export("ws-002", "{4C4C4544004235108048B4C04F4E3732}", "SN2", &["00-1A-2B-3C-4D-5E"])),
+                // Serials keep their separators, "SN-1" and "SN1" are different serials
+                ("c.txt", export("ws-003", "11111111-2222-3333-4444-555555555555", "SN-1", &["001A.2B3C.4D5F"])),
+            ],
+        );
+        let kinds: Vec<IdentifierKind> = report.collisions.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [IdentifierKind::SystemUuid, IdentifierKind::MacAddress]);
+        assert!(report.collisions.iter().all(|c| c.hosts == ["ws-001", "ws-002"]));
+        // The value is shown as first seen
+        assert_eq!(report.collisions[0].value, uuid);
+    }
+
+    #[test]
+    fn merges_exports_of_one_host() {
+        let dir = fleet_dir(
+            "merge",
+            vec![
+                ("ws-001-monday.txt", export("WS-001", "UUID-A", "SN1", &["00:1A:2B:3C:4D:5E"])),
+                ("ws-001-friday.txt", export("ws-001", "uuid-a", "SN1", &["00:1a:2b:3c:4d:5e", "00:1A:2B:3C:4D:5F"])),
+                ("ws-002.txt", export("ws-002", "UUID-B", "SN2", &[])),
+            ],
+        );
+        let fleet = Fleet::load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(fleet.hosts.len(), 2);
+        let host = &fleet.hosts[0];
+        assert_eq!(host.exports, 2);
+        let macs = host.identifiers.iter().filter(|(k, _)| *k == IdentifierKind::MacAddress).count();
+        assert_eq!(macs, 2);
+        assert_eq!(host.identifiers.iter().filter(|(k, _)| *k == IdentifierKind::SystemUuid).count(), 1);
+        // Two files of one machine never collide with each other
+        assert!(fleet.report(&Placeholders::default()).collisions.is_empty());
+    }
+
+    #[test]
+    fn reports_exported_placeholders() {
+        let with_junk = |host: &str| {
+            let mut e = export(host, &format!("UUID-{}", host), &format!("SN-{}", host), &[]);
+            e.section("baseboard")
+                .field("serial_number", "N/A")
+                .placeholder("serial_number", Some("Default string"));
+            e
+        };
+        let report = report("placeholders", vec![("a.txt", with_junk("ws-001")), ("b.txt", with_junk("ws-002"))]);
+        assert_eq!(report.collisions.len(), 1);
+        let collision = &report.collisions[0];
+        assert_eq!(collision.kind, IdentifierKind::BaseboardSerial);
+        assert_eq!(collision.value, "Default string");
+        assert_eq!(collision.placeholder, Some("vendor template text"));
+        let baseboard = report.kinds.iter().find(|s| s.kind == IdentifierKind::BaseboardSerial).unwrap();
+        assert_eq!((baseboard.present, baseboard.colliding), (2, 2));
+    }
+}
@@ -1,20 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::export::Export;
+
 #[cfg(windows)]
 use winreg::enums::*;
 #[cfg(windows)]
 use winreg::RegKey;
 
 /// Represents the lock status of the motherboard/BIOS
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LockedMotherboardInfo {
     pub is_oem_system: bool,
     pub oem_vendor: String,
     pub bios_write_protected: bool,
     pub secure_boot_enforced: bool,
     pub tpm_locked: bool,
+    pub tpm_version: String,
+    pub boot_mode: String,
+    pub kernel_lockdown: String,
+    pub iommu_enabled: bool,
+    pub module_sig_enforced: bool,
     pub overall_locked: bool,
     pub lock_reasons: Vec<String>,
 }
 
+/// Manufacturer whose name marks an OEM system
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OemVendor {
+    /// Lowercase text looked for in the system manufacturer
+    #[serde(rename = "match")]
+    pub pattern: String,
+    pub name: String,
+    /// Whether the vendor typically ships a locked BIOS
+    #[serde(default)]
+    pub locked: bool,
+}
+
+impl OemVendor {
+    fn new(pattern: &str, name: &str, locked: bool) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            name: name.to_string(),
+            locked,
+        }
+    }
+
+    /// Known OEM vendors, Dell, HP and Lenovo typically have locked BIOS
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("dell", "Dell", true),
+            Self::new("hp", "HP", true),
+            Self::new("hewlett", "HP", true),
+            Self::new("lenovo", "Lenovo", true),
+            Self::new("asus", "ASUS", false),
+            Self::new("acer", "Acer", false),
+            Self::new("msi", "MSI", false),
+            Self::new("gigabyte", "Gigabyte", false),
+            Self::new("asrock", "ASRock", false),
+        ]
+    }
+}
+
 /// Serial comparison result
 #[derive(Debug, Clone, PartialEq)]
 pub enum SerialStatus {
@@ -35,23 +82,30 @@ pub struct PreviousSerials {
     pub disk_serials: Vec<String>,
     pub network_macs: Vec<String>,
     pub monitor_serials: Vec<String>,
-    pub gpu_guids: Vec<String>,
+    pub gpu_ids: Vec<String>,
+    /// Identifiers are salted hashes, compare them against hashed current values
+    pub redacted: bool,
 }
 
 impl LockedMotherboardInfo {
-    pub fn detect() -> Self {
+    pub fn detect(vendors: &[OemVendor]) -> Self {
         #[cfg(windows)]
         {
-            Self::detect_windows()
+            Self::detect_windows(vendors)
         }
-        #[cfg(not(windows))]
+        #[cfg(target_os = "linux")]
         {
+            Self::detect_linux(vendors)
+        }
+        #[cfg(not(any(windows, target_os = "linux")))]
+        {
+            let _ = vendors;
             Self::default()
         }
     }
 
     #[cfg(windows)]
-    fn detect_windows() -> Self {
+    fn detect_windows(vendors: &[OemVendor]) -> Self {
         let mut info = Self::default();
         let mut lock_reasons = Vec::new();
 
@@ -61,33 +115,7 @@ impl LockedMotherboardInfo {
         // Check system manufacturer
         if let Ok(key) = hklm.open_subkey("HARDWARE\\DESCRIPTION\\System\\BIOS") {
             if let Ok(manufacturer) = key.get_value::<String, _>("SystemManufacturer") {
-                let manufacturer_lower = manufacturer.to_lowercase();
-                
-                // Known OEM vendors with locked BIOS
-                let oem_vendors = [
-                    ("dell", "Dell"),
-                    ("hp", "HP"),
-                    ("hewlett", "HP"),
-                    ("lenovo", "Lenovo"),
-                    ("asus", "ASUS"),
-                    ("acer", "Acer"),
-                    ("msi", "MSI"),
-                    ("gigabyte", "Gigabyte"),
-                    ("asrock", "ASRock"),
-                ];
-                
-                for (pattern, vendor) in oem_vendors {
-                    if manufacturer_lower.contains(pattern) {
-                        info.oem_vendor = vendor.to_string();
-                        
-                        // Dell, HP, Lenovo typically have locked BIOS
-                        if ["dell", "hp", "hewlett", "lenovo"].contains(&pattern) {
-                            info.is_oem_system = true;
-                            lock_reasons.push(format!("{} OEM system detected - BIOS typically locked", vendor));
-                        }
-                        break;
-                    }
-                }
+                Self::detect_oem_vendor(vendors, &manufacturer, &mut info, &mut lock_reasons);
             }
         }
 
@@ -111,6 +139,17 @@ impl LockedMotherboardInfo {
             }
         }
 
+        // Firmware type the system booted with (1 = legacy BIOS, 2 = UEFI)
+        if let Ok(key) = hklm.open_subkey("SYSTEM\\CurrentControlSet\\Control") {
+            if let Ok(value) = key.get_value::<u32, _>("PEFirmwareType") {
+                info.boot_mode = match value {
+                    1 => "Legacy BIOS".to_string(),
+                    2 => "UEFI".to_string(),
+                    _ => "Unknown".to_string(),
+                };
+            }
+        }
+
         // Check for BIOS write protection indicators
         if let Ok(key) = hklm.open_subkey("SYSTEM\\CurrentControlSet\\Control\\DeviceGuard") {
             if let Ok(value) = key.get_value::<u32, _>("EnableVirtualizationBasedSecurity") {
@@ -138,6 +177,110 @@ impl LockedMotherboardInfo {
         
         info
     }
+
+    #[cfg(target_os = "linux")]
+    fn detect_linux(vendors: &[OemVendor]) -> Self {
+        use std::fs;
+        use std::path::Path;
+
+        use crate::info::tpm::{self, TpmInfo};
+
+        let mut info = Self::default();
+        let mut lock_reasons = Vec::new();
+
+        // Detect OEM vendor from DMI
+        if let Ok(manufacturer) = fs::read_to_string("/sys/class/dmi/id/sys_vendor") {
+            Self::detect_oem_vendor(vendors, manufacturer.trim(), &mut info, &mut lock_reasons);
+        }
+
+        // UEFI systems expose the EFI runtime under /sys/firmware/efi
+        let uefi = Path::new("/sys/firmware/efi").exists();
+        info.boot_mode = if uefi { "UEFI" } else { "Legacy BIOS" }.to_string();
+
+        // Check Secure Boot (efivar = 4 byte attributes followed by a 1 byte value)
+        if uefi {
+            let secure_boot = fs::read_dir("/sys/firmware/efi/efivars")
+                .ok()
+                .and_then(|entries| {
+                    entries
+                        .flatten()
+                        .find(|e| e.file_name().to_string_lossy().starts_with("SecureBoot-"))
+                })
+                .and_then(|entry| fs::read(entry.path()).ok())
+                .map(|data| data.get(4) == Some(&1))
+                .unwrap_or(false);
+            if secure_boot {
+                info.secure_boot_enforced = true;
+                lock_reasons.push("Secure Boot enabled - EFI modifications restricted".to_string());
+            }
+        }
+
+        // Check TPM, the version is read the same way as for the BIOS tab
+        let tpm = TpmInfo::from_sysfs(Path::new(tpm::SYSFS_DIR));
+        if tpm.present {
+            info.tpm_locked = true;
+            lock_reasons.push(match tpm.spec_version.as_str() {
+                "Unknown" => "TPM active - Hardware attestation may detect changes".to_string(),
+                version => format!("TPM {} active - Hardware attestation may detect changes", version),
+            });
+            info.tpm_version = tpm.spec_version;
+        }
+
+        // Check kernel lockdown, the active mode is shown in brackets: "none [integrity] confidentiality"
+        if let Ok(lockdown) = fs::read_to_string("/sys/kernel/security/lockdown") {
+            if let Some(mode) = lockdown
+                .split_whitespace()
+                .find(|m| m.starts_with('[') && m.ends_with(']'))
+            {
+                info.kernel_lockdown = mode.trim_matches(|c| c == '[' || c == ']').to_string();
+                if info.kernel_lockdown != "none" {
+                    info.bios_write_protected = true;
+                    lock_reasons.push(format!(
+                        "Kernel lockdown ({}) - Kernel-level protections active",
+                        info.kernel_lockdown
+                    ));
+                }
+            }
+        }
+
+        // Check IOMMU
+        let iommu_units = fs::read_dir("/sys/class/iommu")
+            .map(|entries| entries.count())
+            .unwrap_or(0);
+        if iommu_units > 0 {
+            info.iommu_enabled = true;
+            lock_reasons.push("IOMMU enabled - DMA access to memory restricted".to_string());
+        }
+
+        // Check module signature enforcement
+        let sig_enforce = fs::read_to_string("/sys/module/module/parameters/sig_enforce")
+            .map(|v| v.trim() == "Y")
+            .unwrap_or(false);
+        if sig_enforce {
+            info.module_sig_enforced = true;
+            info.bios_write_protected = true;
+            lock_reasons.push("Module signature enforcement enabled - Driver signing enforced".to_string());
+        }
+
+        info.lock_reasons = lock_reasons;
+        info.overall_locked = info.is_oem_system || info.secure_boot_enforced || info.bios_write_protected;
+
+        info
+    }
+
+    /// Match the system manufacturer against known OEM vendors
+    #[cfg(any(windows, target_os = "linux"))]
+    fn detect_oem_vendor(vendors: &[OemVendor], manufacturer: &str, info: &mut Self, lock_reasons: &mut Vec<String>) {
+        let manufacturer_lower = manufacturer.to_lowercase();
+
+        if let Some(vendor) = vendors.iter().find(|v| manufacturer_lower.contains(&v.pattern)) {
+            info.oem_vendor = vendor.name.clone();
+            if vendor.locked {
+                info.is_oem_system = true;
+                lock_reasons.push(format!("{} OEM system detected - BIOS typically locked", vendor.name));
+            }
+        }
+    }
 }
 
 impl Default for LockedMotherboardInfo {
@@ -148,15 +291,60 @@ impl Default for LockedMotherboardInfo {
             bios_write_protected: false,
             secure_boot_enforced: false,
             tpm_locked: false,
+            tpm_version: "N/A".to_string(),
+            boot_mode: "Unknown".to_string(),
+            kernel_lockdown: "N/A".to_string(),
+            iommu_enabled: false,
+            module_sig_enforced: false,
             overall_locked: false,
             lock_reasons: Vec::new(),
         }
     }
 }
 
+/// Exported values that carry no identity are skipped
+fn known(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty() && value != "N/A").then(|| value.to_string())
+}
+
 impl PreviousSerials {
-    /// Parse serials from the export file content
+    /// Parse serials from the export file content, versioned or legacy
     pub fn parse(content: &str) -> Self {
+        match Export::parse(content) {
+            Some(export) => Self::from_export(&export),
+            None => Self::parse_legacy(content),
+        }
+    }
+
+    fn from_export(export: &Export) -> Self {
+        let field = |section: &str, key: &str| {
+            export.section(section).and_then(|s| s.get(key)).and_then(known)
+        };
+        let list = |section: &str, keys: &[&str]| -> Vec<String> {
+            export
+                .sections(section)
+                .flat_map(|s| keys.iter().filter_map(|key| s.get(key).and_then(known)))
+                .collect()
+        };
+
+        Self {
+            system_serial: field("system", "serial_number"),
+            system_uuid: field("system", "uuid"),
+            system_sku: field("system", "sku"),
+            baseboard_serial: field("baseboard", "serial_number"),
+            processor_serial: field("processor", "serial_number"),
+            chassis_serial: field("chassis", "serial_number"),
+            disk_serials: list("disk", &["storage_query"]),
+            network_macs: list("network", &["mac_address"]),
+            monitor_serials: list("monitor", &["serial_number"]),
+            gpu_ids: list("gpu", &["pci_device", "board_serial"]),
+            redacted: export.section("export").and_then(|s| s.get("redacted")) == Some("true"),
+        }
+    }
+
+    /// Files written before the versioned format: "=== SECTION ===" headers and "Key: Value" lines
+    fn parse_legacy(content: &str) -> Self {
         let mut serials = Self::default();
         let mut current_section = "";
 
@@ -169,6 +357,14 @@ impl PreviousSerials {
                 continue;
             }
 
+            // Interface names may contain ": " but MAC addresses never do, so split on the last one
+            if current_section == "NETWORK" {
+                if let Some(mac) = line.rsplit_once(": ").and_then(|(_, mac)| known(mac)) {
+                    serials.network_macs.push(mac);
+                }
+                continue;
+            }
+
             // Parse key-value pairs
             if let Some((key, value)) = line.split_once(':') {
                 let key = key.trim();
@@ -185,40 +381,26 @@ impl PreviousSerials {
                         "SKU" => serials.system_sku = Some(value),
                         _ => {}
                     },
-                    "BASEBOARD" => {
-                        if key == "Serial Number" {
-                            serials.baseboard_serial = Some(value);
-                        }
-                    }
-                    "PROCESSOR" => {
-                        if key == "Serial Number" {
-                            serials.processor_serial = Some(value);
-                        }
+                    "BASEBOARD" if key == "Serial Number" => {
+                        serials.baseboard_serial = Some(value);
                     }
-                    "CHASSIS" => {
-                        if key == "Serial Number" {
-                            serials.chassis_serial = Some(value);
-                        }
+                    "PROCESSOR" if key == "Serial Number" => {
+                        serials.processor_serial = Some(value);
                     }
-                    "DISKS" => {
-                        if key.contains("Serial") {
-                            serials.disk_serials.push(value);
-                        }
+                    "CHASSIS" if key == "Serial Number" => {
+                        serials.chassis_serial = Some(value);
                     }
-                    "NETWORK" => {
-                        // Format is "Interface: MAC"
-                        serials.network_macs.push(value);
+                    "DISKS" if key.contains("Serial") => {
+                        serials.disk_serials.push(value);
                     }
-                    "MONITORS" => {
-                        if key == "Serial Number" {
-                            serials.monitor_serials.push(value);
-                        }
+                    "MONITORS" if key == "Serial Number" => {
+                        serials.monitor_serials.push(value);
                     }
-                    "GPU" => {
-                        if key.contains("GUID") {
-                            serials.gpu_guids.push(value);
-                        }
+                    "GPU" if key == "PCI Device" || key == "Board Serial" => {
+                        serials.gpu_ids.push(value);
                     }
+                    // The GUID line held the display adapter class GUID, the same on every machine
+                    "GPU" if key == "GUID" => {}
                     _ => {}
                 }
             }
@@ -240,6 +422,7 @@ impl PreviousSerials {
             "baseboard_serial" => self.baseboard_serial.as_deref(),
             "processor_serial" => self.processor_serial.as_deref(),
             "chassis_serial" => self.chassis_serial.as_deref(),
+            "disk" | "network" | "monitor" | "gpu" => return self.compare_list(category, current),
             _ => None,
         };
 
@@ -260,7 +443,7 @@ impl PreviousSerials {
             "disk" => &self.disk_serials,
             "network" => &self.network_macs,
             "monitor" => &self.monitor_serials,
-            "gpu" => &self.gpu_guids,
+            "gpu" => &self.gpu_ids,
             _ => return SerialStatus::New,
         };
 
@@ -275,3 +458,82 @@ impl PreviousSerials {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// serials_export.txt as the first releases wrote it
+    const LEGACY_EXPORT: &str = "=== SERIAL EXPORT ===
+Generated: 2025-03-14 09:26:53
+
+=== SYSTEM ===
+Serial Number: 5CG1234XYZ
+UUID: 4C4C4544-0042-3510-8048-B4C04F4E3732
+SKU: N/A
+
+=== BASEBOARD ===
+Serial Number: /5CG1234XYZ/CNFCW0012A0042/
+Asset Tag: N/A
+
+=== PROCESSOR ===
+Serial Number: N/A
+Part Number: N/A
+
+=== CHASSIS ===
+Serial Number: 5CG1234XYZ
+Asset Tag: N/A
+SKU: N/A
+
+=== DISKS ===
+Disk 1: SAMSUNG MZVL2512HCJQ-00B00: OEM
+  Serial (Storage Query): S675NX0R123456
+  WWN: N/A
+Disk 2: WDC WD10EZEX-08WN4A0
+  Serial (Storage Query): WD-WCC6Y0ABCDEF
+  WWN: 5000c500a1b2c3d4
+
+=== NETWORK ===
+Ethernet: 00:1A:2B:3C:4D:5E
+Ethernet: 2: 00:1A:2B:3C:4D:5F
+Wi-Fi: N/A
+
+=== MONITORS ===
+DISPLAY1: DELL U2720Q
+  Serial Number: 8GZ1234
+  ID Serial: N/A
+
+=== GPU ===
+NVIDIA GeForce RTX 3060
+  PCI Device: PCI\\VEN_10DE&DEV_2504&SUBSYS_39761462&REV_A1
+  GUID: {4d36e968-e325-11ce-bfc1-08002be10318}
+";
+
+    #[test]
+    fn parses_legacy_exports() {
+        let serials = PreviousSerials::parse(LEGACY_EXPORT);
+        assert_eq!(serials.system_serial.as_deref(), Some("5CG1234XYZ"));
+        assert_eq!(serials.system_uuid.as_deref(), Some("4C4C4544-0042-3510-8048-B4C04F4E3732"));
+        assert_eq!(serials.system_sku, None);
+        assert_eq!(serials.baseboard_serial.as_deref(), Some("/5CG1234XYZ/CNFCW0012A0042/"));
+        assert_eq!(serials.processor_serial, None);
+        assert_eq!(serials.chassis_serial.as_deref(), Some("5CG1234XYZ"));
+        assert_eq!(serials.disk_serials, ["S675NX0R123456", "WD-WCC6Y0ABCDEF"]);
+        assert_eq!(serials.network_macs, ["00:1A:2B:3C:4D:5E", "00:1A:2B:3C:4D:5F"]);
+        assert_eq!(serials.monitor_serials, ["8GZ1234"]);
+        assert_eq!(serials.gpu_ids, [r"PCI\VEN_10DE&DEV_2504&SUBSYS_39761462&REV_A1"]);
+        assert!(!serials.redacted);
+    }
+
+    #[test]
+    fn compares_against_legacy_exports() {
+        let serials = PreviousSerials::parse(LEGACY_EXPORT);
+        assert_eq!(serials.compare("system_serial", "5CG1234XYZ"), SerialStatus::Unchanged);
+        assert_eq!(
+            serials.compare("chassis_serial", "5CG9999ABC"),
+            SerialStatus::Changed { old: "5CG1234XYZ".to_string() }
+        );
+        assert_eq!(serials.compare("processor_serial", "BFEBFBFF000906EA"), SerialStatus::New);
+        assert_eq!(serials.compare_list("network", "00:1A:2B:3C:4D:5F"), SerialStatus::Unchanged);
+    }
+}
@@ -1,10 +1,11 @@
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BaseboardInfo {
     pub manufacturer: String,
     pub product_name: String,
@@ -44,17 +45,9 @@ impl BaseboardInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
-            return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         let boards: Vec<Win32BaseBoard> = wmi_con
             .raw_query("SELECT * FROM Win32_BaseBoard")
@@ -65,23 +58,18 @@ impl BaseboardInfo {
         Self {
             manufacturer: board
                 .and_then(|b| b.manufacturer.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             product_name: board
                 .and_then(|b| b.product.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             version: board
                 .and_then(|b| b.version.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             serial_number: board
                 .and_then(|b| b.serial_number.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             asset_tag: board
                 .and_then(|b| b.tag.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             location: "(Integrated)".to_string(),
         }
@@ -100,21 +88,3 @@ impl Default for BaseboardInfo {
         }
     }
 }
-
-/// Check if a string is a placeholder/empty value
-fn is_placeholder(s: &str) -> bool {
-    let lower = s.trim().to_lowercase();
-    lower.is_empty() 
-        || lower.contains("to be filled")
-        || lower.contains("o.e.m")
-        || lower.contains("oem")
-        || lower == "default string"
-        || lower == "not specified"
-        || lower == "none"
-        || lower == "n/a"
-        || lower == "unknown"
-        || lower == "system serial number"
-        || lower == "system product name"
-        || lower == "base board serial number"
-        || lower == "chassis serial number"
-}
@@ -1,19 +1,23 @@
+use chrono::NaiveDate;
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use wmi::WMIConnection;
+#[cfg(windows)]
+use crate::info::wmi_session;
 
 #[cfg(windows)]
 use winreg::enums::*;
 #[cfg(windows)]
 use winreg::RegKey;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BiosInfo {
     pub vendor: String,
     pub version: String,
-    pub release_date: String,
+    pub release_date: Option<NaiveDate>,
     pub core_isolation: bool,
     pub virtualization: bool,
     pub secure_boot: bool,
@@ -59,17 +63,9 @@ impl BiosInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
-            return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         // Query Win32_BIOS
         let bioses: Vec<Win32Bios> = wmi_con
@@ -92,15 +88,9 @@ impl BiosInfo {
 
         // Parse release date (WMI returns format like "20231015000000.000000+000")
         let release_date = bios
-            .and_then(|b| b.release_date.clone())
-            .map(|d| {
-                if d.len() >= 8 {
-                    format!("{}/{}/{}", &d[4..6], &d[6..8], &d[0..4])
-                } else {
-                    d
-                }
-            })
-            .unwrap_or_else(|| "N/A".to_string());
+            .and_then(|b| b.release_date.as_deref())
+            .and_then(|d| d.get(0..8))
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok());
 
         Self {
             vendor: bios
@@ -186,7 +176,7 @@ impl Default for BiosInfo {
         Self {
             vendor: "N/A".to_string(),
             version: "N/A".to_string(),
-            release_date: "N/A".to_string(),
+            release_date: None,
             core_isolation: false,
             virtualization: false,
             secure_boot: false,
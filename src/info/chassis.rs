@@ -1,10 +1,11 @@
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ChassisInfo {
     pub manufacturer: String,
     pub chassis_type: String,
@@ -48,17 +49,9 @@ impl ChassisInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
-            return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         let enclosures: Vec<Win32SystemEnclosure> = wmi_con
             .raw_query("SELECT * FROM Win32_SystemEnclosure")
@@ -75,24 +68,19 @@ impl ChassisInfo {
         Self {
             manufacturer: enclosure
                 .and_then(|e| e.manufacturer.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             chassis_type,
             version: enclosure
                 .and_then(|e| e.version.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             serial_number: enclosure
                 .and_then(|e| e.serial_number.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             asset_tag: enclosure
                 .and_then(|e| e.smbios_asset_tag.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             sku: enclosure
                 .and_then(|e| e.sku.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
         }
     }
@@ -153,16 +141,3 @@ impl Default for ChassisInfo {
         }
     }
 }
-
-/// Check if a string is a placeholder/empty value
-fn is_placeholder(s: &str) -> bool {
-    let lower = s.trim().to_lowercase();
-    lower.is_empty() 
-        || lower.contains("to be filled")
-        || lower.contains("o.e.m")
-        || lower == "default string"
-        || lower == "not specified"
-        || lower == "none"
-        || lower == "unknown"
-        || lower == "chassis serial number"
-}
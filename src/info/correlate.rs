@@ -0,0 +1,205 @@
+/// Identity of a disk as reported by one source (WMI class, sysfs, saved identify data)
+#[derive(Debug, Clone, Default)]
+pub struct DiskRecord {
+    pub serial: Option<String>,
+    pub device_number: Option<u32>,
+    pub pnp_id: Option<String>,
+}
+
+/// Which identity two records agreed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKey {
+    DeviceNumber,
+    Serial,
+    PnpId,
+}
+
+impl MatchKey {
+    fn matches(&self, a: &str, b: &str) -> bool {
+        match self {
+            // Storage paths wrap the PnP ID in a device interface path, so allow containment
+            MatchKey::PnpId => a.contains(b) || b.contains(a),
+            _ => a == b,
+        }
+    }
+}
+
+/// Result of pairing two lists of disk records, by index into each list
+#[derive(Debug, Clone, Default)]
+pub struct Correlation {
+    pub pairs: Vec<(usize, usize, MatchKey)>,
+    // Only the WMI collector has two full lists to report on
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub unmatched_left: Vec<usize>,
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub unmatched_right: Vec<usize>,
+}
+
+impl Correlation {
+    /// Index into the right-hand list matched to a left-hand record
+    pub fn right_for(&self, left: usize) -> Option<(usize, MatchKey)> {
+        self.pairs
+            .iter()
+            .find(|(l, _, _)| *l == left)
+            .map(|&(_, r, key)| (r, key))
+    }
+}
+
+/// Pulls one normalized identity out of a record
+type KeyExtractor = fn(&DiskRecord) -> Option<String>;
+
+/// Pair records that describe the same disk, never by position
+///
+/// Device numbers are tried first since they are unique per boot, then serials, then PnP IDs.
+/// A record is used at most once, and a key only matches when it is unambiguous on both sides.
+pub fn correlate(left: &[DiskRecord], right: &[DiskRecord]) -> Correlation {
+    let mut left_used = vec![false; left.len()];
+    let mut right_used = vec![false; right.len()];
+    let mut pairs = Vec::new();
+
+    let keys: [(MatchKey, KeyExtractor); 3] = [
+        (MatchKey::DeviceNumber, |r| r.device_number.map(|n| n.to_string())),
+        (MatchKey::Serial, |r| r.serial.as_deref().and_then(normalize_serial)),
+        (MatchKey::PnpId, |r| r.pnp_id.as_deref().and_then(normalize_pnp_id)),
+    ];
+
+    for (key, extract) in keys {
+        for (l, record) in left.iter().enumerate() {
+            if left_used[l] {
+                continue;
+            }
+            let Some(value) = extract(record) else {
+                continue;
+            };
+
+            let same = |other: &DiskRecord| extract(other).is_some_and(|v| key.matches(&value, &v));
+            let candidates: Vec<usize> = (0..right.len())
+                .filter(|&r| !right_used[r] && same(&right[r]))
+                .collect();
+            let left_duplicates = left
+                .iter()
+                .enumerate()
+                .filter(|&(other, rec)| other != l && !left_used[other] && same(rec))
+                .count();
+
+            if let [r] = candidates[..] {
+                if left_duplicates == 0 {
+                    left_used[l] = true;
+                    right_used[r] = true;
+                    pairs.push((l, r, key));
+                }
+            }
+        }
+    }
+
+    Correlation {
+        pairs,
+        unmatched_left: (0..left.len()).filter(|&i| !left_used[i]).collect(),
+        unmatched_right: (0..right.len()).filter(|&i| !right_used[i]).collect(),
+    }
+}
+
+/// Serials come space padded, in different case, and sometimes with the ATA byte order left swapped
+fn normalize_serial(serial: &str) -> Option<String> {
+    let serial: String = serial
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-' && *c != '.')
+        .collect::<String>()
+        .to_uppercase();
+    if serial.is_empty() || serial == "N/A" || serial.chars().all(|c| c == '0') {
+        return None;
+    }
+    // Compare byte-swapped serials in a fixed order so both spellings meet
+    let swapped: String = serial
+        .as_bytes()
+        .chunks(2)
+        .flat_map(|pair| pair.iter().rev())
+        .map(|&b| b as char)
+        .collect();
+    Some(if swapped < serial { swapped } else { serial })
+}
+
+/// "SCSI\DISK&VEN_X\4&1A2B&0&000000" and "\\?\scsi#disk&ven_x#4&1a2b&0&000000#{guid}" meet here
+fn normalize_pnp_id(id: &str) -> Option<String> {
+    let id = id.trim().trim_start_matches(r"\\?\").to_uppercase().replace('#', "\\");
+    let id = match id.find("\\{") {
+        Some(guid) => id[..guid].to_string(),
+        None => id,
+    };
+    (!id.is_empty()).then_some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Win32_DiskDrive / MSFT_PhysicalDisk style record
+    fn record(device_number: Option<u32>, serial: Option<&str>, pnp_id: Option<&str>) -> DiskRecord {
+        DiskRecord {
+            serial: serial.map(str::to_string),
+            device_number,
+            pnp_id: pnp_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn matches_device_numbers_regardless_of_order() {
+        let left = [record(Some(0), Some("A"), None), record(Some(1), Some("B"), None)];
+        let right = [record(Some(1), Some("X"), None), record(Some(0), Some("Y"), None)];
+        let correlation = correlate(&left, &right);
+        assert_eq!(correlation.right_for(0), Some((1, MatchKey::DeviceNumber)));
+        assert_eq!(correlation.right_for(1), Some((0, MatchKey::DeviceNumber)));
+        assert!(correlation.unmatched_left.is_empty());
+        assert!(correlation.unmatched_right.is_empty());
+    }
+
+    #[test]
+    fn falls_back_to_normalized_serial() {
+        // Win32_DiskDrive pads and lowercases, MSFT_PhysicalDisk may leave ATA byte pairs swapped
+        let left = [record(None, Some("  s4evnx0n123456 "), None)];
+        let right = [record(Some(3), Some("4SVEXNN0214365"), None)];
+        let correlation = correlate(&left, &right);
+        assert_eq!(correlation.right_for(0), Some((0, MatchKey::Serial)));
+    }
+
+    #[test]
+    fn falls_back_to_pnp_id() {
+        let left = [record(None, None, Some(r"SCSI\DISK&VEN_NVME&PROD_SAMSUNG\4&1A2B3C&0&000000"))];
+        let right = [record(
+            None,
+            Some("N/A"),
+            Some(r"\\?\scsi#disk&ven_nvme&prod_samsung#4&1a2b3c&0&000000#{53f56307-b6bf-11d0-94f2-00a0c91efb8b}"),
+        )];
+        let correlation = correlate(&left, &right);
+        assert_eq!(correlation.right_for(0), Some((0, MatchKey::PnpId)));
+    }
+
+    #[test]
+    fn duplicate_serials_stay_unmatched() {
+        // Cheap USB bridges report the same serial for every disk behind them
+        let left = [record(None, Some("0123456789AB"), None), record(None, Some("0123456789AB"), None)];
+        let right = [record(None, Some("0123456789AB"), None), record(None, Some("0123456789AB"), None)];
+        let correlation = correlate(&left, &right);
+        assert!(correlation.pairs.is_empty());
+
+        let one_left = [record(None, Some("0123456789AB"), None)];
+        assert!(correlate(&one_left, &right).pairs.is_empty());
+    }
+
+    #[test]
+    fn reports_unmatched_records() {
+        let left = [record(Some(0), None, None), record(None, Some("ONLYLEFT"), None)];
+        let right = [record(Some(0), None, None), record(Some(7), Some("ONLYRIGHT"), None)];
+        let correlation = correlate(&left, &right);
+        assert_eq!(correlation.pairs, vec![(0, 0, MatchKey::DeviceNumber)]);
+        assert_eq!(correlation.unmatched_left, vec![1]);
+        assert_eq!(correlation.unmatched_right, vec![1]);
+    }
+
+    #[test]
+    fn placeholder_serials_never_match() {
+        let left = [record(None, Some("0000000000"), None)];
+        let right = [record(None, Some("0000000000"), None)];
+        assert!(correlate(&left, &right).pairs.is_empty());
+    }
+}
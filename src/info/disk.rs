@@ -1,10 +1,25 @@
+use std::path::Path;
+
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
+
+#[cfg(any(windows, target_os = "linux"))]
+use crate::info::passthrough;
+#[cfg(windows)]
+use crate::info::passthrough::AtaIdentity;
+use crate::info::{
+    correlate::{correlate, DiskRecord},
+    nvme::NvmeIdentity,
+    smart::SmartHealth,
+    volume::PartitionTable,
+    CollectOptions,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DiskEntry {
     pub model: String,
     pub storage_query: String,
@@ -12,11 +27,18 @@ pub struct DiskEntry {
     pub wwn: String,
     pub scsi: String,
     pub ata: String,
+    pub device_path: String,
+    pub partition_table: Option<PartitionTable>,
+    pub volume_error: Option<String>,
+    pub nvme: Option<NvmeIdentity>,
+    pub smart: Option<SmartHealth>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct DiskInfo {
     pub disks: Vec<DiskEntry>,
+    /// Records from one source that could not be tied to a disk from another
+    pub unmatched: Vec<String>,
 }
 
 #[cfg(windows)]
@@ -38,6 +60,11 @@ struct Win32DiskDrive {
     pnp_device_id: Option<String>,
     #[serde(default)]
     status: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "DeviceID")]
+    device_id: Option<String>,
+    #[serde(default)]
+    index: Option<u32>,
 }
 
 #[cfg(windows)]
@@ -50,33 +77,43 @@ struct MsftDisk {
     serial_number: Option<String>,
     #[serde(default)]
     unique_id: Option<String>,
+    #[serde(default)]
+    number: Option<u32>,
+    #[serde(default)]
+    path: Option<String>,
 }
 
 impl DiskInfo {
-    pub fn collect() -> Self {
+    /// Collect physical disks, plus any disk images or identify data given by the user
+    pub fn collect(options: &CollectOptions) -> Self {
         #[cfg(windows)]
-        {
-            Self::collect_windows()
+        let mut info = Self::collect_windows();
+        #[cfg(target_os = "linux")]
+        let mut info = Self::collect_linux();
+        #[cfg(not(any(windows, target_os = "linux")))]
+        let mut info = Self::default();
+
+        if let Some(identity) = &options.nvme_identity {
+            info.attach_nvme(identity.clone());
         }
-        #[cfg(not(windows))]
-        {
-            Self::default()
+
+        for image in &options.disk_images {
+            info.disks.push(DiskEntry::from_image(image));
         }
+
+        for disk in &mut info.disks {
+            disk.read_volumes();
+            disk.read_smart();
+        }
+
+        info
     }
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
-            return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         // Query Win32_DiskDrive for disk information
         let drives: Vec<Win32DiskDrive> = wmi_con
@@ -84,11 +121,47 @@ impl DiskInfo {
             .unwrap_or_default();
 
         // Try to get additional info from MSFT_Disk (StorageWMI namespace)
-        let msft_disks: Vec<MsftDisk> = WMIConnection::with_namespace_path("ROOT\\Microsoft\\Windows\\Storage", com_con)
-            .ok()
+        let msft_disks: Vec<MsftDisk> = wmi_session::connection("ROOT\\Microsoft\\Windows\\Storage")
             .and_then(|con| con.raw_query("SELECT * FROM MSFT_Disk").ok())
             .unwrap_or_default();
 
+        // The two classes enumerate in different orders, pair them by identity instead
+        let win32_records: Vec<DiskRecord> = drives
+            .iter()
+            .map(|d| DiskRecord {
+                serial: d.serial_number.clone(),
+                device_number: d.index,
+                pnp_id: d.pnp_device_id.clone(),
+            })
+            .collect();
+        let msft_records: Vec<DiskRecord> = msft_disks
+            .iter()
+            .map(|d| DiskRecord {
+                serial: d.serial_number.clone(),
+                device_number: d.number,
+                pnp_id: d.path.clone(),
+            })
+            .collect();
+        let correlation = correlate(&win32_records, &msft_records);
+
+        let mut unmatched = Vec::new();
+        if !msft_disks.is_empty() {
+            for &i in &correlation.unmatched_left {
+                unmatched.push(format!(
+                    "Win32_DiskDrive {} (serial {}) has no MSFT_Disk match",
+                    drives[i].model.as_deref().unwrap_or("Unknown"),
+                    drives[i].serial_number.as_deref().map(str::trim).unwrap_or("N/A"),
+                ));
+            }
+        }
+        for &i in &correlation.unmatched_right {
+            unmatched.push(format!(
+                "MSFT_Disk {} (serial {}) has no Win32_DiskDrive match",
+                msft_disks[i].friendly_name.as_deref().unwrap_or("Unknown"),
+                msft_disks[i].serial_number.as_deref().map(str::trim).unwrap_or("N/A"),
+            ));
+        }
+
         let mut disks = Vec::new();
 
         for (i, drive) in drives.iter().enumerate() {
@@ -101,24 +174,39 @@ impl DiskInfo {
                 .filter(|s| !s.is_empty())
                 .unwrap_or_else(|| "N/A".to_string());
             
-            // SMART status
+            // Drive status as reported by WMI, replaced by real SMART data when the drive answers
             let smart_data = drive.status.clone()
-                .map(|s| if s == "OK" { "OK".to_string() } else { format!("Status: {}", s) })
+                .map(|s| format!("{} [Win32_DiskDrive.Status]", s))
                 .unwrap_or_else(|| "N/A".to_string());
             
-            // WWN - try to get from MSFT_Disk UniqueId
-            let wwn = msft_disks.get(i)
-                .and_then(|d| d.unique_id.clone())
+            // WWN - from the MSFT_Disk UniqueId of the same disk
+            let wwn = correlation
+                .right_for(i)
+                .and_then(|(r, _)| msft_disks[r].unique_id.clone())
                 .unwrap_or_else(|| "N/A".to_string());
             
-            // SCSI/ATA based on interface type
-            let (scsi, ata) = match interface.as_str() {
-                "SCSI" => ("Supported".to_string(), "N/A".to_string()),
-                "IDE" | "ATA" => ("N/A".to_string(), "Supported".to_string()),
-                "USB" => ("USB".to_string(), "N/A".to_string()),
-                _ if interface.contains("NVMe") => ("NVMe".to_string(), "N/A".to_string()),
-                _ => (interface.clone(), "N/A".to_string()),
+            // StorNVMe reports NVMe drives as SCSI, so just ask and see if the driver answers
+            let nvme = drive.device_id.as_deref().and_then(NvmeIdentity::from_device);
+            let storage_query = match &nvme {
+                Some(id) if storage_query == "N/A" => id.serial.clone(),
+                _ => storage_query,
+            };
+
+            // Ask the drive itself, StorNVMe answers neither ATA nor SCSI pass-through
+            let (scsi_vpd, ata_identity) = match (&nvme, drive.device_id.as_deref()) {
+                (None, Some(path)) => (passthrough::scsi_vpd(path), passthrough::ata_identify(path)),
+                _ => (None, None),
             };
+            let wwn = match (&ata_identity, &scsi_vpd) {
+                (Some(AtaIdentity { wwn: Some(ata_wwn), .. }), _) if wwn == "N/A" => ata_wwn.clone(),
+                (_, Some(vpd)) if wwn == "N/A" => vpd.wwn().map(str::to_string).unwrap_or(wwn),
+                _ => wwn,
+            };
+            let (scsi, ata) = passthrough_fields(
+                &interface,
+                scsi_vpd.as_ref().map(|vpd| vpd.summary("IOCTL_SCSI_PASS_THROUGH")),
+                ata_identity.as_ref().map(|id| id.summary("IOCTL_ATA_PASS_THROUGH")),
+            );
 
             disks.push(DiskEntry {
                 model,
@@ -127,15 +215,190 @@ impl DiskInfo {
                 wwn,
                 scsi,
                 ata,
+                device_path: drive.device_id.clone().unwrap_or_else(|| "N/A".to_string()),
+                partition_table: None,
+                volume_error: None,
+                nvme,
+                smart: None,
+            });
+        }
+
+        Self { disks, unmatched }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_linux() -> Self {
+        use std::fs;
+
+        // Skip virtual and stacked block devices, they have no identity of their own
+        const SKIP_PREFIXES: &[&str] = &["loop", "ram", "zram", "dm-", "md", "sr", "fd", "nbd"];
+
+        let Ok(entries) = fs::read_dir("/sys/block") else {
+            return Self::default();
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|name| !SKIP_PREFIXES.iter().any(|p| name.starts_with(p)))
+            .collect();
+        names.sort();
+
+        let mut disks = Vec::new();
+
+        for name in names {
+            let sys = Path::new("/sys/block").join(&name);
+            let read = |rel: &str| {
+                fs::read_to_string(sys.join(rel))
+                    .ok()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+
+            let interface = if name.starts_with("nvme") {
+                "NVMe"
+            } else if name.starts_with("vd") {
+                "VirtIO"
+            } else if name.starts_with("mmcblk") {
+                "MMC"
+            } else {
+                "SCSI"
+            };
+
+            let nvme = if interface == "NVMe" { NvmeIdentity::from_sysfs(&sys) } else { None };
+
+            // The kernel caches VPD pages for SCSI disks, libata adds the SAT ATA Information page
+            let (scsi_vpd, ata_identity) = passthrough::read_sysfs(&sys.join("device"));
+            let device_path = format!("/dev/{}", name);
+            let (ata_identity, ata_method) = match ata_identity {
+                Some(id) => (Some(id), "sysfs VPD 0x89"),
+                None if interface == "SCSI" => (passthrough::ata_identify(&device_path), "SG_IO ATA PASS-THROUGH"),
+                None => (None, ""),
+            };
+
+            let storage_query = read("device/serial")
+                .or_else(|| ata_identity.as_ref().map(|id| id.serial.clone()))
+                .or_else(|| scsi_vpd.as_ref().and_then(|vpd| vpd.serial.clone()))
+                .unwrap_or_else(|| "N/A".to_string());
+            let wwn = read("wwid")
+                .or_else(|| read("device/wwid"))
+                .or_else(|| ata_identity.as_ref().and_then(|id| id.wwn.clone()))
+                .or_else(|| scsi_vpd.as_ref().and_then(|vpd| vpd.wwn()).map(str::to_string))
+                .unwrap_or_else(|| "N/A".to_string());
+            let (scsi, ata) = passthrough_fields(
+                interface,
+                scsi_vpd.as_ref().map(|vpd| vpd.summary("sysfs VPD 0x80/0x83")),
+                ata_identity.as_ref().map(|id| id.summary(ata_method)),
+            );
+
+            disks.push(DiskEntry {
+                model: read("device/model").unwrap_or_else(|| "Unknown".to_string()),
+                storage_query,
+                smart_data: "N/A".to_string(),
+                wwn,
+                scsi,
+                ata,
+                device_path,
+                partition_table: None,
+                volume_error: None,
+                nvme,
+                smart: None,
             });
         }
 
-        Self { disks }
+        Self { disks, unmatched: Vec::new() }
     }
 }
 
-impl Default for DiskInfo {
-    fn default() -> Self {
-        Self { disks: Vec::new() }
+impl DiskInfo {
+    /// Attach saved identify data to the drive with the same serial, or list it on its own
+    fn attach_nvme(&mut self, identity: NvmeIdentity) {
+        let blob = [DiskRecord {
+            serial: Some(identity.serial.clone()),
+            ..Default::default()
+        }];
+        let disks: Vec<DiskRecord> = self
+            .disks
+            .iter()
+            .map(|d| DiskRecord {
+                serial: d.nvme.as_ref().map(|n| n.serial.clone()).or_else(|| Some(d.storage_query.clone())),
+                ..Default::default()
+            })
+            .collect();
+
+        match correlate(&blob, &disks).right_for(0) {
+            Some((disk, _)) => self.disks[disk].nvme = Some(identity),
+            None => {
+                self.unmatched.push(format!(
+                    "NVMe identify data (serial {}) matches no attached disk, listed separately",
+                    identity.serial
+                ));
+                self.disks.push(DiskEntry {
+                    model: identity.model.clone(),
+                    storage_query: identity.serial.clone(),
+                    smart_data: "N/A".to_string(),
+                    wwn: "N/A".to_string(),
+                    scsi: "NVMe".to_string(),
+                    ata: "N/A".to_string(),
+                    device_path: "N/A".to_string(),
+                    partition_table: None,
+                    volume_error: None,
+                    nvme: Some(identity),
+                    smart: None,
+                });
+            }
+        }
+    }
+}
+
+/// Pass-through results for display, or which interface gave no answer
+#[cfg(any(windows, target_os = "linux"))]
+fn passthrough_fields(interface: &str, scsi: Option<String>, ata: Option<String>) -> (String, String) {
+    let unavailable = || format!("N/A ({})", interface);
+    (scsi.unwrap_or_else(unavailable), ata.unwrap_or_else(unavailable))
+}
+
+impl DiskEntry {
+    fn from_image(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        Self {
+            model: format!("Image: {}", name),
+            storage_query: "N/A".to_string(),
+            smart_data: "N/A".to_string(),
+            wwn: "N/A".to_string(),
+            scsi: "N/A".to_string(),
+            ata: "N/A".to_string(),
+            device_path: path.display().to_string(),
+            partition_table: None,
+            volume_error: None,
+            nvme: None,
+            smart: None,
+        }
+    }
+
+    /// Decode SMART attributes or the NVMe health log (needs admin/root)
+    fn read_smart(&mut self) {
+        #[cfg(any(windows, target_os = "linux"))]
+        if self.device_path != "N/A" {
+            if let Some(health) = SmartHealth::read(&self.device_path, self.nvme.is_some()) {
+                self.smart_data = health.summary();
+                self.smart = Some(health);
+            }
+        }
+    }
+
+    /// Parse the partition table and filesystem identifiers (needs admin/root for real disks)
+    fn read_volumes(&mut self) {
+        if self.device_path == "N/A" {
+            return;
+        }
+        match PartitionTable::read(Path::new(&self.device_path)) {
+            Ok(table) => self.partition_table = Some(table),
+            Err(e) => self.volume_error = Some(e.to_string()),
+        }
     }
 }
@@ -0,0 +1,148 @@
+/// Fields decoded from the 128-byte EDID base block
+#[derive(Debug, Clone)]
+pub struct Edid {
+    pub manufacturer: String,
+    pub product_code: u16,
+    pub id_serial: u32,
+    pub name: Option<String>,
+    pub serial: Option<String>,
+    pub native_mode: Option<String>,
+}
+
+const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+impl Edid {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let block = data.get(..128)?;
+        if block[..8] != HEADER {
+            return None;
+        }
+
+        // Three 5-bit letters, 'A' is 1
+        let id = u16::from_be_bytes([block[8], block[9]]);
+        let manufacturer: String = [10, 5, 0]
+            .iter()
+            .map(|shift| (((id >> shift) & 0x1F) as u8 + b'A' - 1) as char)
+            .collect();
+
+        let mut edid = Self {
+            manufacturer,
+            product_code: u16::from_le_bytes([block[10], block[11]]),
+            id_serial: u32::from_le_bytes([block[12], block[13], block[14], block[15]]),
+            name: None,
+            serial: None,
+            native_mode: None,
+        };
+
+        for descriptor in block[54..126].chunks_exact(18) {
+            if descriptor[0] != 0 || descriptor[1] != 0 {
+                // The first detailed timing descriptor is the preferred (native) mode
+                if edid.native_mode.is_none() {
+                    edid.native_mode = detailed_timing(descriptor);
+                }
+                continue;
+            }
+            match descriptor[3] {
+                0xFC => edid.name = descriptor_text(&descriptor[5..]),
+                0xFF => edid.serial = descriptor_text(&descriptor[5..]),
+                _ => {}
+            }
+        }
+
+        Some(edid)
+    }
+}
+
+/// "2560x1440 @ 60 Hz" from an 18-byte detailed timing descriptor
+fn detailed_timing(d: &[u8]) -> Option<String> {
+    let clock_hz = u16::from_le_bytes([d[0], d[1]]) as u64 * 10_000;
+    let h_active = d[2] as u64 | ((d[4] as u64 >> 4) << 8);
+    let h_blank = d[3] as u64 | ((d[4] as u64 & 0x0F) << 8);
+    let v_active = d[5] as u64 | ((d[7] as u64 >> 4) << 8);
+    let v_blank = d[6] as u64 | ((d[7] as u64 & 0x0F) << 8);
+
+    if h_active == 0 || v_active == 0 {
+        return None;
+    }
+
+    let total = (h_active + h_blank) * (v_active + v_blank);
+    if total == 0 {
+        return Some(format!("{}x{}", h_active, v_active));
+    }
+    Some(format!("{}x{} @ {} Hz", h_active, v_active, (clock_hz + total / 2) / total))
+}
+
+/// Display descriptor text ends at a line feed and is padded with spaces
+fn descriptor_text(bytes: &[u8]) -> Option<String> {
+    let text: String = bytes
+        .iter()
+        .take_while(|&&b| b != 0x0A)
+        .map(|&b| b as char)
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base block of a 27" 4K panel: one detailed timing, then serial, name and range limits
+    const DELL_U2720Q: [u8; 128] = [
+        0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x10, 0xAC, 0xA5, 0x41, 0x53, 0x39, 0x31, 0x4C,
+        0x0C, 0x1E, 0x01, 0x04, 0xB5, 0x3C, 0x22, 0x78, 0x3B, 0xEE, 0x91, 0xA3, 0x54, 0x4C, 0x99, 0x26,
+        0x0F, 0x50, 0x54, 0xA5, 0x4B, 0x00, 0x01, 0x01, 0xD1, 0xC0, 0x81, 0x80, 0xA9, 0xC0, 0xB3, 0x00,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x4D, 0xD0, 0x00, 0xA0, 0xF0, 0x70, 0x3E, 0x80, 0x30, 0x20,
+        0x35, 0x00, 0x54, 0x4F, 0x21, 0x00, 0x00, 0x1A, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x38, 0x47, 0x5A,
+        0x31, 0x32, 0x33, 0x34, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x44,
+        0x45, 0x4C, 0x4C, 0x20, 0x55, 0x32, 0x37, 0x32, 0x30, 0x51, 0x0A, 0x20, 0x00, 0x00, 0x00, 0xFD,
+        0x00, 0x38, 0x4C, 0x1E, 0x8C, 0x3C, 0x00, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x01, 0xF8,
+    ];
+
+    #[test]
+    fn decodes_base_block() {
+        let edid = Edid::parse(&DELL_U2720Q).unwrap();
+        assert_eq!(edid.manufacturer, "DEL");
+        assert_eq!(edid.product_code, 0x41A5);
+        assert_eq!(edid.id_serial, 0x4C31_3953);
+        assert_eq!(edid.serial.as_deref(), Some("8GZ1234"));
+        assert_eq!(edid.name.as_deref(), Some("DELL U2720Q"));
+        assert_eq!(edid.native_mode.as_deref(), Some("3840x2160 @ 60 Hz"));
+    }
+
+    #[test]
+    fn native_mode_is_the_first_detailed_timing() {
+        // 1920x1080 @ 60 Hz in place of the range limits descriptor
+        let mut data = DELL_U2720Q;
+        data[108..126].copy_from_slice(&[
+            0x02, 0x3A, 0x80, 0x18, 0x71, 0x38, 0x2D, 0x40, 0x58, 0x2C, 0x45, 0x00, 0x56, 0x50, 0x21, 0x00, 0x00, 0x1E,
+        ]);
+        assert_eq!(Edid::parse(&data).unwrap().native_mode.as_deref(), Some("3840x2160 @ 60 Hz"));
+
+        // Without the 4K timing the 1080p one is first
+        data[54..72].copy_from_slice(&[0x00, 0x00, 0x00, 0x10, 0x00, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+        assert_eq!(Edid::parse(&data).unwrap().native_mode.as_deref(), Some("1920x1080 @ 60 Hz"));
+    }
+
+    #[test]
+    fn rejects_short_or_headerless_data() {
+        assert!(Edid::parse(&DELL_U2720Q[..127]).is_none());
+        let mut data = DELL_U2720Q;
+        data[0] = 0xFF;
+        assert!(Edid::parse(&data).is_none());
+
+        // Extension blocks after the base block are ignored
+        let mut extended = DELL_U2720Q.to_vec();
+        extended.extend_from_slice(&[0x02; 128]);
+        assert_eq!(Edid::parse(&extended).unwrap().serial.as_deref(), Some("8GZ1234"));
+    }
+
+    #[test]
+    fn blank_descriptors_are_none() {
+        let mut data = DELL_U2720Q;
+        data[77..90].copy_from_slice(b"\n            ");
+        data[95..108].copy_from_slice(b"             ");
+        let edid = Edid::parse(&data).unwrap();
+        assert_eq!((edid.serial, edid.name), (None, None));
+    }
+}
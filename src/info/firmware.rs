@@ -0,0 +1,292 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::info::{
+    system::SystemInfo,
+    bios::BiosInfo,
+    baseboard::BaseboardInfo,
+};
+
+/// Default firmware age limit (two years)
+pub const DEFAULT_MAX_AGE_DAYS: i64 = 730;
+
+/// Latest known BIOS release for a model
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogEntry {
+    pub version: String,
+    #[serde(default)]
+    pub release_date: Option<NaiveDate>,
+}
+
+/// Local catalog of latest known BIOS versions, keyed by system or baseboard model
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BiosCatalog {
+    pub models: HashMap<String, CatalogEntry>,
+}
+
+/// Settings for the firmware analysis
+#[derive(Debug, Clone)]
+pub struct FirmwareOptions {
+    pub max_age_days: i64,
+    pub catalog: Option<BiosCatalog>,
+}
+
+/// Whether the installed BIOS matches the catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum UpdateStatus {
+    UpToDate,
+    UpdateAvailable,
+    NewerThanCatalog,
+    NotInCatalog,
+    NoCatalog,
+}
+
+impl UpdateStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UpdateStatus::UpToDate => "Up to date",
+            UpdateStatus::UpdateAvailable => "Update available",
+            UpdateStatus::NewerThanCatalog => "Newer than catalog",
+            UpdateStatus::NotInCatalog => "Model not in catalog",
+            UpdateStatus::NoCatalog => "No catalog loaded",
+        }
+    }
+}
+
+/// Firmware age and update status derived from the BIOS and model information
+#[derive(Debug, Clone, Serialize)]
+pub struct FirmwareStatus {
+    pub age_days: Option<i64>,
+    pub max_age_days: i64,
+    pub too_old: bool,
+    pub catalog_model: Option<String>,
+    pub latest_version: Option<String>,
+    pub latest_release_date: Option<NaiveDate>,
+    pub update_status: UpdateStatus,
+}
+
+impl BiosCatalog {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid BIOS catalog {}: {}", path.display(), e))
+    }
+
+    /// Find a catalog entry by model name, ignoring case and surrounding whitespace
+    fn lookup(&self, model: &str) -> Option<(&String, &CatalogEntry)> {
+        let model = model.trim();
+        if model.is_empty() || model == "N/A" {
+            return None;
+        }
+        self.models
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(model))
+    }
+}
+
+impl Default for FirmwareOptions {
+    fn default() -> Self {
+        Self {
+            max_age_days: DEFAULT_MAX_AGE_DAYS,
+            catalog: None,
+        }
+    }
+}
+
+impl FirmwareStatus {
+    pub fn assess(
+        bios: &BiosInfo,
+        system: &SystemInfo,
+        baseboard: &BaseboardInfo,
+        options: &FirmwareOptions,
+    ) -> Self {
+        let today = chrono::Local::now().date_naive();
+        let age_days = bios.release_date.map(|date| (today - date).num_days());
+        let too_old = age_days.is_some_and(|age| age > options.max_age_days);
+
+        let mut status = Self {
+            age_days,
+            max_age_days: options.max_age_days,
+            too_old,
+            catalog_model: None,
+            latest_version: None,
+            latest_release_date: None,
+            update_status: UpdateStatus::NoCatalog,
+        };
+
+        let Some(catalog) = &options.catalog else {
+            return status;
+        };
+
+        // The system product name is the most specific key, baseboard model is the fallback
+        let Some((model, entry)) = catalog
+            .lookup(&system.product_name)
+            .or_else(|| catalog.lookup(&baseboard.product_name))
+        else {
+            status.update_status = UpdateStatus::NotInCatalog;
+            return status;
+        };
+
+        status.catalog_model = Some(model.clone());
+        status.latest_version = Some(entry.version.clone());
+        status.latest_release_date = entry.release_date;
+        status.update_status = Self::compare(bios, entry);
+        status
+    }
+
+    fn compare(bios: &BiosInfo, entry: &CatalogEntry) -> UpdateStatus {
+        if bios.version.trim().eq_ignore_ascii_case(entry.version.trim()) {
+            return UpdateStatus::UpToDate;
+        }
+
+        // Prefer release dates, vendors reuse odd version schemes (A12, 1.21.0, 3607, F15c).
+        // Builds released the same day fall through to the version numbers.
+        if let (Some(installed), Some(latest)) = (bios.release_date, entry.release_date) {
+            match installed.cmp(&latest) {
+                Ordering::Less => return UpdateStatus::UpdateAvailable,
+                Ordering::Greater => return UpdateStatus::NewerThanCatalog,
+                Ordering::Equal => {}
+            }
+        }
+
+        let installed = version_numbers(&bios.version);
+        let latest = version_numbers(&entry.version);
+        if !installed.is_empty() && !latest.is_empty() && installed > latest {
+            UpdateStatus::NewerThanCatalog
+        } else {
+            UpdateStatus::UpdateAvailable
+        }
+    }
+}
+
+/// Extract the numeric components of a version string ("F15c" -> [15], "1.21.0" -> [1, 21, 0])
+fn version_numbers(version: &str) -> Vec<u64> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Option<NaiveDate> {
+        Some(s.parse().unwrap())
+    }
+
+    fn bios(version: &str, release_date: Option<NaiveDate>) -> BiosInfo {
+        BiosInfo {
+            version: version.to_string(),
+            release_date,
+            ..BiosInfo::default()
+        }
+    }
+
+    fn entry(version: &str, release_date: Option<NaiveDate>) -> CatalogEntry {
+        CatalogEntry {
+            version: version.to_string(),
+            release_date,
+        }
+    }
+
+    fn catalog(models: &[(&str, &str)]) -> BiosCatalog {
+        BiosCatalog {
+            models: models
+                .iter()
+                .map(|(model, version)| (model.to_string(), entry(version, None)))
+                .collect(),
+        }
+    }
+
+    fn assess(bios: &BiosInfo, system_model: &str, board_model: &str, options: &FirmwareOptions) -> FirmwareStatus {
+        let system = SystemInfo {
+            product_name: system_model.to_string(),
+            ..SystemInfo::default()
+        };
+        let baseboard = BaseboardInfo {
+            product_name: board_model.to_string(),
+            ..BaseboardInfo::default()
+        };
+        FirmwareStatus::assess(bios, &system, &baseboard, options)
+    }
+
+    #[test]
+    fn flags_firmware_past_the_age_limit() {
+        let today = chrono::Local::now().date_naive();
+        let options = FirmwareOptions {
+            max_age_days: 365,
+            catalog: None,
+        };
+        for (age, too_old) in [(0, false), (365, false), (366, true), (3000, true)] {
+            let released = today - chrono::Duration::days(age);
+            let status = assess(&bios("1.0", Some(released)), "N/A", "N/A", &options);
+            assert_eq!(status.age_days, Some(age));
+            assert_eq!(status.too_old, too_old, "{} days", age);
+            assert_eq!(status.update_status, UpdateStatus::NoCatalog);
+        }
+
+        let undated = assess(&bios("1.0", None), "N/A", "N/A", &options);
+        assert_eq!((undated.age_days, undated.too_old), (None, false));
+    }
+
+    #[test]
+    fn looks_up_models_ignoring_case_then_baseboard() {
+        let options = FirmwareOptions {
+            max_age_days: DEFAULT_MAX_AGE_DAYS,
+            catalog: Some(catalog(&[(" Latitude 5520 ", "1.21.0"), ("PRIME B550-PLUS", "3607")])),
+        };
+        let installed = bios("1.21.0", None);
+        let cases = [
+            ("latitude 5520", "N/A", Some(" Latitude 5520 "), UpdateStatus::UpToDate),
+            ("System Product Name", "Prime b550-plus", Some("PRIME B550-PLUS"), UpdateStatus::UpdateAvailable),
+            ("N/A", "PRIME B550-PLUS", Some("PRIME B550-PLUS"), UpdateStatus::UpdateAvailable),
+            ("OptiPlex 7090", "0K0N7R", None, UpdateStatus::NotInCatalog),
+            ("", "", None, UpdateStatus::NotInCatalog),
+        ];
+        for (system, board, model, update_status) in cases {
+            let status = assess(&installed, system, board, &options);
+            assert_eq!(status.catalog_model.as_deref(), model, "{} / {}", system, board);
+            assert_eq!(status.update_status, update_status, "{} / {}", system, board);
+        }
+    }
+
+    #[test]
+    fn compares_installed_and_catalog_versions() {
+        use UpdateStatus::*;
+        let cases = [
+            // Release dates win over version strings
+            (bios("A12", date("2023-05-01")), entry("A09", date("2024-01-10")), UpdateAvailable),
+            (bios("A09", date("2024-06-01")), entry("A12", date("2024-01-10")), NewerThanCatalog),
+            // Same day, different build: the version numbers decide
+            (bios("1.21.0", date("2024-01-10")), entry("1.21.1", date("2024-01-10")), UpdateAvailable),
+            (bios("1.21.2", date("2024-01-10")), entry("1.21.1", date("2024-01-10")), NewerThanCatalog),
+            // Without both dates the version numbers decide
+            (bios("F15c", None), entry("F16", date("2024-01-10")), UpdateAvailable),
+            (bios("3802", date("2024-01-10")), entry("3607", None), NewerThanCatalog),
+            (bios("1.9.0", None), entry("1.10.0", None), UpdateAvailable),
+            // Versions without numbers cannot be ordered
+            (bios("beta", None), entry("rc", None), UpdateAvailable),
+            (bios(" a12 ", date("2020-01-01")), entry("A12", date("2024-01-10")), UpToDate),
+        ];
+        for (installed, latest, expected) in cases {
+            assert_eq!(FirmwareStatus::compare(&installed, &latest), expected, "{} vs {}", installed.version, latest.version);
+        }
+    }
+
+    #[test]
+    fn extracts_version_numbers() {
+        assert_eq!(version_numbers("1.21.0"), [1, 21, 0]);
+        assert_eq!(version_numbers("F15c"), [15]);
+        assert_eq!(version_numbers("A12"), [12]);
+        assert_eq!(version_numbers("P1.40 (2024)"), [1, 40, 2024]);
+        assert!(version_numbers("beta").is_empty());
+    }
+}
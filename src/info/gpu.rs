@@ -1,24 +1,33 @@
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
 
 #[cfg(windows)]
 use winreg::enums::*;
 #[cfg(windows)]
 use winreg::RegKey;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GpuEntry {
+    /// PnP instance ID on Windows, PCI address on Linux
     pub pci_device: String,
     pub name: String,
-    pub guid: String,
+    /// Device setup class, identical for every display adapter
+    pub class_guid: String,
     pub vram: String,
     pub vendor: String,
+    pub pci_location: String,
+    pub vendor_device: String,
+    pub subsystem: String,
+    pub driver_key: String,
+    pub luid: String,
+    pub board_serial: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct GpuInfo {
     pub gpus: Vec<GpuEntry>,
 }
@@ -48,7 +57,11 @@ impl GpuInfo {
         {
             Self::collect_windows()
         }
-        #[cfg(not(windows))]
+        #[cfg(target_os = "linux")]
+        {
+            Self::collect_linux()
+        }
+        #[cfg(not(any(windows, target_os = "linux")))]
         {
             Self::default()
         }
@@ -56,22 +69,19 @@ impl GpuInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
-            return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         let controllers: Vec<Win32VideoController> = wmi_con
             .raw_query("SELECT * FROM Win32_VideoController")
             .unwrap_or_default();
 
+        let adapter_luids = Self::adapter_luids();
+        let controller_ids: Vec<PciIds> = controllers
+            .iter()
+            .filter_map(|c| c.pnp_device_id.as_deref().and_then(PciIds::from_pnp_id))
+            .collect();
         let mut gpus = Vec::new();
 
         for controller in controllers.iter() {
@@ -109,47 +119,298 @@ impl GpuInfo {
                     }
                 });
 
-            // Try to get GUID from registry
-            let guid = Self::get_gpu_guid(&pci_device, &vendor);
+            let ids = PciIds::from_pnp_id(&pci_device);
+            let device_key = Self::device_key(&pci_device);
+            let luid = ids
+                .as_ref()
+                .and_then(|ids| AdapterLuid::unique(&adapter_luids, &controller_ids, ids))
+                .map(|a| a.luid.clone())
+                .unwrap_or_else(|| "N/A".to_string());
 
             gpus.push(GpuEntry {
                 pci_device,
                 name,
-                guid,
+                class_guid: device_key.class_guid,
                 vram,
                 vendor,
+                pci_location: device_key.location,
+                vendor_device: ids.as_ref().map(PciIds::vendor_device).unwrap_or_else(|| "N/A".to_string()),
+                subsystem: ids.as_ref().map(PciIds::subsystem).unwrap_or_else(|| "N/A".to_string()),
+                driver_key: device_key.driver,
+                luid,
+                // Neither NVIDIA nor AMD publish a board serial through the Windows device stack
+                board_serial: "N/A".to_string(),
             });
         }
 
         Self { gpus }
     }
 
+    /// Identity values stored under the adapter's Enum key
     #[cfg(windows)]
-    fn get_gpu_guid(pnp_id: &str, vendor: &str) -> String {
-        // Try to get GPU GUID from registry
+    fn device_key(pnp_id: &str) -> DeviceKey {
         let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-        
-        // Check under Display adapters
-        if let Ok(key) = hklm.open_subkey("SYSTEM\\CurrentControlSet\\Enum") {
-            // The PNP ID contains the path, try to find matching device
-            if let Ok(subkey) = key.open_subkey(pnp_id.replace('\\', "\\")) {
-                if let Ok(guid) = subkey.get_value::<String, _>("ClassGUID") {
-                    return guid;
-                }
+        let key = hklm.open_subkey(format!("SYSTEM\\CurrentControlSet\\Enum\\{}", pnp_id)).ok();
+        let value = |name: &str| {
+            key.as_ref()
+                .and_then(|k| k.get_value::<String, _>(name).ok())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "N/A".to_string())
+        };
+
+        DeviceKey {
+            class_guid: value("ClassGUID"),
+            driver: value("Driver"),
+            location: PciIds::parse_location(&value("LocationInformation")).unwrap_or_else(|| "N/A".to_string()),
+        }
+    }
+
+    /// Adapter LUIDs from DXGI, which identifies adapters by PCI IDs rather than PnP path
+    #[cfg(windows)]
+    fn adapter_luids() -> Vec<AdapterLuid> {
+        use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
+
+        let mut adapters = Vec::new();
+        let Ok(factory) = (unsafe { CreateDXGIFactory1::<IDXGIFactory1>() }) else {
+            return adapters;
+        };
+
+        let mut index = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+            index += 1;
+            let Ok(desc) = (unsafe { adapter.GetDesc1() }) else {
+                continue;
+            };
+            adapters.push(AdapterLuid {
+                vendor_id: desc.VendorId as u16,
+                device_id: desc.DeviceId as u16,
+                subsys_id: desc.SubSysId,
+                luid: format!("{:08X}-{:08X}", desc.AdapterLuid.HighPart as u32, desc.AdapterLuid.LowPart),
+            });
+        }
+        adapters
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_linux() -> Self {
+        use std::fs;
+        use std::path::Path;
+
+        let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+            return Self::default();
+        };
+
+        let mut addresses: Vec<String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        addresses.sort();
+
+        let mut gpus = Vec::new();
+
+        for address in addresses {
+            let dir = Path::new("/sys/bus/pci/devices").join(&address);
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok().map(|s| s.trim().to_string());
+            let read_id = |file: &str| {
+                read(file)
+                    .and_then(|v| u16::from_str_radix(v.trim_start_matches("0x"), 16).ok())
+                    .unwrap_or(0)
+            };
+
+            // PCI base class 03h is a display controller
+            if !read("class").is_some_and(|class| class.starts_with("0x03")) {
+                continue;
             }
+
+            let ids = PciIds {
+                vendor_id: read_id("vendor"),
+                device_id: read_id("device"),
+                subsys_vendor_id: read_id("subsystem_vendor"),
+                subsys_device_id: read_id("subsystem_device"),
+            };
+
+            let vendor = vendor_name(ids.vendor_id);
+            let name = pci_ids_name(ids.vendor_id, ids.device_id)
+                .unwrap_or_else(|| format!("{} display controller", vendor));
+            let driver = fs::read_link(dir.join("driver"))
+                .ok()
+                .and_then(|link| link.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "N/A".to_string());
+            let vram = read("mem_info_vram_total")
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|bytes| format!("{} MB", bytes / (1024 * 1024)))
+                .unwrap_or_else(|| "N/A".to_string());
+
+            // amdgpu exposes the board serial and a unique ID, the NVIDIA driver a GPU UUID
+            let board_serial = read("serial_number")
+                .or_else(|| read("unique_id"))
+                .or_else(|| nvidia_uuid(&address))
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "N/A".to_string());
+
+            gpus.push(GpuEntry {
+                pci_device: address.clone(),
+                name,
+                class_guid: read("class").unwrap_or_else(|| "N/A".to_string()),
+                vram,
+                vendor,
+                pci_location: PciIds::parse_address(&address).unwrap_or_else(|| "N/A".to_string()),
+                vendor_device: ids.vendor_device(),
+                subsystem: ids.subsystem(),
+                driver_key: driver,
+                luid: "N/A".to_string(),
+                board_serial,
+            });
         }
 
-        // AMD GPUs often don't expose GUID in the same way
-        if vendor.contains("AMD") || vendor.contains("Radeon") {
-            return "N/A (LIKELY AMD GPU)".to_string();
+        Self { gpus }
+    }
+}
+
+/// PCI vendor, device and subsystem IDs of an adapter
+#[cfg(any(windows, target_os = "linux"))]
+struct PciIds {
+    vendor_id: u16,
+    device_id: u16,
+    subsys_vendor_id: u16,
+    subsys_device_id: u16,
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+impl PciIds {
+    /// PCI\VEN_10DE&DEV_2684&SUBSYS_88E21043&REV_A1\4&... (SUBSYS is device then vendor)
+    #[cfg(windows)]
+    fn from_pnp_id(pnp_id: &str) -> Option<Self> {
+        let upper = pnp_id.to_uppercase();
+        let field = |tag: &str, len: usize| {
+            let start = upper.find(tag)? + tag.len();
+            upper.get(start..start + len).and_then(|v| u32::from_str_radix(v, 16).ok())
+        };
+        let subsys = field("SUBSYS_", 8).unwrap_or(0);
+        Some(Self {
+            vendor_id: field("VEN_", 4)? as u16,
+            device_id: field("DEV_", 4)? as u16,
+            subsys_vendor_id: (subsys & 0xFFFF) as u16,
+            subsys_device_id: (subsys >> 16) as u16,
+        })
+    }
+
+    /// "PCI bus 1, device 0, function 0" -> "bus 01, device 00, function 0"
+    #[cfg(windows)]
+    fn parse_location(location: &str) -> Option<String> {
+        let numbers: Vec<u32> = location
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        match numbers[..] {
+            [bus, device, function] => Some(format!("bus {:02X}, device {:02X}, function {}", bus, device, function)),
+            _ => None,
         }
+    }
+
+    /// "0000:01:00.0" -> "bus 01, device 00, function 0"
+    #[cfg(target_os = "linux")]
+    fn parse_address(address: &str) -> Option<String> {
+        let (_, bdf) = address.split_once(':')?;
+        let (bus, df) = bdf.split_once(':')?;
+        let (device, function) = df.split_once('.')?;
+        Some(format!("bus {}, device {}, function {}", bus.to_uppercase(), device.to_uppercase(), function))
+    }
+
+    fn vendor_device(&self) -> String {
+        format!("{:04X}:{:04X}", self.vendor_id, self.device_id)
+    }
+
+    fn subsystem(&self) -> String {
+        if self.subsys_vendor_id == 0 && self.subsys_device_id == 0 {
+            return "N/A".to_string();
+        }
+        format!("{:04X}:{:04X}", self.subsys_vendor_id, self.subsys_device_id)
+    }
+}
 
-        "N/A".to_string()
+#[cfg(windows)]
+struct DeviceKey {
+    class_guid: String,
+    driver: String,
+    location: String,
+}
+
+#[cfg(windows)]
+struct AdapterLuid {
+    vendor_id: u16,
+    device_id: u16,
+    subsys_id: u32,
+    luid: String,
+}
+
+#[cfg(windows)]
+impl AdapterLuid {
+    fn matches(&self, ids: &PciIds) -> bool {
+        let subsys = ((ids.subsys_device_id as u32) << 16) | ids.subsys_vendor_id as u32;
+        self.vendor_id == ids.vendor_id
+            && self.device_id == ids.device_id
+            && (self.subsys_id == 0 || subsys == 0 || self.subsys_id == subsys)
+    }
+
+    /// The adapter for `ids`, when neither side has an identical twin
+    ///
+    /// DXGI reports no PCI location, so two cards of the same model cannot be told apart and
+    /// both are left without a LUID rather than sharing one.
+    fn unique<'a>(adapters: &'a [AdapterLuid], controllers: &[PciIds], ids: &PciIds) -> Option<&'a AdapterLuid> {
+        let mut candidates = adapters.iter().filter(|a| a.matches(ids));
+        let adapter = candidates.next()?;
+        let twins = controllers.iter().filter(|c| adapter.matches(c)).count();
+        (candidates.next().is_none() && twins == 1).then_some(adapter)
     }
 }
 
-impl Default for GpuInfo {
-    fn default() -> Self {
-        Self { gpus: Vec::new() }
+#[cfg(target_os = "linux")]
+fn vendor_name(vendor_id: u16) -> String {
+    match vendor_id {
+        0x10DE => "NVIDIA",
+        0x1002 => "AMD",
+        0x8086 => "Intel",
+        0x1AF4 => "Red Hat (virtio)",
+        0x1234 => "QEMU",
+        0x15AD => "VMware",
+        0x80EE => "VirtualBox",
+        0x1414 => "Microsoft",
+        0x1A03 => "ASPEED",
+        0x102B => "Matrox",
+        _ => return format!("Vendor {:04X}", vendor_id),
     }
+    .to_string()
+}
+
+/// Look the device name up in the system's pci.ids database when it is installed
+#[cfg(target_os = "linux")]
+fn pci_ids_name(vendor_id: u16, device_id: u16) -> Option<String> {
+    let content = ["/usr/share/hwdata/pci.ids", "/usr/share/misc/pci.ids", "/usr/share/pci.ids"]
+        .iter()
+        .find_map(|path| std::fs::read_to_string(path).ok())?;
+
+    let vendor = format!("{:04x}  ", vendor_id);
+    let device = format!("\t{:04x}  ", device_id);
+    let mut in_vendor = false;
+
+    for line in content.lines() {
+        if !line.starts_with('\t') && !line.starts_with('#') && !line.is_empty() {
+            in_vendor = line.starts_with(&vendor);
+        } else if in_vendor {
+            if let Some(name) = line.strip_prefix(&device) {
+                return Some(name.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+/// GPU UUID reported by the NVIDIA proprietary driver
+#[cfg(target_os = "linux")]
+fn nvidia_uuid(address: &str) -> Option<String> {
+    let info = std::fs::read_to_string(format!("/proc/driver/nvidia/gpus/{}/information", address)).ok()?;
+    info.lines()
+        .find_map(|line| line.strip_prefix("GPU UUID:"))
+        .map(|uuid| uuid.trim().to_string())
 }
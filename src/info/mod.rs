@@ -1,10 +1,58 @@
 pub mod system;
 pub mod bios;
+pub mod firmware;
 pub mod baseboard;
 pub mod disk;
+pub mod correlate;
+pub mod nvme;
+pub mod passthrough;
+pub mod smart;
 pub mod processor;
 pub mod chassis;
 pub mod network;
 pub mod monitor;
+pub mod edid;
 pub mod gpu;
 pub mod advanced;
+pub mod tpm;
+pub mod volume;
+pub mod virtualization;
+pub mod placeholder;
+#[cfg(windows)]
+pub mod wmi_session;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use advanced::OemVendor;
+use firmware::FirmwareOptions;
+use nvme::NvmeIdentity;
+use placeholder::Placeholders;
+
+/// Settings that change what the collectors report
+#[derive(Debug, Clone)]
+pub struct CollectOptions {
+    pub firmware: FirmwareOptions,
+    pub tpm_ek_cert: Option<PathBuf>,
+    pub disk_images: Vec<PathBuf>,
+    pub nvme_identity: Option<NvmeIdentity>,
+    /// Per-collector time limit, `None` for the default
+    pub timeout: Option<Duration>,
+    /// SMBIOS values treated as never filled in
+    pub placeholders: Placeholders,
+    pub oem_vendors: Vec<OemVendor>,
+}
+
+impl Default for CollectOptions {
+    fn default() -> Self {
+        Self {
+            firmware: FirmwareOptions::default(),
+            tpm_ek_cert: None,
+            disk_images: Vec::new(),
+            nvme_identity: None,
+            timeout: None,
+            placeholders: Placeholders::default(),
+            oem_vendors: OemVendor::defaults(),
+        }
+    }
+}
@@ -1,25 +1,33 @@
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
 
 #[cfg(windows)]
 use winreg::enums::*;
 #[cfg(windows)]
 use winreg::RegKey;
 
-#[derive(Debug, Clone)]
+#[cfg(any(windows, target_os = "linux"))]
+use crate::info::edid::Edid;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct MonitorEntry {
+    /// Connector or output name (DRM connector on Linux, PnP instance path on Windows)
     pub display_name: String,
+    pub instance_path: String,
     pub manufacturer: String,
     pub model: String,
     pub serial_number: String,
     pub id_serial: String,
-    pub resolution: String,
+    pub active: bool,
+    pub current_mode: String,
+    pub native_mode: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct MonitorInfo {
     pub monitors: Vec<MonitorEntry>,
 }
@@ -41,6 +49,8 @@ struct Win32DesktopMonitor {
     screen_width: Option<u32>,
     #[serde(default)]
     screen_height: Option<u32>,
+    #[serde(default)]
+    availability: Option<u16>,
 }
 
 #[cfg(windows)]
@@ -55,6 +65,10 @@ struct WmiMonitorId {
     serial_number_id: Option<Vec<u16>>,
     #[serde(default)]
     user_friendly_name: Option<Vec<u16>>,
+    #[serde(default)]
+    instance_name: Option<String>,
+    #[serde(default)]
+    active: Option<bool>,
 }
 
 impl MonitorInfo {
@@ -63,7 +77,11 @@ impl MonitorInfo {
         {
             Self::collect_windows()
         }
-        #[cfg(not(windows))]
+        #[cfg(target_os = "linux")]
+        {
+            Self::collect_linux()
+        }
+        #[cfg(not(any(windows, target_os = "linux")))]
         {
             Self::default()
         }
@@ -71,21 +89,12 @@ impl MonitorInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
-            return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         // Try WmiMonitorID for detailed monitor info (requires admin on some systems)
-        let wmi_ids: Vec<WmiMonitorId> = WMIConnection::with_namespace_path("ROOT\\WMI", com_con)
-            .ok()
+        let wmi_ids: Vec<WmiMonitorId> = wmi_session::connection("ROOT\\WMI")
             .and_then(|con| con.raw_query("SELECT * FROM WmiMonitorID").ok())
             .unwrap_or_default();
 
@@ -97,7 +106,7 @@ impl MonitorInfo {
         let mut monitors = Vec::new();
 
         // Process WmiMonitorID results (more detailed)
-        for (i, wmi_id) in wmi_ids.iter().enumerate() {
+        for wmi_id in &wmi_ids {
             let manufacturer = Self::decode_wmi_string(&wmi_id.manufacturer_name);
             let model = Self::decode_wmi_string(&wmi_id.user_friendly_name)
                 .or_else(|| Self::decode_wmi_string(&wmi_id.product_code_id))
@@ -105,36 +114,41 @@ impl MonitorInfo {
             let serial = Self::decode_wmi_string(&wmi_id.serial_number_id)
                 .unwrap_or_else(|| "N/A".to_string());
 
-            // Try to get resolution from desktop monitor
-            let resolution = desktop_monitors.get(i)
-                .map(|m| {
-                    match (m.screen_width, m.screen_height) {
-                        (Some(w), Some(h)) if w > 0 && h > 0 => format!("{}x{}", w, h),
-                        _ => "N/A".to_string(),
-                    }
+            // WmiMonitorID instance names carry a "_0" suffix on the PnP instance path
+            let instance_path = wmi_id.instance_name.as_deref().map(Self::instance_path);
+            let desktop = instance_path.as_ref().and_then(|path| {
+                desktop_monitors.iter().find(|m| {
+                    m.pnp_device_id.as_deref().is_some_and(|id| id.eq_ignore_ascii_case(path))
                 })
-                .unwrap_or_else(|| "N/A".to_string());
+            });
+            let edid = instance_path.as_deref().and_then(Self::registry_edid);
 
             monitors.push(MonitorEntry {
-                display_name: format!("\\DISPLAY{}", i + 1),
+                display_name: instance_path.clone().unwrap_or_else(|| "N/A".to_string()),
+                instance_path: instance_path.unwrap_or_else(|| "N/A".to_string()),
                 manufacturer: manufacturer.unwrap_or_else(|| "Unknown".to_string()),
                 model,
                 serial_number: serial.clone(),
                 id_serial: serial,
-                resolution,
+                active: wmi_id.active.unwrap_or(desktop.is_some()),
+                current_mode: desktop.map(Self::desktop_mode).unwrap_or_else(|| "N/A".to_string()),
+                native_mode: edid
+                    .and_then(|e| e.native_mode)
+                    .unwrap_or_else(|| "N/A".to_string()),
             });
         }
 
         // If WmiMonitorID didn't work, use Win32_DesktopMonitor
         if monitors.is_empty() {
-            for (i, monitor) in desktop_monitors.iter().enumerate() {
-                let resolution = match (monitor.screen_width, monitor.screen_height) {
-                    (Some(w), Some(h)) if w > 0 && h > 0 => format!("{}x{}", w, h),
-                    _ => "N/A".to_string(),
-                };
+            for monitor in &desktop_monitors {
+                let instance_path = monitor.pnp_device_id.clone().unwrap_or_else(|| "N/A".to_string());
+                let edid = monitor.pnp_device_id.as_deref().and_then(Self::registry_edid);
 
                 monitors.push(MonitorEntry {
-                    display_name: format!("\\DISPLAY{}", i + 1),
+                    display_name: monitor.pnp_device_id.clone()
+                        .or_else(|| monitor.name.clone())
+                        .unwrap_or_else(|| "N/A".to_string()),
+                    instance_path,
                     manufacturer: monitor.monitor_manufacturer.clone()
                         .unwrap_or_else(|| "Unknown".to_string()),
                     model: monitor.monitor_type.clone()
@@ -142,7 +156,12 @@ impl MonitorInfo {
                         .unwrap_or_else(|| "Unknown".to_string()),
                     serial_number: Self::extract_serial_from_pnp(&monitor.pnp_device_id),
                     id_serial: monitor.pnp_device_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                    resolution,
+                    // Availability 3 is "Running/Full Power"
+                    active: monitor.availability == Some(3),
+                    current_mode: Self::desktop_mode(monitor),
+                    native_mode: edid
+                        .and_then(|e| e.native_mode)
+                        .unwrap_or_else(|| "N/A".to_string()),
                 });
             }
         }
@@ -150,6 +169,96 @@ impl MonitorInfo {
         Self { monitors }
     }
 
+    /// Strip the WMI instance suffix: DISPLAY\DEL404D\5&12345678&0&UID256_0 -> ...UID256
+    #[cfg(windows)]
+    fn instance_path(instance_name: &str) -> String {
+        match instance_name.rsplit_once('_') {
+            Some((path, index)) if !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()) => path.to_string(),
+            _ => instance_name.to_string(),
+        }
+    }
+
+    #[cfg(windows)]
+    fn desktop_mode(monitor: &Win32DesktopMonitor) -> String {
+        match (monitor.screen_width, monitor.screen_height) {
+            (Some(w), Some(h)) if w > 0 && h > 0 => format!("{}x{}", w, h),
+            _ => "N/A".to_string(),
+        }
+    }
+
+    /// EDID the monitor driver caches under its device key
+    #[cfg(windows)]
+    fn registry_edid(instance_path: &str) -> Option<Edid> {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let key = hklm
+            .open_subkey(format!("SYSTEM\\CurrentControlSet\\Enum\\{}\\Device Parameters", instance_path))
+            .ok()?;
+        let value = key.get_raw_value("EDID").ok()?;
+        Edid::parse(&value.bytes)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_linux() -> Self {
+        use std::fs;
+        use std::path::Path;
+
+        let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+            return Self::default();
+        };
+
+        // Connectors are named card<N>-<type>-<index>, e.g. card0-DP-1
+        let mut connectors: Vec<String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with("card") && name.contains('-'))
+            .collect();
+        connectors.sort();
+
+        let mut monitors = Vec::new();
+
+        for name in connectors {
+            let dir = Path::new("/sys/class/drm").join(&name);
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok().map(|s| s.trim().to_string());
+
+            if read("status").as_deref() != Some("connected") {
+                continue;
+            }
+
+            let edid = fs::read(dir.join("edid")).ok().and_then(|data| Edid::parse(&data));
+            let card = name.split('-').next().unwrap_or("card0");
+            let current_mode = read("connector_id")
+                .and_then(|id| id.parse().ok())
+                .and_then(|id| drm::current_mode(&format!("/dev/dri/{}", card), id));
+            // The kernel lists the preferred mode first
+            let first_mode = read("modes").and_then(|modes| modes.lines().next().map(str::to_string));
+
+            let (manufacturer, model, serial_number, id_serial, native_mode) = match edid {
+                Some(edid) => (
+                    edid.manufacturer,
+                    edid.name.unwrap_or_else(|| format!("{:04X}", edid.product_code)),
+                    edid.serial.unwrap_or_else(|| "N/A".to_string()),
+                    edid.id_serial.to_string(),
+                    edid.native_mode.or(first_mode),
+                ),
+                None => ("Unknown".to_string(), "Unknown".to_string(), "N/A".to_string(), "N/A".to_string(), first_mode),
+            };
+
+            monitors.push(MonitorEntry {
+                display_name: name.clone(),
+                instance_path: dir.display().to_string(),
+                manufacturer,
+                model,
+                serial_number,
+                id_serial,
+                active: read("enabled").as_deref() == Some("enabled"),
+                current_mode: current_mode.unwrap_or_else(|| "N/A".to_string()),
+                native_mode: native_mode.unwrap_or_else(|| "N/A".to_string()),
+            });
+        }
+
+        Self { monitors }
+    }
+
     #[cfg(windows)]
     fn decode_wmi_string(data: &Option<Vec<u16>>) -> Option<String> {
         data.as_ref().map(|v| {
@@ -173,8 +282,108 @@ impl MonitorInfo {
     }
 }
 
-impl Default for MonitorInfo {
-    fn default() -> Self {
-        Self { monitors: Vec::new() }
+/// Current mode of a connector through the DRM mode-setting ioctls
+///
+/// connector -> encoder -> CRTC, the CRTC holds the mode being scanned out.
+#[cfg(target_os = "linux")]
+mod drm {
+    use std::os::fd::AsRawFd;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct GetConnector {
+        encoders_ptr: u64,
+        modes_ptr: u64,
+        props_ptr: u64,
+        prop_values_ptr: u64,
+        count_modes: u32,
+        count_props: u32,
+        count_encoders: u32,
+        encoder_id: u32,
+        connector_id: u32,
+        connector_type: u32,
+        connector_type_id: u32,
+        connection: u32,
+        mm_width: u32,
+        mm_height: u32,
+        subpixel: u32,
+        pad: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct GetEncoder {
+        encoder_id: u32,
+        encoder_type: u32,
+        crtc_id: u32,
+        possible_crtcs: u32,
+        possible_clones: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct ModeInfo {
+        clock: u32,
+        hdisplay: u16,
+        hsync_start: u16,
+        hsync_end: u16,
+        htotal: u16,
+        hskew: u16,
+        vdisplay: u16,
+        vsync_start: u16,
+        vsync_end: u16,
+        vtotal: u16,
+        vscan: u16,
+        vrefresh: u32,
+        flags: u32,
+        mode_type: u32,
+        name: [u8; 32],
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct GetCrtc {
+        set_connectors_ptr: u64,
+        count_connectors: u32,
+        crtc_id: u32,
+        fb_id: u32,
+        x: u32,
+        y: u32,
+        gamma_size: u32,
+        mode_valid: u32,
+        mode: ModeInfo,
+    }
+
+    /// _IOWR('d', nr, T)
+    const fn iowr<T>(nr: u32) -> u32 {
+        (3 << 30) | ((std::mem::size_of::<T>() as u32) << 16) | ((b'd' as u32) << 8) | nr
+    }
+
+    const GET_CRTC: u32 = iowr::<GetCrtc>(0xA1);
+    const GET_ENCODER: u32 = iowr::<GetEncoder>(0xA6);
+    const GET_CONNECTOR: u32 = iowr::<GetConnector>(0xA7);
+
+    pub fn current_mode(card: &str, connector_id: u32) -> Option<String> {
+        let file = std::fs::File::open(card).ok()?;
+        let fd = file.as_raw_fd();
+
+        // With all counts at zero the kernel only fills in the scalar fields
+        let mut connector = GetConnector { connector_id, ..Default::default() };
+        if unsafe { libc::ioctl(fd, GET_CONNECTOR as _, &mut connector) } != 0 || connector.encoder_id == 0 {
+            return None;
+        }
+
+        let mut encoder = GetEncoder { encoder_id: connector.encoder_id, ..Default::default() };
+        if unsafe { libc::ioctl(fd, GET_ENCODER as _, &mut encoder) } != 0 || encoder.crtc_id == 0 {
+            return None;
+        }
+
+        let mut crtc = GetCrtc { crtc_id: encoder.crtc_id, ..Default::default() };
+        if unsafe { libc::ioctl(fd, GET_CRTC as _, &mut crtc) } != 0 || crtc.mode_valid == 0 {
+            return None;
+        }
+
+        let mode = &crtc.mode;
+        Some(format!("{}x{} @ {} Hz", mode.hdisplay, mode.vdisplay, mode.vrefresh))
     }
 }
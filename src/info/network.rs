@@ -1,17 +1,18 @@
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NetworkInterface {
     pub name: String,
     pub mac_address: String,
     pub ip_address: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct NetworkInfo {
     pub interfaces: Vec<NetworkInterface>,
 }
@@ -64,17 +65,9 @@ impl NetworkInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
-            return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         // Get physical adapters with MAC addresses
         let adapters: Vec<Win32NetworkAdapter> = wmi_con
@@ -114,9 +107,3 @@ impl NetworkInfo {
         Self { interfaces }
     }
 }
-
-impl Default for NetworkInfo {
-    fn default() -> Self {
-        Self { interfaces: Vec::new() }
-    }
-}
@@ -0,0 +1,408 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+#[cfg(windows)]
+use crate::info::passthrough::device_ioctl;
+
+/// Size of the Identify Controller and Identify Namespace data structures
+pub const IDENTIFY_LEN: usize = 4096;
+
+/// Identity decoded from NVMe Identify Controller / Identify Namespace data
+#[derive(Debug, Clone, Serialize)]
+pub struct NvmeIdentity {
+    pub serial: String,
+    pub model: String,
+    pub firmware: String,
+    pub ieee_oui: String,
+    pub controller_id: Option<u16>,
+    pub subsystem_nqn: String,
+    pub namespace_id: Option<u32>,
+    pub nguid: String,
+    pub eui64: String,
+    pub capacity_bytes: Option<u64>,
+    pub lba_size: Option<u32>,
+    pub source: String,
+}
+
+impl NvmeIdentity {
+    pub fn new(source: &str) -> Self {
+        Self {
+            serial: "N/A".to_string(),
+            model: "N/A".to_string(),
+            firmware: "N/A".to_string(),
+            ieee_oui: "N/A".to_string(),
+            controller_id: None,
+            subsystem_nqn: "N/A".to_string(),
+            namespace_id: None,
+            nguid: "N/A".to_string(),
+            eui64: "N/A".to_string(),
+            capacity_bytes: None,
+            lba_size: None,
+            source: source.to_string(),
+        }
+    }
+
+    /// Load saved identify blobs, e.g. from `nvme id-ctrl -b` and `nvme id-ns -b`
+    pub fn from_files(controller: Option<&Path>, namespace: Option<&Path>) -> Result<Self, String> {
+        let mut identity = Self::new("Identify data file");
+        if let Some(path) = controller {
+            let data = fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+            identity
+                .apply_controller(&data)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        if let Some(path) = namespace {
+            let data = fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+            identity
+                .apply_namespace(&data)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        Ok(identity)
+    }
+
+    /// Decode the Identify Controller structure (CNS 01h)
+    pub fn apply_controller(&mut self, data: &[u8]) -> Result<(), String> {
+        if data.len() < IDENTIFY_LEN {
+            return Err(format!("Identify Controller data is {} bytes, expected {}", data.len(), IDENTIFY_LEN));
+        }
+
+        self.serial = ascii_field(&data[4..24]);
+        self.model = ascii_field(&data[24..64]);
+        self.firmware = ascii_field(&data[64..72]);
+
+        // IEEE field is stored least significant byte first
+        let oui = &data[73..76];
+        if oui.iter().any(|&b| b != 0) {
+            self.ieee_oui = format!("{:02X}-{:02X}-{:02X}", oui[2], oui[1], oui[0]);
+        }
+
+        self.controller_id = Some(u16::from_le_bytes([data[78], data[79]]));
+        self.subsystem_nqn = ascii_field(&data[768..1024]);
+        Ok(())
+    }
+
+    /// Decode the Identify Namespace structure (CNS 00h)
+    pub fn apply_namespace(&mut self, data: &[u8]) -> Result<(), String> {
+        if data.len() < IDENTIFY_LEN {
+            return Err(format!("Identify Namespace data is {} bytes, expected {}", data.len(), IDENTIFY_LEN));
+        }
+
+        let capacity_blocks = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        // FLBAS bits 3:0 pick the format, bits 6:5 extend it when more than 16 formats exist
+        let flbas = data[26];
+        let format = ((flbas & 0x0F) | ((flbas >> 1) & 0x30)) as usize;
+        let lbads = data.get(128 + format * 4 + 2).copied().unwrap_or(0);
+        if (9..32).contains(&lbads) {
+            let lba_size = 1u32 << lbads;
+            self.lba_size = Some(lba_size);
+            self.capacity_bytes = Some(capacity_blocks.saturating_mul(lba_size as u64));
+        }
+
+        self.nguid = hex_id(&data[104..120]);
+        self.eui64 = hex_id(&data[120..128]);
+        Ok(())
+    }
+
+    /// Read the identity the kernel exposes for a controller and one of its namespaces
+    #[cfg(target_os = "linux")]
+    pub fn from_sysfs(block: &Path) -> Option<Self> {
+        let read = |path: std::path::PathBuf| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        // /sys/block/nvmeXnY/device points at the controller (/sys/class/nvme/nvmeX)
+        let controller = block.join("device");
+        let serial = read(controller.join("serial"))?;
+
+        let mut identity = Self::new("sysfs");
+        identity.serial = serial;
+        identity.model = read(controller.join("model")).unwrap_or_else(|| "N/A".to_string());
+        identity.firmware = read(controller.join("firmware_rev")).unwrap_or_else(|| "N/A".to_string());
+        identity.controller_id = read(controller.join("cntlid")).and_then(|s| s.parse().ok());
+        identity.subsystem_nqn = read(controller.join("subsysnqn")).unwrap_or_else(|| "N/A".to_string());
+        identity.namespace_id = read(block.join("nsid")).and_then(|s| s.parse().ok());
+
+        // sysfs prints these as "xx xx ..." and UUID style, normalize to plain hex
+        let normalize = |s: String| {
+            let hex: String = s.chars().filter(|c| c.is_ascii_hexdigit()).collect::<String>().to_uppercase();
+            if hex.is_empty() || hex.chars().all(|c| c == '0') { "N/A".to_string() } else { hex }
+        };
+        identity.nguid = read(block.join("nguid")).map(normalize).unwrap_or_else(|| "N/A".to_string());
+        identity.eui64 = read(block.join("eui")).map(normalize).unwrap_or_else(|| "N/A".to_string());
+
+        // "size" is always in 512-byte sectors regardless of the LBA format
+        identity.lba_size = read(block.join("queue/logical_block_size")).and_then(|s| s.parse().ok());
+        identity.capacity_bytes = read(block.join("size"))
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(|sectors| sectors * 512);
+
+        Some(identity)
+    }
+
+    /// Ask the storage driver for both identify structures (StorNVMe protocol-specific query)
+    #[cfg(windows)]
+    pub fn from_device(path: &str) -> Option<Self> {
+        let mut identity = Self::new("IOCTL_STORAGE_QUERY_PROPERTY");
+        let controller = query_identify(path, 1, 0)?;
+        identity.apply_controller(&controller).ok()?;
+        if let Some(namespace) = query_identify(path, 0, 1) {
+            identity.namespace_id = Some(1);
+            let _ = identity.apply_namespace(&namespace);
+        }
+        Some(identity)
+    }
+}
+
+/// Issue an NVMe Identify through IOCTL_STORAGE_QUERY_PROPERTY
+#[cfg(windows)]
+fn query_identify(path: &str, cns: u32, nsid: u32) -> Option<Vec<u8>> {
+    const STORAGE_ADAPTER_PROTOCOL_SPECIFIC_PROPERTY: u32 = 49;
+    const NVME_DATA_TYPE_IDENTIFY: u32 = 1;
+    query_protocol(path, STORAGE_ADAPTER_PROTOCOL_SPECIFIC_PROPERTY, NVME_DATA_TYPE_IDENTIFY, cns, nsid, IDENTIFY_LEN)
+}
+
+/// Read an NVMe log page for the whole controller
+#[cfg(windows)]
+pub fn get_log_page(path: &str, log_id: u8, len: usize) -> Option<Vec<u8>> {
+    const STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY: u32 = 50;
+    const NVME_DATA_TYPE_LOG_PAGE: u32 = 2;
+    query_protocol(path, STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY, NVME_DATA_TYPE_LOG_PAGE, log_id as u32, 0, len)
+}
+
+/// StorNVMe protocol-specific query
+///
+/// The request is built by hand: STORAGE_PROPERTY_QUERY followed by STORAGE_PROTOCOL_SPECIFIC_DATA,
+/// and the reply is a STORAGE_PROTOCOL_DATA_DESCRIPTOR with the data right after it.
+#[cfg(windows)]
+fn query_protocol(path: &str, property: u32, data_type: u32, value: u32, sub_value: u32, len: usize) -> Option<Vec<u8>> {
+    use windows::Win32::System::Ioctl::IOCTL_STORAGE_QUERY_PROPERTY;
+
+    const PROPERTY_STANDARD_QUERY: u32 = 0;
+    const PROTOCOL_TYPE_NVME: u32 = 3;
+    const PROTOCOL_DATA_LEN: usize = 40;
+    const HEADER_LEN: usize = 8 + PROTOCOL_DATA_LEN;
+
+    let mut buffer = vec![0u8; HEADER_LEN + len];
+    let fields = [
+        property,
+        PROPERTY_STANDARD_QUERY,
+        PROTOCOL_TYPE_NVME,
+        data_type,
+        value,
+        sub_value,
+        PROTOCOL_DATA_LEN as u32,
+        len as u32,
+    ];
+    for (i, field) in fields.iter().enumerate() {
+        buffer[i * 4..i * 4 + 4].copy_from_slice(&field.to_le_bytes());
+    }
+
+    let returned = device_ioctl(path, IOCTL_STORAGE_QUERY_PROPERTY, &mut buffer)?;
+    if returned < HEADER_LEN + len {
+        return None;
+    }
+    Some(buffer[HEADER_LEN..].to_vec())
+}
+
+/// Read an NVMe log page for the whole controller with an admin Get Log Page command
+#[cfg(target_os = "linux")]
+pub fn get_log_page(path: &str, log_id: u8, len: usize) -> Option<Vec<u8>> {
+    use std::os::fd::AsRawFd;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct NvmePassthruCmd {
+        opcode: u8,
+        flags: u8,
+        rsvd1: u16,
+        nsid: u32,
+        cdw2: u32,
+        cdw3: u32,
+        metadata: u64,
+        addr: u64,
+        metadata_len: u32,
+        data_len: u32,
+        cdw10: u32,
+        cdw11: u32,
+        cdw12: u32,
+        cdw13: u32,
+        cdw14: u32,
+        cdw15: u32,
+        timeout_ms: u32,
+        result: u32,
+    }
+
+    const NVME_IOCTL_ADMIN_CMD: u32 = 0xC048_4E41;
+    const GET_LOG_PAGE: u8 = 0x02;
+
+    let file = fs::File::open(path).ok()?;
+    let mut data = vec![0u8; len];
+    let mut cmd = NvmePassthruCmd {
+        opcode: GET_LOG_PAGE,
+        nsid: 0xFFFF_FFFF,
+        addr: data.as_mut_ptr() as u64,
+        data_len: len as u32,
+        // Number of dwords minus one in the upper half
+        cdw10: log_id as u32 | (((len / 4) as u32 - 1) << 16),
+        timeout_ms: 5000,
+        ..Default::default()
+    };
+
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), NVME_IOCTL_ADMIN_CMD as _, &mut cmd) };
+    (result == 0).then_some(data)
+}
+
+/// Space padded ASCII field with trailing NULs stripped
+fn ascii_field(bytes: &[u8]) -> String {
+    let text: String = bytes
+        .iter()
+        .take_while(|&&b| b != 0)
+        .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '?' })
+        .collect();
+    let text = text.trim();
+    if text.is_empty() { "N/A".to_string() } else { text.to_string() }
+}
+
+/// Big-endian identifier as hex, "N/A" when the controller leaves it zeroed
+fn hex_id(bytes: &[u8]) -> String {
+    if bytes.iter().all(|&b| b == 0) {
+        return "N/A".to_string();
+    }
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Identify Controller data laid out as a Samsung 980 PRO returns it
+    fn controller_fixture() -> Vec<u8> {
+        let mut data = vec![0u8; IDENTIFY_LEN];
+        data[0..2].copy_from_slice(&0x144Du16.to_le_bytes());
+        data[4..24].copy_from_slice(b"S5GXNX0R123456A     ");
+        data[24..64].copy_from_slice(b"Samsung SSD 980 PRO 1TB                 ");
+        data[64..72].copy_from_slice(b"5B2QGXA7");
+        data[73..76].copy_from_slice(&[0x38, 0x25, 0x00]);
+        data[78..80].copy_from_slice(&6u16.to_le_bytes());
+        let nqn = b"nqn.1994-11.com.samsung:nvme:980PRO:M.2:S5GXNX0R123456A";
+        data[768..768 + nqn.len()].copy_from_slice(nqn);
+        data
+    }
+
+    /// Identify Namespace data with 4 KiB sectors selected through LBA format `format`
+    fn namespace_fixture(format: u8, nguid: [u8; 16], eui64: [u8; 8]) -> Vec<u8> {
+        let mut data = vec![0u8; IDENTIFY_LEN];
+        data[0..8].copy_from_slice(&244_190_646u64.to_le_bytes());
+        data[8..16].copy_from_slice(&244_190_646u64.to_le_bytes());
+        data[26] = (format & 0x0F) | ((format & 0x30) << 1);
+        data[104..120].copy_from_slice(&nguid);
+        data[120..128].copy_from_slice(&eui64);
+        // LBA format 0 is 512 bytes, the selected one 4 KiB
+        data[128 + 2] = 9;
+        data[128 + format as usize * 4 + 2] = 12;
+        data
+    }
+
+    #[test]
+    fn decodes_controller() {
+        let mut identity = NvmeIdentity::new("test");
+        identity.apply_controller(&controller_fixture()).unwrap();
+        assert_eq!(identity.serial, "S5GXNX0R123456A");
+        assert_eq!(identity.model, "Samsung SSD 980 PRO 1TB");
+        assert_eq!(identity.firmware, "5B2QGXA7");
+        assert_eq!(identity.ieee_oui, "00-25-38");
+        assert_eq!(identity.controller_id, Some(6));
+        assert_eq!(identity.subsystem_nqn, "nqn.1994-11.com.samsung:nvme:980PRO:M.2:S5GXNX0R123456A");
+    }
+
+    #[test]
+    fn blank_controller_fields_are_missing() {
+        let mut data = controller_fixture();
+        data[4..24].copy_from_slice(&[b' '; 20]);
+        data[73..76].fill(0);
+        data[768..1024].fill(0);
+
+        let mut identity = NvmeIdentity::new("test");
+        identity.apply_controller(&data).unwrap();
+        assert_eq!(identity.serial, "N/A");
+        assert_eq!(identity.ieee_oui, "N/A");
+        assert_eq!(identity.subsystem_nqn, "N/A");
+    }
+
+    #[test]
+    fn decodes_namespace() {
+        let nguid = [0x00, 0x25, 0x38, 0x51, 0x11, 0xB0, 0x25, 0x6C, 0, 0, 0, 0, 0, 0, 0, 0x01];
+        let eui64 = [0x00, 0x25, 0x38, 0x51, 0x11, 0xB0, 0x25, 0x6C];
+        let mut identity = NvmeIdentity::new("test");
+        identity.apply_namespace(&namespace_fixture(1, nguid, eui64)).unwrap();
+        assert_eq!(identity.nguid, "0025385111B0256C0000000000000001");
+        assert_eq!(identity.eui64, "0025385111B0256C");
+        assert_eq!(identity.lba_size, Some(4096));
+        assert_eq!(identity.capacity_bytes, Some(244_190_646 * 4096));
+    }
+
+    #[test]
+    fn zeroed_namespace_ids_are_missing() {
+        let mut identity = NvmeIdentity::new("test");
+        identity.apply_namespace(&namespace_fixture(0, [0; 16], [0; 8])).unwrap();
+        assert_eq!(identity.nguid, "N/A");
+        assert_eq!(identity.eui64, "N/A");
+        assert_eq!(identity.lba_size, Some(4096));
+    }
+
+    #[test]
+    fn extended_lba_format_index() {
+        let mut identity = NvmeIdentity::new("test");
+        identity.apply_namespace(&namespace_fixture(17, [0; 16], [0; 8])).unwrap();
+        assert_eq!(identity.lba_size, Some(4096));
+    }
+
+    /// Whole 4096-byte Identify structures as `nvme id-ctrl -b` / `nvme id-ns -b` write them,
+    /// reconstructed for a 980 PRO with its power states, capacities and limits filled in as well;
+    /// captures from real drives can be dropped in next to them
+    fn testdata(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/nvme").join(name)
+    }
+
+    #[test]
+    fn decodes_identify_files() {
+        let identity = NvmeIdentity::from_files(Some(&testdata("id-ctrl.bin")), Some(&testdata("id-ns.bin"))).unwrap();
+        assert_eq!(identity.source, "Identify data file");
+        assert_eq!(identity.serial, "S6B0NL0T912345J");
+        assert_eq!(identity.model, "Samsung SSD 980 PRO 2TB");
+        assert_eq!(identity.firmware, "5B2QGXA7");
+        assert_eq!(identity.ieee_oui, "00-25-38");
+        assert_eq!(identity.controller_id, Some(6));
+        assert_eq!(identity.subsystem_nqn, "nqn.1994-11.com.samsung:nvme:980PRO:M.2:S6B0NL0T912345J");
+        // This drive reports an EUI-64 but leaves the NGUID zeroed
+        assert_eq!(identity.eui64, "002538B221B03C7E");
+        assert_eq!(identity.nguid, "N/A");
+        assert_eq!(identity.lba_size, Some(512));
+
+        // Namespace size agrees with the controller's total NVM capacity (TNVMCAP, bytes 280..296)
+        let controller = fs::read(testdata("id-ctrl.bin")).unwrap();
+        let tnvmcap = u128::from_le_bytes(controller[280..296].try_into().unwrap());
+        assert_eq!(identity.capacity_bytes.map(u128::from), Some(tnvmcap));
+    }
+
+    #[test]
+    fn reports_unreadable_identify_files() {
+        let missing = testdata("missing.bin");
+        let error = NvmeIdentity::from_files(Some(&missing), None).unwrap_err();
+        assert!(error.starts_with("Cannot read "), "{}", error);
+    }
+
+    #[test]
+    fn rejects_short_buffers() {
+        let mut identity = NvmeIdentity::new("test");
+        assert!(identity.apply_controller(&[0u8; 512]).is_err());
+        assert!(identity.apply_namespace(&[0u8; 4095]).is_err());
+        assert_eq!(identity.serial, "N/A");
+    }
+}
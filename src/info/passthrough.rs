@@ -0,0 +1,533 @@
+use serde::Serialize;
+
+/// Size of the ATA IDENTIFY DEVICE data block
+pub const ATA_IDENTIFY_LEN: usize = 512;
+
+/// Identity decoded from ATA IDENTIFY DEVICE data
+#[derive(Debug, Clone, Serialize)]
+pub struct AtaIdentity {
+    pub serial: String,
+    pub model: String,
+    pub firmware: String,
+    pub wwn: Option<String>,
+}
+
+/// Identity decoded from SCSI VPD pages 0x80 (unit serial) and 0x83 (device identification)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScsiVpd {
+    pub serial: Option<String>,
+    pub designators: Vec<String>,
+}
+
+impl AtaIdentity {
+    /// Decode the 256-word IDENTIFY DEVICE block
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let data = data.get(..ATA_IDENTIFY_LEN)?;
+        if data.iter().all(|&b| b == 0) {
+            return None;
+        }
+
+        // Word 255 carries an optional checksum signature (A5h) over the whole block
+        if data[510] == 0xA5 && data.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) != 0 {
+            return None;
+        }
+
+        let word = |n: usize| u16::from_le_bytes([data[n * 2], data[n * 2 + 1]]);
+
+        // Word 87 bit 8: world wide name is present in words 108-111
+        let wwn = (word(87) & 0xC100 == 0x4100)
+            .then(|| (108..112).fold(0u64, |acc, n| (acc << 16) | word(n) as u64))
+            .filter(|&wwn| wwn != 0)
+            .map(|wwn| format!("{:016X}", wwn));
+
+        Some(Self {
+            serial: ata_string(&data[20..40]),
+            model: ata_string(&data[54..94]),
+            firmware: ata_string(&data[46..54]),
+            wwn,
+        })
+    }
+
+    pub fn summary(&self, method: &str) -> String {
+        format!(
+            "SN {}, FW {}, WWN {} [{}]",
+            self.serial,
+            self.firmware,
+            self.wwn.as_deref().unwrap_or("N/A"),
+            method
+        )
+    }
+}
+
+impl ScsiVpd {
+    /// Decode whichever of the two pages could be read
+    pub fn parse(unit_serial: Option<&[u8]>, device_id: Option<&[u8]>) -> Option<Self> {
+        let vpd = Self {
+            serial: unit_serial.and_then(parse_unit_serial),
+            designators: device_id.map(parse_device_id).unwrap_or_default(),
+        };
+        (vpd.serial.is_some() || !vpd.designators.is_empty()).then_some(vpd)
+    }
+
+    /// First NAA or EUI-64 designator, usable as the disk WWN
+    pub fn wwn(&self) -> Option<&str> {
+        self.designators
+            .iter()
+            .find(|d| d.starts_with("naa.") || d.starts_with("eui."))
+            .map(|d| &d[4..])
+    }
+
+    pub fn summary(&self, method: &str) -> String {
+        let mut parts = vec![format!("SN {}", self.serial.as_deref().unwrap_or("N/A"))];
+        parts.extend(self.designators.iter().cloned());
+        format!("{} [{}]", parts.join(", "), method)
+    }
+}
+
+/// VPD page 0x80: the serial number follows the 4-byte page header
+fn parse_unit_serial(page: &[u8]) -> Option<String> {
+    if page.len() < 4 || page[1] != 0x80 {
+        return None;
+    }
+    let len = u16::from_be_bytes([page[2], page[3]]) as usize;
+    let serial = page.get(4..4 + len).unwrap_or(&page[4..]);
+    let serial = String::from_utf8_lossy(serial).trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string();
+    (!serial.is_empty()).then_some(serial)
+}
+
+/// VPD page 0x83: collect the designators that identify the logical unit itself
+fn parse_device_id(page: &[u8]) -> Vec<String> {
+    let mut designators = Vec::new();
+    if page.len() < 4 || page[1] != 0x83 {
+        return designators;
+    }
+
+    let end = (4 + u16::from_be_bytes([page[2], page[3]]) as usize).min(page.len());
+    let mut offset = 4;
+
+    while offset + 4 <= end {
+        let code_set = page[offset] & 0x0F;
+        let association = (page[offset + 1] >> 4) & 0x03;
+        let designator_type = page[offset + 1] & 0x0F;
+        let len = page[offset + 3] as usize;
+        let Some(value) = page.get(offset + 4..offset + 4 + len) else {
+            break;
+        };
+        offset += 4 + len;
+
+        // Association 0 is the logical unit, target port designators differ per path
+        if association != 0 || value.is_empty() {
+            continue;
+        }
+
+        let text = || String::from_utf8_lossy(value).trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string();
+        let hex = || value.iter().map(|b| format!("{:02X}", b)).collect::<String>();
+
+        let designator = match (designator_type, code_set) {
+            (1, 2 | 3) => format!("t10.{}", text()),
+            (2, 1) => format!("eui.{}", hex()),
+            (3, 1) => format!("naa.{}", hex()),
+            (8, 3) => text(),
+            _ => continue,
+        };
+        designators.push(designator);
+    }
+
+    designators
+}
+
+/// ATA strings store two characters per word with the bytes swapped
+fn ata_string(bytes: &[u8]) -> String {
+    let swapped: Vec<u8> = bytes.chunks_exact(2).flat_map(|pair| [pair[1], pair[0]]).collect();
+    let text = String::from_utf8_lossy(&swapped).trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string();
+    if text.is_empty() { "N/A".to_string() } else { text }
+}
+
+/// Read VPD pages the kernel caches for a SCSI device, plus the SAT ATA Information page
+#[cfg(target_os = "linux")]
+pub fn read_sysfs(device: &std::path::Path) -> (Option<ScsiVpd>, Option<AtaIdentity>) {
+    let pg80 = std::fs::read(device.join("vpd_pg80")).ok();
+    let pg83 = std::fs::read(device.join("vpd_pg83")).ok();
+    let scsi = ScsiVpd::parse(pg80.as_deref(), pg83.as_deref());
+
+    // VPD page 0x89 (SCSI/ATA translation) embeds the IDENTIFY DEVICE data at byte 60
+    let ata = std::fs::read(device.join("vpd_pg89"))
+        .ok()
+        .filter(|page| page.len() >= 2 && page[1] == 0x89)
+        .and_then(|page| page.get(60..).and_then(AtaIdentity::parse));
+
+    (scsi, ata)
+}
+
+/// Send IDENTIFY DEVICE (ECh) to the drive
+#[cfg(any(windows, target_os = "linux"))]
+pub fn ata_identify(path: &str) -> Option<AtaIdentity> {
+    let data = ata_command(path, AtaCommand::IDENTIFY)?;
+    AtaIdentity::parse(&data)
+}
+
+/// Task file of a PIO data-in ATA command that returns one 512-byte sector
+#[cfg(any(windows, target_os = "linux"))]
+#[derive(Debug, Clone, Copy)]
+pub struct AtaCommand {
+    pub features: u8,
+    pub lba_mid: u8,
+    pub lba_high: u8,
+    pub command: u8,
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+impl AtaCommand {
+    pub const IDENTIFY: Self = Self { features: 0, lba_mid: 0, lba_high: 0, command: 0xEC };
+    pub const SMART_READ_DATA: Self = Self { features: 0xD0, lba_mid: 0x4F, lba_high: 0xC2, command: 0xB0 };
+    pub const SMART_READ_THRESHOLDS: Self = Self { features: 0xD1, lba_mid: 0x4F, lba_high: 0xC2, command: 0xB0 };
+}
+
+/// Run an ATA command through IOCTL_ATA_PASS_THROUGH
+///
+/// ATA_PASS_THROUGH_EX is laid out by hand, its DataBufferOffset is pointer sized.
+#[cfg(windows)]
+pub fn ata_command(path: &str, cmd: AtaCommand) -> Option<Vec<u8>> {
+    const IOCTL_ATA_PASS_THROUGH: u32 = 0x0004_D02C;
+    const ATA_FLAGS_DRDY_REQUIRED: u16 = 0x01;
+    const ATA_FLAGS_DATA_IN: u16 = 0x02;
+    const SECTOR: usize = 512;
+
+    let ptr = std::mem::size_of::<usize>();
+    let offset_field = 20usize.div_ceil(ptr) * ptr;
+    let header_len = offset_field + ptr + 16;
+
+    let mut buffer = vec![0u8; header_len + SECTOR];
+    buffer[0..2].copy_from_slice(&(header_len as u16).to_le_bytes());
+    buffer[2..4].copy_from_slice(&(ATA_FLAGS_DRDY_REQUIRED | ATA_FLAGS_DATA_IN).to_le_bytes());
+    buffer[8..12].copy_from_slice(&(SECTOR as u32).to_le_bytes());
+    buffer[12..16].copy_from_slice(&5u32.to_le_bytes());
+    buffer[offset_field..offset_field + ptr].copy_from_slice(&header_len.to_le_bytes()[..ptr]);
+
+    // CurrentTaskFile: features, sector count, LBA low, LBA mid, LBA high, device, command
+    let task_file = offset_field + ptr + 8;
+    buffer[task_file..task_file + 7]
+        .copy_from_slice(&[cmd.features, 1, 0, cmd.lba_mid, cmd.lba_high, 0, cmd.command]);
+
+    let returned = device_ioctl(path, IOCTL_ATA_PASS_THROUGH, &mut buffer)?;
+    if returned < header_len + SECTOR {
+        return None;
+    }
+    Some(buffer[header_len..].to_vec())
+}
+
+/// Run an ATA command wrapped in a SCSI ATA PASS-THROUGH (16) CDB over SG_IO
+#[cfg(target_os = "linux")]
+pub fn ata_command(path: &str, cmd: AtaCommand) -> Option<Vec<u8>> {
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    #[repr(C)]
+    struct SgIoHdr {
+        interface_id: i32,
+        dxfer_direction: i32,
+        cmd_len: u8,
+        mx_sb_len: u8,
+        iovec_count: u16,
+        dxfer_len: u32,
+        dxferp: *mut libc::c_void,
+        cmdp: *mut u8,
+        sbp: *mut u8,
+        timeout: u32,
+        flags: u32,
+        pack_id: i32,
+        usr_ptr: *mut libc::c_void,
+        status: u8,
+        masked_status: u8,
+        msg_status: u8,
+        sb_len_wr: u8,
+        host_status: u16,
+        driver_status: u16,
+        resid: i32,
+        duration: u32,
+        info: u32,
+    }
+
+    const SG_IO: u32 = 0x2285;
+    const SG_DXFER_FROM_DEV: i32 = -3;
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)
+        .ok()?;
+
+    let mut data = vec![0u8; 512];
+    let mut sense = [0u8; 32];
+    // Protocol 4 (PIO data-in), transfer length in the sector count field, in blocks, from device
+    let mut cdb: [u8; 16] = [
+        0x85, 0x08, 0x0E, 0, cmd.features, 0, 1, 0, 0, 0, cmd.lba_mid, 0, cmd.lba_high, 0, cmd.command, 0,
+    ];
+
+    let mut hdr = SgIoHdr {
+        interface_id: 'S' as i32,
+        dxfer_direction: SG_DXFER_FROM_DEV,
+        cmd_len: cdb.len() as u8,
+        mx_sb_len: sense.len() as u8,
+        iovec_count: 0,
+        dxfer_len: data.len() as u32,
+        dxferp: data.as_mut_ptr() as *mut libc::c_void,
+        cmdp: cdb.as_mut_ptr(),
+        sbp: sense.as_mut_ptr(),
+        timeout: 5000,
+        flags: 0,
+        pack_id: 0,
+        usr_ptr: std::ptr::null_mut(),
+        status: 0,
+        masked_status: 0,
+        msg_status: 0,
+        sb_len_wr: 0,
+        host_status: 0,
+        driver_status: 0,
+        resid: 0,
+        duration: 0,
+        info: 0,
+    };
+
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), SG_IO as _, &mut hdr) };
+    if result < 0 || hdr.status != 0 || hdr.host_status != 0 || hdr.resid != 0 {
+        return None;
+    }
+    Some(data)
+}
+
+/// Read VPD pages 0x80 and 0x83 with INQUIRY (EVPD) through IOCTL_SCSI_PASS_THROUGH
+#[cfg(windows)]
+pub fn scsi_vpd(path: &str) -> Option<ScsiVpd> {
+    let pg80 = scsi_inquiry_vpd(path, 0x80);
+    let pg83 = scsi_inquiry_vpd(path, 0x83);
+    ScsiVpd::parse(pg80.as_deref(), pg83.as_deref())
+}
+
+/// SCSI_PASS_THROUGH is laid out by hand, its DataBufferOffset is pointer sized
+#[cfg(windows)]
+fn scsi_inquiry_vpd(path: &str, page: u8) -> Option<Vec<u8>> {
+    const IOCTL_SCSI_PASS_THROUGH: u32 = 0x0004_D004;
+    const SCSI_IOCTL_DATA_IN: u8 = 1;
+    const DATA_LEN: usize = 255;
+
+    let ptr = std::mem::size_of::<usize>();
+    let offset_field = 20usize.div_ceil(ptr) * ptr;
+    let cdb_field = offset_field + ptr + 4;
+    let header_len = (cdb_field + 16).div_ceil(ptr) * ptr;
+
+    let mut buffer = vec![0u8; header_len + DATA_LEN];
+    buffer[0..2].copy_from_slice(&(header_len as u16).to_le_bytes());
+    buffer[6] = 6; // CdbLength
+    buffer[8] = SCSI_IOCTL_DATA_IN;
+    buffer[12..16].copy_from_slice(&(DATA_LEN as u32).to_le_bytes());
+    buffer[16..20].copy_from_slice(&5u32.to_le_bytes());
+    buffer[offset_field..offset_field + ptr].copy_from_slice(&header_len.to_le_bytes()[..ptr]);
+    buffer[cdb_field..cdb_field + 6].copy_from_slice(&[0x12, 0x01, page, 0x00, DATA_LEN as u8, 0x00]);
+
+    device_ioctl(path, IOCTL_SCSI_PASS_THROUGH, &mut buffer)?;
+    // ScsiStatus must be GOOD
+    if buffer[2] != 0 {
+        return None;
+    }
+    Some(buffer[header_len..].to_vec())
+}
+
+/// Open a physical drive and run a buffered IOCTL in place, returning the bytes written back
+#[cfg(windows)]
+pub fn device_ioctl(path: &str, code: u32, buffer: &mut [u8]) -> Option<usize> {
+    use std::ffi::c_void;
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+    };
+    use windows::Win32::System::IO::DeviceIoControl;
+
+    const GENERIC_READ_WRITE: u32 = 0x8000_0000 | 0x4000_0000;
+
+    let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut returned = 0u32;
+
+    unsafe {
+        let handle = CreateFileW(
+            PCWSTR(wide.as_ptr()),
+            GENERIC_READ_WRITE,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            HANDLE::default(),
+        )
+        .ok()?;
+
+        let len = buffer.len() as u32;
+        let result = DeviceIoControl(
+            handle,
+            code,
+            Some(buffer.as_ptr() as *const c_void),
+            len,
+            Some(buffer.as_mut_ptr() as *mut c_void),
+            len,
+            Some(&mut returned),
+            None,
+        );
+        let _ = CloseHandle(handle);
+        result.ok()?;
+    }
+
+    Some(returned as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ATA string field: space padded, two characters per word with the bytes swapped
+    fn put_ata_string(data: &mut [u8], word: usize, words: usize, text: &str) {
+        let mut padded = format!("{:<width$}", text, width = words * 2).into_bytes();
+        for pair in padded.chunks_exact_mut(2) {
+            pair.swap(0, 1);
+        }
+        data[word * 2..(word + words) * 2].copy_from_slice(&padded);
+    }
+
+    fn put_word(data: &mut [u8], word: usize, value: u16) {
+        data[word * 2..word * 2 + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// IDENTIFY DEVICE block as a WD Red reports it, with the word 255 checksum
+    fn identify(word87: u16, wwn: [u16; 4]) -> Vec<u8> {
+        let mut data = vec![0u8; ATA_IDENTIFY_LEN];
+        put_word(&mut data, 0, 0x427A);
+        put_ata_string(&mut data, 10, 10, "WD-WCC4N1234567");
+        put_ata_string(&mut data, 23, 4, "82.00A82");
+        put_ata_string(&mut data, 27, 20, "WDC WD40EFRX-68N32N0");
+        put_word(&mut data, 87, word87);
+        for (i, w) in wwn.iter().enumerate() {
+            put_word(&mut data, 108 + i, *w);
+        }
+        seal(data)
+    }
+
+    /// Signature A5h in the low byte of word 255, the high byte makes the block sum to zero
+    fn seal(mut data: Vec<u8>) -> Vec<u8> {
+        data[510] = 0xA5;
+        let sum = data[..511].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+        data[511] = 0u8.wrapping_sub(sum);
+        data
+    }
+
+    const WWN: [u16; 4] = [0x5001, 0x4EE2, 0x0B1C, 0x7D3A];
+
+    #[test]
+    fn decodes_identify_strings() {
+        let identity = AtaIdentity::parse(&identify(0x4100, WWN)).unwrap();
+        assert_eq!(identity.serial, "WD-WCC4N1234567");
+        assert_eq!(identity.model, "WDC WD40EFRX-68N32N0");
+        assert_eq!(identity.firmware, "82.00A82");
+        assert_eq!(identity.wwn.as_deref(), Some("50014EE20B1C7D3A"));
+        assert_eq!(
+            identity.summary("SAT"),
+            "SN WD-WCC4N1234567, FW 82.00A82, WWN 50014EE20B1C7D3A [SAT]"
+        );
+    }
+
+    #[test]
+    fn wwn_needs_word_87() {
+        // Bit 8 clear: words 108-111 hold nothing
+        assert_eq!(AtaIdentity::parse(&identify(0x4000, WWN)).unwrap().wwn, None);
+        // Bits 15:14 must read 01b or the word is not valid
+        assert_eq!(AtaIdentity::parse(&identify(0xC100, WWN)).unwrap().wwn, None);
+        assert_eq!(AtaIdentity::parse(&identify(0x0100, WWN)).unwrap().wwn, None);
+        assert_eq!(AtaIdentity::parse(&identify(0x4100, [0; 4])).unwrap().wwn, None);
+    }
+
+    #[test]
+    fn checks_identify_checksum() {
+        let mut data = identify(0x4100, WWN);
+        data[40] ^= 0x20;
+        assert!(AtaIdentity::parse(&data).is_none());
+
+        // Without the A5h signature there is no checksum to check
+        data[510] = 0;
+        assert!(AtaIdentity::parse(&data).is_some());
+
+        assert!(AtaIdentity::parse(&[0u8; ATA_IDENTIFY_LEN]).is_none());
+        assert!(AtaIdentity::parse(&identify(0x4100, WWN)[..256]).is_none());
+    }
+
+    #[test]
+    fn ata_strings_swap_and_trim() {
+        assert_eq!(ata_string(b"DWW-CC"), "WD-WCC");
+        assert_eq!(ata_string(b"1A  \0\0"), "A1");
+        assert_eq!(ata_string(b"        "), "N/A");
+    }
+
+    /// VPD page with a 4-byte header
+    fn vpd_page(code: u8, payload: &[u8]) -> Vec<u8> {
+        let mut page = vec![0x00, code];
+        page.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        page.extend_from_slice(payload);
+        page
+    }
+
+    fn designator(code_set: u8, association: u8, designator_type: u8, value: &[u8]) -> Vec<u8> {
+        let mut d = vec![code_set, (association << 4) | designator_type, 0, value.len() as u8];
+        d.extend_from_slice(value);
+        d
+    }
+
+    #[test]
+    fn decodes_unit_serial_page() {
+        let page = vpd_page(0x80, b"  S4EVNX0N123456\0\0");
+        assert_eq!(parse_unit_serial(&page).as_deref(), Some("S4EVNX0N123456"));
+
+        // A page cut short keeps what arrived
+        assert_eq!(parse_unit_serial(&page[..10]).as_deref(), Some("S4EV"));
+        assert_eq!(parse_unit_serial(&vpd_page(0x80, b"    ")), None);
+        assert_eq!(parse_unit_serial(&vpd_page(0x83, b"S4EVNX0N123456")), None);
+        assert_eq!(parse_unit_serial(&[0x00, 0x80]), None);
+    }
+
+    #[test]
+    fn decodes_device_identification_page() {
+        let naa = [0x50, 0x02, 0x53, 0x8E, 0x40, 0xA1, 0xB2, 0xC3];
+        let eui = [0x00, 0x25, 0x38, 0x5B, 0x71, 0xB0, 0x12, 0x34];
+        let mut payload = Vec::new();
+        payload.extend(designator(2, 0, 1, b"ATA     Samsung SSD 860 EVO S4EVNX0N123456  "));
+        // Target port designators differ per path and are skipped
+        payload.extend(designator(1, 1, 3, &[0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]));
+        payload.extend(designator(1, 0, 3, &naa));
+        payload.extend(designator(1, 0, 2, &eui));
+        payload.extend(designator(3, 0, 8, b"naa.5002538E40A1B2C3\0\0\0\0"));
+        let page = vpd_page(0x83, &payload);
+
+        let designators = parse_device_id(&page);
+        assert_eq!(
+            designators,
+            [
+                "t10.ATA     Samsung SSD 860 EVO S4EVNX0N123456",
+                "naa.5002538E40A1B2C3",
+                "eui.0025385B71B01234",
+                "naa.5002538E40A1B2C3",
+            ]
+        );
+
+        let vpd = ScsiVpd::parse(Some(&vpd_page(0x80, b"S4EVNX0N123456")), Some(&page)).unwrap();
+        assert_eq!(vpd.wwn(), Some("5002538E40A1B2C3"));
+    }
+
+    #[test]
+    fn stops_at_truncated_designators() {
+        let mut payload = designator(1, 0, 3, &[0x50, 0x02, 0x53, 0x8E, 0x40, 0xA1, 0xB2, 0xC3]);
+        payload.extend(designator(1, 0, 2, &[0x00, 0x25, 0x38, 0x5B, 0x71, 0xB0, 0x12, 0x34]));
+        let page = vpd_page(0x83, &payload);
+
+        // The second designator claims 8 bytes but the page ends after 3 of them
+        let truncated = &page[..page.len() - 5];
+        assert_eq!(parse_device_id(truncated), ["naa.5002538E40A1B2C3"]);
+        assert!(parse_device_id(&page[..6]).is_empty());
+        assert!(ScsiVpd::parse(None, Some(&page[..6])).is_none());
+    }
+}
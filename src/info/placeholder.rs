@@ -0,0 +1,270 @@
+use serde::Serialize;
+
+/// Template text firmware and device vendors leave in fields they never filled in
+///
+/// Patterns are matched case-insensitively against the whole trimmed value, with `_` and
+/// runs of whitespace read as a single space. Spaces are optional, so "DefaultString" is
+/// "default string". `*` matches any run of characters.
+pub const DEFAULT_PATTERNS: [&str; 28] = [
+    "*to be filled*",
+    "*o.e.m*",
+    "oem",
+    "default string",
+    "default",
+    "not specified",
+    "not available",
+    "not applicable",
+    "none",
+    "n/a",
+    "na",
+    "unknown",
+    "undefined",
+    "null",
+    "empty",
+    "no asset tag",
+    "no asset information",
+    "system serial number",
+    "system product name",
+    "system version",
+    "system manufacturer",
+    "base board serial number",
+    "base board product name",
+    "chassis serial number",
+    "chassis manufacture",
+    "0123456789",
+    "123456789",
+    "1234567890",
+];
+
+/// Fewer letters and digits than this and a value cannot identify anything
+const MIN_LENGTH: usize = 2;
+
+/// A value made of one character this many times or more is filler
+const MIN_REPEAT: usize = 4;
+
+/// Values the collectors themselves write when a field is missing
+const MISSING: [&str; 2] = ["N/A", "Unknown"];
+
+/// Why a value was rejected as a placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JunkReason {
+    Blank,
+    AllZeros,
+    AllFs,
+    TemplateText,
+    TooShort,
+    RepeatedCharacter,
+}
+
+impl JunkReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JunkReason::Blank => "blank",
+            JunkReason::AllZeros => "all zeros",
+            JunkReason::AllFs => "all F's",
+            JunkReason::TemplateText => "vendor template text",
+            JunkReason::TooShort => "too short",
+            JunkReason::RepeatedCharacter => "repeated character",
+        }
+    }
+}
+
+/// A collected value replaced with N/A, keyed by its snapshot path
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JunkValue {
+    pub path: String,
+    pub value: String,
+    pub reason: JunkReason,
+}
+
+/// Placeholder patterns shared by every collector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholders {
+    patterns: Vec<String>,
+}
+
+impl Default for Placeholders {
+    fn default() -> Self {
+        Self::new(DEFAULT_PATTERNS.iter().map(|p| p.to_string()).collect())
+    }
+}
+
+impl Placeholders {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self {
+            patterns: patterns.iter().map(|p| normalize(p)).collect(),
+        }
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Why `value` is not a real identifier, `None` when it looks genuine
+    pub fn classify(&self, value: &str) -> Option<JunkReason> {
+        let text = normalize(value);
+        if text.is_empty() {
+            return Some(JunkReason::Blank);
+        }
+
+        // Separators and a hex prefix say nothing, "0x0000-00:00" is all zeros
+        let compact: String = text.chars().filter(|c| c.is_alphanumeric()).collect();
+        let digits = match compact.strip_prefix("0x") {
+            Some(rest) if !rest.is_empty() => rest,
+            _ => compact.as_str(),
+        };
+
+        if !digits.is_empty() && digits.chars().all(|c| c == '0') {
+            Some(JunkReason::AllZeros)
+        } else if !digits.is_empty() && digits.chars().all(|c| c == 'f') {
+            Some(JunkReason::AllFs)
+        } else if self.is_template(&text) {
+            Some(JunkReason::TemplateText)
+        } else if compact.chars().count() < MIN_LENGTH {
+            Some(JunkReason::TooShort)
+        } else if compact.chars().count() >= MIN_REPEAT && compact.chars().all(|c| compact.starts_with(c)) {
+            Some(JunkReason::RepeatedCharacter)
+        } else {
+            None
+        }
+    }
+
+    /// Whether normalized `text` matches a pattern, with or without its spaces
+    fn is_template(&self, text: &str) -> bool {
+        let squashed = text.replace(' ', "");
+        self.patterns
+            .iter()
+            .any(|pattern| glob_match(pattern, text) || glob_match(&pattern.replace(' ', ""), &squashed))
+    }
+
+    /// Replace placeholder values with N/A, returning what was replaced and why
+    pub fn scrub<'a>(&self, fields: impl IntoIterator<Item = (String, &'a mut String)>) -> Vec<JunkValue> {
+        let mut junk = Vec::new();
+        for (path, value) in fields {
+            if MISSING.contains(&value.as_str()) {
+                continue;
+            }
+            if let Some(reason) = self.classify(value) {
+                junk.push(JunkValue {
+                    path,
+                    value: std::mem::replace(value, "N/A".to_string()),
+                    reason,
+                });
+            }
+        }
+        junk
+    }
+}
+
+/// Lowercase, trimmed (NULs included), `_` as space and whitespace runs collapsed
+fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .replace('_', " ")
+        .split(|c: char| c.is_whitespace() || c == '\0')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Match `text` against `pattern`, where `*` stands for any run of characters
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` at all, the pattern is the whole value
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_vendor_placeholders() {
+        let corpus = [
+            ("To Be Filled By O.E.M.", JunkReason::TemplateText),
+            ("To_Be_Filled_By_O.E.M.", JunkReason::TemplateText),
+            ("Default string", JunkReason::TemplateText),
+            ("DefaultString", JunkReason::TemplateText),
+            ("  Default   String\0\0", JunkReason::TemplateText),
+            ("0123456789", JunkReason::TemplateText),
+            ("System Serial Number", JunkReason::TemplateText),
+            ("SystemSerialNumber", JunkReason::TemplateText),
+            ("Not Specified", JunkReason::TemplateText),
+            ("O.E.M.", JunkReason::TemplateText),
+            ("00000000-0000-0000-0000-000000000000", JunkReason::AllZeros),
+            ("0x00000000", JunkReason::AllZeros),
+            ("FFFFFFFF", JunkReason::AllFs),
+            ("FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF", JunkReason::AllFs),
+            ("", JunkReason::Blank),
+            ("   ", JunkReason::Blank),
+            ("X", JunkReason::TooShort),
+            ("11111111", JunkReason::RepeatedCharacter),
+        ];
+        let placeholders = Placeholders::default();
+        for (value, reason) in corpus {
+            assert_eq!(placeholders.classify(value), Some(reason), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn keeps_genuine_serials() {
+        let genuine = [
+            "PF2ABC12",
+            "C02XK1JHJG5J",
+            "5CD1234XYZ",
+            "4C4C4544-0042-3510-8048-B4C04F4E3732",
+            "S4EVNX0N123456",
+            "MXL1234567",
+            "WD-WCC4N1234567",
+            "00:1A:2B:3C:4D:5E",
+            "OEM12345",
+            "Defaulted",
+            "1234",
+        ];
+        let placeholders = Placeholders::default();
+        for value in genuine {
+            assert_eq!(placeholders.classify(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn custom_patterns_replace_defaults() {
+        let placeholders = Placeholders::new(vec!["asset-*".to_string()]);
+        assert_eq!(placeholders.classify("ASSET-0001"), Some(JunkReason::TemplateText));
+        assert_eq!(placeholders.classify("Default string"), None);
+        // The structural checks always apply
+        assert_eq!(placeholders.classify("0000"), Some(JunkReason::AllZeros));
+    }
+
+    #[test]
+    fn scrub_replaces_junk_and_skips_missing() {
+        let mut serial = "To Be Filled By O.E.M.".to_string();
+        let mut uuid = "N/A".to_string();
+        let mut tag = "PF2ABC12".to_string();
+        let junk = Placeholders::default().scrub([
+            ("system.serial_number".to_string(), &mut serial),
+            ("system.uuid".to_string(), &mut uuid),
+            ("chassis.asset_tag".to_string(), &mut tag),
+        ]);
+        assert_eq!(junk.len(), 1);
+        assert_eq!(junk[0].path, "system.serial_number");
+        assert_eq!(junk[0].value, "To Be Filled By O.E.M.");
+        assert_eq!((serial.as_str(), uuid.as_str(), tag.as_str()), ("N/A", "N/A", "PF2ABC12"));
+    }
+}
@@ -0,0 +1,466 @@
+use serde::Serialize;
+
+/// Size of the ATA SMART data sector and the NVMe SMART / Health Information log
+pub const SMART_LOG_LEN: usize = 512;
+
+/// NVMe log identifier of the SMART / Health Information page
+pub const NVME_HEALTH_LOG_ID: u8 = 0x02;
+
+/// Drive temperature that is worth a warning during inventory
+const HOT_CELSIUS: i64 = 60;
+
+/// Wear level (percentage of rated endurance used) that is worth a warning
+const WORN_PERCENT: u8 = 90;
+
+const ATA_ATTRIBUTES: &[(u8, &str)] = &[
+    (1, "Raw Read Error Rate"),
+    (5, "Reallocated Sectors"),
+    (9, "Power-On Hours"),
+    (10, "Spin Retry Count"),
+    (12, "Power Cycles"),
+    (177, "Wear Leveling Count"),
+    (184, "End-to-End Errors"),
+    (187, "Reported Uncorrectable"),
+    (190, "Airflow Temperature"),
+    (194, "Temperature"),
+    (196, "Reallocation Events"),
+    (197, "Pending Sectors"),
+    (198, "Offline Uncorrectable"),
+    (199, "UDMA CRC Errors"),
+    (231, "SSD Life Left"),
+    (233, "Media Wearout Indicator"),
+    (241, "Total LBAs Written"),
+    (242, "Total LBAs Read"),
+];
+
+/// Overall verdict for a drive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum HealthStatus {
+    Good,
+    Warning,
+    Failing,
+}
+
+impl HealthStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HealthStatus::Good => "Good",
+            HealthStatus::Warning => "Warning",
+            HealthStatus::Failing => "Failing",
+        }
+    }
+}
+
+/// One entry of the ATA SMART attribute table
+#[derive(Debug, Clone, Serialize)]
+pub struct SmartAttribute {
+    pub id: u8,
+    pub name: String,
+    pub current: u8,
+    pub worst: u8,
+    pub threshold: Option<u8>,
+    pub raw: u64,
+}
+
+/// Health counters decoded from ATA SMART data or the NVMe health log
+#[derive(Debug, Clone, Serialize)]
+pub struct SmartHealth {
+    pub status: HealthStatus,
+    pub source: String,
+    pub temperature_c: Option<i64>,
+    pub power_on_hours: Option<u64>,
+    pub power_cycles: Option<u64>,
+    pub percentage_used: Option<u8>,
+    pub reallocated_sectors: Option<u64>,
+    pub pending_sectors: Option<u64>,
+    pub uncorrectable_sectors: Option<u64>,
+    pub available_spare: Option<u8>,
+    pub critical_warning: Option<u8>,
+    pub media_errors: Option<u64>,
+    pub unsafe_shutdowns: Option<u64>,
+    pub attributes: Vec<SmartAttribute>,
+    pub findings: Vec<String>,
+}
+
+impl SmartHealth {
+    fn new(source: &str) -> Self {
+        Self {
+            status: HealthStatus::Good,
+            source: source.to_string(),
+            temperature_c: None,
+            power_on_hours: None,
+            power_cycles: None,
+            percentage_used: None,
+            reallocated_sectors: None,
+            pending_sectors: None,
+            uncorrectable_sectors: None,
+            available_spare: None,
+            critical_warning: None,
+            media_errors: None,
+            unsafe_shutdowns: None,
+            attributes: Vec::new(),
+            findings: Vec::new(),
+        }
+    }
+
+    /// Decode SMART READ DATA, optionally with SMART READ THRESHOLDS
+    pub fn from_ata(data: &[u8], thresholds: Option<&[u8]>, source: &str) -> Option<Self> {
+        let data = data.get(..SMART_LOG_LEN)?;
+        if data.iter().all(|&b| b == 0) || !checksum_ok(data) {
+            return None;
+        }
+
+        let thresholds = thresholds
+            .and_then(|t| t.get(..SMART_LOG_LEN))
+            .filter(|t| checksum_ok(t));
+
+        let mut health = Self::new(source);
+
+        // 30 entries of 12 bytes after the 2-byte revision number
+        for entry in data[2..362].chunks_exact(12) {
+            let id = entry[0];
+            if id == 0 {
+                continue;
+            }
+            let mut raw_bytes = [0u8; 8];
+            raw_bytes[..6].copy_from_slice(&entry[5..11]);
+            let threshold = thresholds.and_then(|t| {
+                t[2..362].chunks_exact(12).find(|th| th[0] == id).map(|th| th[1])
+            });
+
+            health.attributes.push(SmartAttribute {
+                id,
+                name: ATA_ATTRIBUTES
+                    .iter()
+                    .find(|(known, _)| *known == id)
+                    .map(|(_, name)| name.to_string())
+                    .unwrap_or_else(|| format!("Attribute {}", id)),
+                current: entry[3],
+                worst: entry[4],
+                threshold,
+                raw: u64::from_le_bytes(raw_bytes),
+            });
+        }
+
+        let attribute = |id: u8| health.attributes.iter().find(|a| a.id == id);
+
+        // Vendors pack extra data into the upper raw bytes of hours and temperature
+        let power_on_hours = attribute(9).map(|a| a.raw & 0xFFFF_FFFF);
+        let power_cycles = attribute(12).map(|a| a.raw);
+        let temperature_c = attribute(194).or(attribute(190)).map(|a| (a.raw & 0xFF) as i64);
+        let reallocated_sectors = attribute(5).map(|a| a.raw);
+        let pending_sectors = attribute(197).map(|a| a.raw);
+        let uncorrectable_sectors = attribute(198).or(attribute(187)).map(|a| a.raw);
+        // Wear attributes count down from 100 as the flash wears out
+        let percentage_used = attribute(233)
+            .or(attribute(231))
+            .or(attribute(177))
+            .map(|a| 100u8.saturating_sub(a.current.min(100)));
+        let tripped: Vec<String> = health
+            .attributes
+            .iter()
+            .filter(|a| a.threshold.is_some_and(|t| t > 0 && a.current <= t))
+            .map(|a| format!("{} at or below threshold ({} <= {})", a.name, a.current, a.threshold.unwrap_or(0)))
+            .collect();
+
+        health.power_on_hours = power_on_hours;
+        health.power_cycles = power_cycles;
+        health.temperature_c = temperature_c;
+        health.reallocated_sectors = reallocated_sectors;
+        health.pending_sectors = pending_sectors;
+        health.uncorrectable_sectors = uncorrectable_sectors;
+        health.percentage_used = percentage_used;
+
+        for finding in tripped {
+            health.flag(HealthStatus::Failing, finding);
+        }
+        for (count, what) in [
+            (reallocated_sectors, "reallocated sectors"),
+            (pending_sectors, "pending sectors"),
+            (uncorrectable_sectors, "uncorrectable sectors"),
+        ] {
+            if let Some(n) = count.filter(|&n| n > 0) {
+                health.flag(HealthStatus::Warning, format!("{} {}", n, what));
+            }
+        }
+        health.check_common();
+
+        Some(health)
+    }
+
+    /// Decode the NVMe SMART / Health Information log page (02h)
+    pub fn from_nvme(data: &[u8], source: &str) -> Option<Self> {
+        let data = data.get(..SMART_LOG_LEN)?;
+        let mut health = Self::new(source);
+
+        let u128_at = |offset: usize| u128::from_le_bytes(data[offset..offset + 16].try_into().unwrap());
+        let counter = |offset: usize| u64::try_from(u128_at(offset)).unwrap_or(u64::MAX);
+
+        let critical_warning = data[0];
+        let kelvin = u16::from_le_bytes([data[1], data[2]]) as i64;
+        let available_spare = data[3];
+        let spare_threshold = data[4];
+
+        health.critical_warning = Some(critical_warning);
+        health.temperature_c = (kelvin > 0).then_some(kelvin - 273);
+        health.available_spare = Some(available_spare);
+        health.percentage_used = Some(data[5]);
+        health.power_cycles = Some(counter(112));
+        health.power_on_hours = Some(counter(128));
+        health.unsafe_shutdowns = Some(counter(144));
+        health.media_errors = Some(counter(160));
+
+        const CRITICAL_WARNINGS: &[(u8, &str)] = &[
+            (0x01, "available spare below threshold"),
+            (0x02, "temperature outside limits"),
+            (0x04, "reliability degraded by media errors"),
+            (0x08, "media placed in read-only mode"),
+            (0x10, "volatile memory backup failed"),
+            (0x20, "persistent memory region read-only"),
+        ];
+        for (bit, what) in CRITICAL_WARNINGS {
+            if critical_warning & bit != 0 {
+                health.flag(HealthStatus::Failing, format!("Critical warning: {}", what));
+            }
+        }
+        if critical_warning & 0x01 == 0 && spare_threshold > 0 && available_spare < spare_threshold {
+            health.flag(HealthStatus::Failing, format!("Available spare {}% below threshold {}%", available_spare, spare_threshold));
+        }
+        if let Some(n) = health.media_errors.filter(|&n| n > 0) {
+            health.flag(HealthStatus::Warning, format!("{} media errors", n));
+        }
+        health.check_common();
+
+        Some(health)
+    }
+
+    /// Read SMART straight from the drive (needs admin/root)
+    #[cfg(any(windows, target_os = "linux"))]
+    pub fn read(device_path: &str, nvme: bool) -> Option<Self> {
+        use crate::info::nvme;
+        use crate::info::passthrough::{ata_command, AtaCommand};
+
+        if nvme {
+            let log = nvme::get_log_page(device_path, NVME_HEALTH_LOG_ID, SMART_LOG_LEN)?;
+            return Self::from_nvme(&log, "NVMe health log");
+        }
+
+        let data = ata_command(device_path, AtaCommand::SMART_READ_DATA)?;
+        let thresholds = ata_command(device_path, AtaCommand::SMART_READ_THRESHOLDS);
+        Self::from_ata(&data, thresholds.as_deref(), "ATA SMART READ DATA")
+    }
+
+    /// Temperature and wear checks shared by both formats
+    fn check_common(&mut self) {
+        if let Some(temp) = self.temperature_c.filter(|&t| t >= HOT_CELSIUS) {
+            self.flag(HealthStatus::Warning, format!("Temperature {} C", temp));
+        }
+        if let Some(used) = self.percentage_used.filter(|&u| u >= WORN_PERCENT) {
+            self.flag(HealthStatus::Warning, format!("{}% of rated endurance used", used));
+        }
+    }
+
+    fn flag(&mut self, status: HealthStatus, finding: String) {
+        if status == HealthStatus::Failing || self.status == HealthStatus::Good {
+            self.status = status;
+        }
+        self.findings.push(finding);
+    }
+
+    /// One-line summary for the disk list
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(hours) = self.power_on_hours {
+            parts.push(format!("{} h", hours));
+        }
+        if let Some(temp) = self.temperature_c {
+            parts.push(format!("{} C", temp));
+        }
+        if let Some(used) = self.percentage_used {
+            parts.push(format!("{}% used", used));
+        }
+
+        if parts.is_empty() {
+            format!("{} [{}]", self.status.label(), self.source)
+        } else {
+            format!("{} ({}) [{}]", self.status.label(), parts.join(", "), self.source)
+        }
+    }
+}
+
+/// SMART data sectors end with a checksum byte that makes the sector sum to zero
+fn checksum_ok(sector: &[u8]) -> bool {
+    sector.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fix the last byte so the sector sums to zero
+    fn seal(mut sector: Vec<u8>) -> Vec<u8> {
+        let sum = sector[..511].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+        sector[511] = 0u8.wrapping_sub(sum);
+        sector
+    }
+
+    /// SMART READ DATA with (id, current, worst, raw) entries in table order
+    fn ata_data(attributes: &[(u8, u8, u8, u64)]) -> Vec<u8> {
+        let mut sector = vec![0u8; SMART_LOG_LEN];
+        sector[0] = 0x10;
+        for (i, &(id, current, worst, raw)) in attributes.iter().enumerate() {
+            let entry = &mut sector[2 + i * 12..2 + (i + 1) * 12];
+            entry[0] = id;
+            entry[1] = 0x33;
+            entry[3] = current;
+            entry[4] = worst;
+            entry[5..11].copy_from_slice(&raw.to_le_bytes()[..6]);
+        }
+        seal(sector)
+    }
+
+    /// SMART READ THRESHOLDS with (id, threshold) entries
+    fn ata_thresholds(thresholds: &[(u8, u8)]) -> Vec<u8> {
+        let mut sector = vec![0u8; SMART_LOG_LEN];
+        sector[0] = 0x10;
+        for (i, &(id, threshold)) in thresholds.iter().enumerate() {
+            sector[2 + i * 12] = id;
+            sector[3 + i * 12] = threshold;
+        }
+        seal(sector)
+    }
+
+    #[test]
+    fn decodes_ata_attribute_table() {
+        let data = ata_data(&[
+            (5, 100, 100, 8),
+            // Seagate packs milliseconds above the hour count
+            (9, 91, 91, 0x0012_0000_1F40),
+            (177, 97, 97, 42),
+            // Current, minimum and maximum temperature share the raw field
+            (194, 64, 48, 0x0028_0014_0024),
+            (250, 100, 100, 1),
+        ]);
+        let health = SmartHealth::from_ata(&data, None, "test").unwrap();
+
+        let names: Vec<&str> = health.attributes.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["Reallocated Sectors", "Power-On Hours", "Wear Leveling Count", "Temperature", "Attribute 250"]);
+        assert_eq!(health.attributes[1].raw, 0x0012_0000_1F40);
+        assert_eq!(health.power_on_hours, Some(8000));
+        assert_eq!(health.temperature_c, Some(36));
+        assert_eq!(health.percentage_used, Some(3));
+        assert_eq!(health.reallocated_sectors, Some(8));
+        assert_eq!(health.status, HealthStatus::Warning);
+        assert_eq!(health.findings, ["8 reallocated sectors"]);
+        assert_eq!(health.summary(), "Warning (8000 h, 36 C, 3% used) [test]");
+    }
+
+    #[test]
+    fn trips_ata_thresholds() {
+        let data = ata_data(&[(5, 9, 9, 0), (9, 100, 100, 10)]);
+        let thresholds = ata_thresholds(&[(5, 10), (9, 0)]);
+        let health = SmartHealth::from_ata(&data, Some(&thresholds), "test").unwrap();
+        assert_eq!(health.attributes[0].threshold, Some(10));
+        assert_eq!(health.status, HealthStatus::Failing);
+        // A zero threshold means "never fails" and is not compared
+        assert_eq!(health.findings, ["Reallocated Sectors at or below threshold (9 <= 10)"]);
+
+        // Thresholds with a bad checksum are ignored rather than misread
+        let mut corrupt = thresholds.clone();
+        corrupt[3] = 50;
+        let health = SmartHealth::from_ata(&data, Some(&corrupt), "test").unwrap();
+        assert_eq!(health.attributes[0].threshold, None);
+        assert_eq!(health.status, HealthStatus::Good);
+    }
+
+    #[test]
+    fn rejects_bad_ata_sectors() {
+        let data = ata_data(&[(9, 100, 100, 10)]);
+        assert!(checksum_ok(&data));
+        assert!(SmartHealth::from_ata(&data, None, "test").is_some());
+
+        let mut corrupt = data.clone();
+        corrupt[7] ^= 0x01;
+        assert!(!checksum_ok(&corrupt));
+        assert!(SmartHealth::from_ata(&corrupt, None, "test").is_none());
+
+        // All zeros sums to zero but is a drive with SMART disabled
+        assert!(SmartHealth::from_ata(&[0u8; SMART_LOG_LEN], None, "test").is_none());
+        assert!(SmartHealth::from_ata(&data[..400], None, "test").is_none());
+    }
+
+    /// NVMe SMART / Health Information log page
+    fn nvme_log(critical_warning: u8, kelvin: u16, spare: u8, spare_threshold: u8, used: u8) -> Vec<u8> {
+        let mut log = vec![0u8; SMART_LOG_LEN];
+        log[0] = critical_warning;
+        log[1..3].copy_from_slice(&kelvin.to_le_bytes());
+        log[3] = spare;
+        log[4] = spare_threshold;
+        log[5] = used;
+        log
+    }
+
+    fn put_u128(log: &mut [u8], offset: usize, value: u128) {
+        log[offset..offset + 16].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn decodes_nvme_health_log() {
+        let mut log = nvme_log(0, 310, 100, 10, 7);
+        put_u128(&mut log, 112, 1520);
+        put_u128(&mut log, 128, 12_345);
+        put_u128(&mut log, 144, 37);
+        let health = SmartHealth::from_nvme(&log, "test").unwrap();
+
+        assert_eq!(health.temperature_c, Some(37));
+        assert_eq!(health.available_spare, Some(100));
+        assert_eq!(health.percentage_used, Some(7));
+        assert_eq!(health.power_cycles, Some(1520));
+        assert_eq!(health.power_on_hours, Some(12_345));
+        assert_eq!(health.unsafe_shutdowns, Some(37));
+        assert_eq!(health.media_errors, Some(0));
+        assert_eq!(health.status, HealthStatus::Good);
+        assert!(health.findings.is_empty());
+
+        // No sensor reading is 0 K, not -273 C
+        assert_eq!(SmartHealth::from_nvme(&nvme_log(0, 0, 100, 10, 0), "test").unwrap().temperature_c, None);
+        assert!(SmartHealth::from_nvme(&log[..256], "test").is_none());
+    }
+
+    #[test]
+    fn saturates_128_bit_counters() {
+        let mut log = nvme_log(0, 300, 100, 10, 0);
+        put_u128(&mut log, 128, u64::MAX as u128 + 1);
+        put_u128(&mut log, 160, 3);
+        let health = SmartHealth::from_nvme(&log, "test").unwrap();
+        assert_eq!(health.power_on_hours, Some(u64::MAX));
+        assert_eq!(health.media_errors, Some(3));
+        assert_eq!(health.status, HealthStatus::Warning);
+        assert_eq!(health.findings, ["3 media errors"]);
+    }
+
+    #[test]
+    fn flags_nvme_critical_warnings() {
+        let health = SmartHealth::from_nvme(&nvme_log(0x05, 300, 5, 10, 0), "test").unwrap();
+        assert_eq!(health.critical_warning, Some(0x05));
+        assert_eq!(health.status, HealthStatus::Failing);
+        // Bit 0 already reports the spare, it is not flagged twice
+        assert_eq!(
+            health.findings,
+            [
+                "Critical warning: available spare below threshold",
+                "Critical warning: reliability degraded by media errors",
+            ]
+        );
+
+        let health = SmartHealth::from_nvme(&nvme_log(0, 300, 5, 10, 0), "test").unwrap();
+        assert_eq!(health.findings, ["Available spare 5% below threshold 10%"]);
+    }
+
+    #[test]
+    fn warns_on_heat_and_wear() {
+        let health = SmartHealth::from_nvme(&nvme_log(0, 273 + HOT_CELSIUS as u16, 100, 10, WORN_PERCENT), "test").unwrap();
+        assert_eq!(health.status, HealthStatus::Warning);
+        assert_eq!(health.findings, ["Temperature 60 C", "90% of rated endurance used"]);
+    }
+}
@@ -1,10 +1,11 @@
+use serde::Serialize;
 #[cfg(windows)]
 use serde::Deserialize;
 
 #[cfg(windows)]
-use wmi::{COMLibrary, WMIConnection};
+use crate::info::wmi_session;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SystemInfo {
     pub manufacturer: String,
     pub product_name: String,
@@ -63,17 +64,9 @@ impl SystemInfo {
 
     #[cfg(windows)]
     fn collect_windows() -> Self {
-        let com_con = COMLibrary::new();
-        if com_con.is_err() {
+        let Some(wmi_con) = wmi_session::connection(wmi_session::CIMV2) else {
             return Self::default();
-        }
-        let com_con = com_con.unwrap();
-        
-        let wmi_con = WMIConnection::new(com_con);
-        if wmi_con.is_err() {
-            return Self::default();
-        }
-        let wmi_con = wmi_con.unwrap();
+        };
 
         // Query Win32_ComputerSystemProduct for UUID, Serial, SKU
         let products: Vec<Win32ComputerSystemProduct> = wmi_con
@@ -93,33 +86,26 @@ impl SystemInfo {
             manufacturer: system
                 .and_then(|s| s.manufacturer.clone())
                 .or_else(|| product.and_then(|p| p.vendor.clone()))
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             product_name: system
                 .and_then(|s| s.model.clone())
                 .or_else(|| product.and_then(|p| p.name.clone()))
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             version: product
                 .and_then(|p| p.version.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             serial_number: product
                 .and_then(|p| p.identifying_number.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             uuid: product
                 .and_then(|p| p.uuid.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             family: system
                 .and_then(|s| s.system_family.clone())
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
             sku: system
                 .and_then(|s| s.system_sku_number.clone())
                 .or_else(|| product.and_then(|p| p.sku_number.clone()))
-                .filter(|s| !is_placeholder(s))
                 .unwrap_or_else(|| "N/A".to_string()),
         }
     }
@@ -138,17 +124,3 @@ impl Default for SystemInfo {
         }
     }
 }
-
-/// Check if a string is a placeholder/empty value
-fn is_placeholder(s: &str) -> bool {
-    let lower = s.trim().to_lowercase();
-    lower.is_empty() 
-        || lower.contains("to be filled")
-        || lower.contains("o.e.m")
-        || lower == "default string"
-        || lower == "not specified"
-        || lower == "none"
-        || lower == "unknown"
-        || lower == "system serial number"
-        || lower == "system product name"
-}
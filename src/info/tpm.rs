@@ -0,0 +1,493 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[cfg(windows)]
+use serde::Deserialize;
+
+#[cfg(windows)]
+use crate::info::wmi_session;
+
+#[cfg(windows)]
+use winreg::enums::*;
+#[cfg(windows)]
+use winreg::RegKey;
+
+/// TPM class directory the kernel creates for the first TPM
+#[cfg(target_os = "linux")]
+pub const SYSFS_DIR: &str = "/sys/class/tpm/tpm0";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TpmInfo {
+    pub present: bool,
+    pub spec_version: String,
+    pub manufacturer_id: String,
+    pub manufacturer: String,
+    pub firmware_version: String,
+    pub ek_cert_fingerprint: String,
+    pub source: String,
+}
+
+#[cfg(windows)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Win32Tpm {
+    #[serde(default)]
+    spec_version: Option<String>,
+    #[serde(default)]
+    manufacturer_id: Option<u32>,
+    #[serde(default)]
+    manufacturer_version: Option<String>,
+}
+
+/// TCG vendor ID registry (4 ASCII characters packed into a big-endian u32)
+const TPM_VENDORS: &[(&str, &str)] = &[
+    ("AMD", "AMD"),
+    ("ATML", "Atmel"),
+    ("BRCM", "Broadcom"),
+    ("CSCO", "Cisco"),
+    ("FLYS", "Flyslice Technologies"),
+    ("GOOG", "Google"),
+    ("HISI", "Huawei"),
+    ("HPE", "HPE"),
+    ("IBM", "IBM"),
+    ("IFX", "Infineon"),
+    ("INTC", "Intel"),
+    ("LEN", "Lenovo"),
+    ("MSFT", "Microsoft"),
+    ("NSM", "National Semiconductor"),
+    ("NTC", "Nuvoton Technology"),
+    ("NTZ", "Nationz Technologies"),
+    ("QCOM", "Qualcomm"),
+    ("ROCC", "Fuzhou Rockchip"),
+    ("SMSC", "SMSC"),
+    ("SMSN", "Samsung"),
+    ("SNS", "Sinosun Technology"),
+    ("STM", "STMicroelectronics"),
+    ("TXN", "Texas Instruments"),
+    ("WEC", "Winbond"),
+];
+
+// TPM 2.0 fixed properties (TPM_PT_FIXED + n)
+#[cfg(target_os = "linux")]
+const TPM_PT_FAMILY_INDICATOR: u32 = 0x100;
+#[cfg(target_os = "linux")]
+const TPM_PT_REVISION: u32 = 0x102;
+#[cfg(target_os = "linux")]
+const TPM_PT_MANUFACTURER: u32 = 0x105;
+#[cfg(target_os = "linux")]
+const TPM_PT_FIRMWARE_VERSION_1: u32 = 0x10B;
+#[cfg(target_os = "linux")]
+const TPM_PT_FIRMWARE_VERSION_2: u32 = 0x10C;
+
+impl TpmInfo {
+    /// Collect TPM details; `ek_cert` is an optional NV index dump of the EK certificate
+    pub fn collect(ek_cert: Option<&Path>) -> Self {
+        #[cfg(windows)]
+        let mut info = Self::collect_windows();
+        #[cfg(target_os = "linux")]
+        let mut info = Self::collect_linux();
+        #[cfg(not(any(windows, target_os = "linux")))]
+        let mut info = Self::default();
+
+        if let Some(path) = ek_cert {
+            info.ek_cert_fingerprint = match fs::read(path) {
+                Ok(data) => ek_cert_fingerprint(&data)
+                    .unwrap_or_else(|| format!("Invalid certificate in {}", path.display())),
+                Err(e) => format!("Cannot read {}: {}", path.display(), e),
+            };
+        }
+
+        info
+    }
+
+    #[cfg(windows)]
+    fn collect_windows() -> Self {
+        let mut info = Self::default();
+
+        // Win32_Tpm requires administrator rights
+        let tpms: Vec<Win32Tpm> = wmi_session::connection("ROOT\\CIMV2\\Security\\MicrosoftTpm")
+            .and_then(|con| con.raw_query("SELECT * FROM Win32_Tpm").ok())
+            .unwrap_or_default();
+
+        if let Some(tpm) = tpms.first() {
+            info.present = true;
+            info.source = "Win32_Tpm".to_string();
+
+            // SpecVersion looks like "2.0, 0, 1.38" (version, level, revision)
+            if let Some(spec) = &tpm.spec_version {
+                let parts: Vec<&str> = spec.split(',').map(|p| p.trim()).collect();
+                info.spec_version = match parts.as_slice() {
+                    [version, _, revision, ..] => format!("{} (rev {})", version, revision),
+                    [version, ..] => version.to_string(),
+                    [] => "N/A".to_string(),
+                };
+            }
+            if let Some(id) = tpm.manufacturer_id {
+                info.set_manufacturer(id);
+            }
+            if let Some(version) = &tpm.manufacturer_version {
+                info.firmware_version = version.clone();
+            }
+        }
+
+        // Windows caches the EK certificate as a serialized certificate store element
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        if let Ok(store) = hklm.open_subkey("SYSTEM\\CurrentControlSet\\Services\\TPM\\WMI\\Endorsement\\EKCertStore\\Certificates") {
+            for name in store.enum_keys().flatten() {
+                let blob = store.open_subkey(&name)
+                    .and_then(|key| key.get_raw_value("Blob"));
+                if let Ok(blob) = blob {
+                    if let Some(fingerprint) = Self::cert_from_store_blob(&blob.bytes).and_then(ek_cert_fingerprint) {
+                        info.ek_cert_fingerprint = fingerprint;
+                        break;
+                    }
+                }
+            }
+        }
+
+        info
+    }
+
+    /// Extract the DER certificate (CERT_CERT_PROP_ID) from a serialized store element
+    #[cfg(windows)]
+    fn cert_from_store_blob(blob: &[u8]) -> Option<&[u8]> {
+        const CERT_CERT_PROP_ID: u32 = 0x20;
+
+        let mut offset = 0;
+        while offset + 12 <= blob.len() {
+            let prop_id = u32::from_le_bytes(blob[offset..offset + 4].try_into().ok()?);
+            let length = u32::from_le_bytes(blob[offset + 8..offset + 12].try_into().ok()?) as usize;
+            let data = blob.get(offset + 12..offset + 12 + length)?;
+            if prop_id == CERT_CERT_PROP_ID {
+                return Some(data);
+            }
+            offset += 12 + length;
+        }
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn collect_linux() -> Self {
+        let mut info = Self::from_sysfs(Path::new(SYSFS_DIR));
+
+        // sysfs has no manufacturer for TPM 2.0, ask the chip directly (needs root or the tss group)
+        if info.present && (info.spec_version.starts_with('2') || info.spec_version == "Unknown") {
+            for device in ["/dev/tpmrm0", "/dev/tpm0"] {
+                if let Some(properties) = Self::query_tpm2_properties(Path::new(device)) {
+                    info.apply_tpm2_properties(&properties);
+                    info.source = device.to_string();
+                    break;
+                }
+            }
+        }
+
+        info
+    }
+
+    /// Read the TPM class directory (e.g. /sys/class/tpm/tpm0)
+    #[cfg(target_os = "linux")]
+    pub fn from_sysfs(dir: &Path) -> Self {
+        let mut info = Self::default();
+        if !dir.exists() {
+            return info;
+        }
+
+        info.present = true;
+        info.source = "sysfs".to_string();
+
+        // TPM 1.2 devices expose their capabilities as "Key: value" lines
+        if let Ok(caps) = fs::read_to_string(dir.join("device/caps")) {
+            info.spec_version = "1.2".to_string();
+            for line in caps.lines() {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+                let value = value.trim();
+                match key.trim() {
+                    "Manufacturer" => {
+                        if let Ok(id) = u32::from_str_radix(value.trim_start_matches("0x"), 16) {
+                            info.set_manufacturer(id);
+                        }
+                    }
+                    "TCG version" => info.spec_version = value.to_string(),
+                    "Firmware version" => info.firmware_version = value.to_string(),
+                    _ => {}
+                }
+            }
+        }
+
+        match fs::read_to_string(dir.join("tpm_version_major")) {
+            Ok(major) if major.trim() == "2" => info.spec_version = "2.0".to_string(),
+            Ok(major) if major.trim() == "1" && info.spec_version == "N/A" => info.spec_version = "1.2".to_string(),
+            // Kernels before 5.6 have no version file, the GetCapability query can still tell
+            Err(_) if info.spec_version == "N/A" => info.spec_version = "Unknown".to_string(),
+            _ => {}
+        }
+
+        info
+    }
+
+    /// Send TPM2_GetCapability(TPM_CAP_TPM_PROPERTIES) for the fixed properties
+    #[cfg(target_os = "linux")]
+    fn query_tpm2_properties(device: &Path) -> Option<Vec<(u32, u32)>> {
+        use std::io::{Read, Write};
+
+        let mut file = fs::OpenOptions::new().read(true).write(true).open(device).ok()?;
+
+        let mut command = Vec::with_capacity(22);
+        command.extend_from_slice(&0x8001u16.to_be_bytes()); // TPM_ST_NO_SESSIONS
+        command.extend_from_slice(&22u32.to_be_bytes()); // commandSize
+        command.extend_from_slice(&0x0000_017Au32.to_be_bytes()); // TPM_CC_GetCapability
+        command.extend_from_slice(&6u32.to_be_bytes()); // TPM_CAP_TPM_PROPERTIES
+        command.extend_from_slice(&TPM_PT_FAMILY_INDICATOR.to_be_bytes());
+        command.extend_from_slice(&(TPM_PT_FIRMWARE_VERSION_2 - TPM_PT_FAMILY_INDICATOR + 1).to_be_bytes());
+        file.write_all(&command).ok()?;
+
+        let mut response = vec![0u8; 4096];
+        let len = file.read(&mut response).ok()?;
+        parse_tpm2_properties(&response[..len])
+    }
+
+    #[cfg(target_os = "linux")]
+    fn apply_tpm2_properties(&mut self, properties: &[(u32, u32)]) {
+        let get = |tag: u32| properties.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+
+        if let Some(family) = get(TPM_PT_FAMILY_INDICATOR) {
+            let family = vendor_string(family);
+            self.spec_version = match get(TPM_PT_REVISION) {
+                Some(revision) => format!("{} (rev {}.{})", family, revision / 100, revision % 100),
+                None => family,
+            };
+        }
+        if let Some(id) = get(TPM_PT_MANUFACTURER) {
+            self.set_manufacturer(id);
+        }
+        if let (Some(v1), Some(v2)) = (get(TPM_PT_FIRMWARE_VERSION_1), get(TPM_PT_FIRMWARE_VERSION_2)) {
+            self.firmware_version = format!("{}.{}.{}.{}", v1 >> 16, v1 & 0xFFFF, v2 >> 16, v2 & 0xFFFF);
+        }
+    }
+
+    fn set_manufacturer(&mut self, id: u32) {
+        let code = vendor_string(id);
+        self.manufacturer_id = format!("0x{:08X} ({})", id, code);
+        self.manufacturer = TPM_VENDORS
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| "Unknown vendor".to_string());
+    }
+}
+
+impl Default for TpmInfo {
+    fn default() -> Self {
+        Self {
+            present: false,
+            spec_version: "N/A".to_string(),
+            manufacturer_id: "N/A".to_string(),
+            manufacturer: "N/A".to_string(),
+            firmware_version: "N/A".to_string(),
+            ek_cert_fingerprint: "N/A".to_string(),
+            source: "N/A".to_string(),
+        }
+    }
+}
+
+/// Decode a TCG vendor/family u32 into its ASCII characters ("IFX\0" -> "IFX")
+fn vendor_string(value: u32) -> String {
+    value
+        .to_be_bytes()
+        .iter()
+        .filter(|b| b.is_ascii_graphic())
+        .map(|&b| b as char)
+        .collect()
+}
+
+/// Parse a TPM2_GetCapability response into (property, value) pairs
+#[cfg(target_os = "linux")]
+fn parse_tpm2_properties(response: &[u8]) -> Option<Vec<(u32, u32)>> {
+    let be32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(response.get(offset..offset + 4)?.try_into().ok()?))
+    };
+
+    // tag(2) size(4) responseCode(4) moreData(1) capability(4) count(4)
+    if be32(6)? != 0 {
+        return None;
+    }
+    let count = be32(15)? as usize;
+    (0..count)
+        .map(|i| Some((be32(19 + i * 8)?, be32(23 + i * 8)?)))
+        .collect()
+}
+
+/// SHA-256 of the DER certificate inside an NV index dump (which is usually padded)
+pub fn ek_cert_fingerprint(data: &[u8]) -> Option<String> {
+    let der = &data[..der_length(data)?];
+    Some(
+        Sha256::digest(der)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+    )
+}
+
+/// Total length of the DER SEQUENCE at the start of `data`
+fn der_length(data: &[u8]) -> Option<usize> {
+    if data.first() != Some(&0x30) {
+        return None;
+    }
+    let first = *data.get(1)? as usize;
+    let (header, content) = if first < 0x80 {
+        (2, first)
+    } else {
+        let octets = first & 0x7F;
+        if octets == 0 || octets > 4 {
+            return None;
+        }
+        let len = data.get(2..2 + octets)?
+            .iter()
+            .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        (2 + octets, len)
+    };
+    let total = header + content;
+    (total <= data.len()).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// TPM2_GetCapability response carrying `properties`, as the chip returns it
+    #[cfg(target_os = "linux")]
+    fn capability_response(code: u32, properties: &[(u32, u32)]) -> Vec<u8> {
+        let mut response = Vec::new();
+        response.extend_from_slice(&0x8001u16.to_be_bytes());
+        response.extend_from_slice(&((19 + properties.len() * 8) as u32).to_be_bytes());
+        response.extend_from_slice(&code.to_be_bytes());
+        response.push(0); // moreData
+        response.extend_from_slice(&6u32.to_be_bytes());
+        response.extend_from_slice(&(properties.len() as u32).to_be_bytes());
+        for (tag, value) in properties {
+            response.extend_from_slice(&tag.to_be_bytes());
+            response.extend_from_slice(&value.to_be_bytes());
+        }
+        response
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_capability_response() {
+        let properties = [
+            (TPM_PT_FAMILY_INDICATOR, u32::from_be_bytes(*b"2.0\0")),
+            (TPM_PT_REVISION, 138),
+            (TPM_PT_MANUFACTURER, u32::from_be_bytes(*b"IFX\0")),
+            (TPM_PT_FIRMWARE_VERSION_1, 0x0007_0055),
+            (TPM_PT_FIRMWARE_VERSION_2, 0x0011_0800),
+        ];
+        let parsed = parse_tpm2_properties(&capability_response(0, &properties)).unwrap();
+        assert_eq!(parsed, properties);
+
+        let mut info = TpmInfo::default();
+        info.apply_tpm2_properties(&parsed);
+        assert_eq!(info.spec_version, "2.0 (rev 1.38)");
+        assert_eq!(info.manufacturer_id, "0x49465800 (IFX)");
+        assert_eq!(info.manufacturer, "Infineon");
+        assert_eq!(info.firmware_version, "7.85.17.2048");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn rejects_failed_or_truncated_response() {
+        assert_eq!(parse_tpm2_properties(&capability_response(0x101, &[(TPM_PT_REVISION, 138)])), None);
+
+        let mut truncated = capability_response(0, &[(TPM_PT_REVISION, 138), (TPM_PT_MANUFACTURER, 1)]);
+        truncated.truncate(truncated.len() - 3);
+        assert_eq!(parse_tpm2_properties(&truncated), None);
+        assert_eq!(parse_tpm2_properties(&[0x80, 0x01]), None);
+    }
+
+    #[test]
+    fn der_length_short_form() {
+        assert_eq!(der_length(&[0x30, 0x03, 1, 2, 3, 0xFF, 0xFF]), Some(5));
+    }
+
+    #[test]
+    fn der_length_long_form() {
+        let mut cert = vec![0x30, 0x82, 0x01, 0x00];
+        cert.resize(4 + 0x100, 0xAA);
+        cert.resize(2048, 0xFF);
+        assert_eq!(der_length(&cert), Some(4 + 0x100));
+    }
+
+    #[test]
+    fn der_length_rejects_indefinite_and_truncated() {
+        assert_eq!(der_length(&[0x30, 0x80, 1, 2, 0, 0]), None);
+        assert_eq!(der_length(&[0x30, 0x82, 0x01]), None);
+        assert_eq!(der_length(&[0x30, 0x05, 1, 2]), None);
+        assert_eq!(der_length(&[0x30, 0x85, 1, 1, 1, 1, 1]), None);
+        assert_eq!(der_length(&[0x04, 0x01, 0]), None);
+        assert_eq!(der_length(&[]), None);
+    }
+
+    #[test]
+    fn fingerprint_ignores_nv_padding() {
+        let der = [0x30, 0x03, 1, 2, 3];
+        let padded = [&der[..], &[0xFF; 11]].concat();
+        let expected: String = Sha256::digest(der).iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(ek_cert_fingerprint(&padded), Some(expected));
+    }
+
+    #[cfg(target_os = "linux")]
+    fn sysfs_dir(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("serial-checker-tpm-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("device")).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_tpm2_sysfs() {
+        let dir = sysfs_dir("tpm2", &[("tpm_version_major", "2\n")]);
+        let info = TpmInfo::from_sysfs(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(info.present);
+        assert_eq!(info.spec_version, "2.0");
+        assert_eq!(info.source, "sysfs");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_tpm12_caps() {
+        let caps = "Manufacturer: 0x49465800\nTCG version: 1.2\nFirmware version: 3.19\n";
+        let dir = sysfs_dir("tpm12", &[("device/caps", caps), ("tpm_version_major", "1\n")]);
+        let info = TpmInfo::from_sysfs(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(info.spec_version, "1.2");
+        assert_eq!(info.manufacturer, "Infineon");
+        assert_eq!(info.firmware_version, "3.19");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn unreadable_version_is_unknown() {
+        let dir = sysfs_dir("tpm-old-kernel", &[("device/description", "TPM\n")]);
+        let info = TpmInfo::from_sysfs(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(info.present);
+        assert_eq!(info.spec_version, "Unknown");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn missing_sysfs_means_no_tpm() {
+        let info = TpmInfo::from_sysfs(Path::new("/nonexistent/tpm0"));
+        assert!(!info.present);
+        assert_eq!(info.spec_version, "N/A");
+    }
+}
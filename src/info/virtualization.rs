@@ -0,0 +1,378 @@
+use serde::Serialize;
+
+use crate::info::{
+    system::SystemInfo,
+    bios::BiosInfo,
+    baseboard::BaseboardInfo,
+    disk::DiskInfo,
+    network::NetworkInfo,
+};
+
+/// Overall verdict of the virtualization assessment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum VmVerdict {
+    Physical,
+    PossiblyVirtual,
+    Virtual,
+}
+
+impl VmVerdict {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VmVerdict::Physical => "Physical",
+            VmVerdict::PossiblyVirtual => "Possibly Virtual",
+            VmVerdict::Virtual => "Virtual Machine",
+        }
+    }
+}
+
+/// Combined result of all virtualization signals
+#[derive(Debug, Clone, Serialize)]
+pub struct VirtualizationInfo {
+    pub verdict: VmVerdict,
+    pub hypervisor: String,
+    pub cpuid_hypervisor_bit: bool,
+    pub cpuid_vendor: String,
+    pub evidence: Vec<String>,
+}
+
+/// What the CPU reports about the hypervisor it runs under
+#[derive(Debug, Clone)]
+struct Cpuid {
+    hypervisor_bit: bool,
+    vendor: String,
+    /// Hyper-V grants CreatePartitions only to the root partition, i.e. the physical host
+    hyperv_root: bool,
+}
+
+/// Known hypervisor fingerprints
+struct Hypervisor {
+    name: &'static str,
+    smbios_patterns: &'static [&'static str],
+    cpuid_vendors: &'static [&'static str],
+    mac_ouis: &'static [&'static str],
+    disk_patterns: &'static [&'static str],
+}
+
+const HYPERVISORS: &[Hypervisor] = &[
+    Hypervisor {
+        name: "VMware",
+        smbios_patterns: &["vmware"],
+        cpuid_vendors: &["VMwareVMware"],
+        mac_ouis: &["00:05:69", "00:0C:29", "00:1C:14", "00:50:56"],
+        disk_patterns: &["vmware"],
+    },
+    Hypervisor {
+        name: "VirtualBox",
+        smbios_patterns: &["virtualbox", "innotek"],
+        cpuid_vendors: &["VBoxVBoxVBox"],
+        mac_ouis: &["08:00:27", "0A:00:27"],
+        disk_patterns: &["vbox"],
+    },
+    Hypervisor {
+        name: "Hyper-V",
+        smbios_patterns: &["virtual machine", "hyper-v"],
+        cpuid_vendors: &["Microsoft Hv"],
+        mac_ouis: &["00:15:5D"],
+        disk_patterns: &["msft virtual disk"],
+    },
+    Hypervisor {
+        name: "Xen",
+        smbios_patterns: &["xen", "hvm domu"],
+        cpuid_vendors: &["XenVMMXenVMM"],
+        mac_ouis: &["00:16:3E"],
+        disk_patterns: &["xen"],
+    },
+    Hypervisor {
+        name: "KVM",
+        smbios_patterns: &["kvm"],
+        cpuid_vendors: &["KVMKVMKVM"],
+        mac_ouis: &[],
+        disk_patterns: &[],
+    },
+    Hypervisor {
+        name: "QEMU",
+        smbios_patterns: &["qemu", "seabios", "bochs"],
+        cpuid_vendors: &["TCGTCGTCGTCG"],
+        mac_ouis: &["52:54:00"],
+        disk_patterns: &["qemu"],
+    },
+];
+
+impl VirtualizationInfo {
+    /// Assess whether the collected data came from a virtual machine
+    pub fn assess(
+        system: &SystemInfo,
+        bios: &BiosInfo,
+        baseboard: &BaseboardInfo,
+        network: &NetworkInfo,
+        disk: &DiskInfo,
+    ) -> Self {
+        Self::assess_with(system, bios, baseboard, network, disk, Self::read_cpuid())
+    }
+
+    fn assess_with(
+        system: &SystemInfo,
+        bios: &BiosInfo,
+        baseboard: &BaseboardInfo,
+        network: &NetworkInfo,
+        disk: &DiskInfo,
+        cpuid: Cpuid,
+    ) -> Self {
+        let Cpuid {
+            hypervisor_bit: cpuid_hypervisor_bit,
+            vendor: cpuid_vendor,
+            hyperv_root,
+        } = cpuid;
+        let mut evidence = Vec::new();
+        let mut score = 0;
+        let mut hypervisor: Option<&'static str> = None;
+
+        // SMBIOS vendor/product strings
+        let smbios_fields = [
+            ("System manufacturer", &system.manufacturer),
+            ("System product", &system.product_name),
+            ("System version", &system.version),
+            ("BIOS vendor", &bios.vendor),
+            ("BIOS version", &bios.version),
+            ("Baseboard manufacturer", &baseboard.manufacturer),
+            ("Baseboard product", &baseboard.product_name),
+        ];
+        for (label, value) in smbios_fields {
+            if let Some(hv) = Self::match_pattern(value, |h| h.smbios_patterns) {
+                evidence.push(format!("{} \"{}\" matches {}", label, value, hv.name));
+                hypervisor.get_or_insert(hv.name);
+                score += 2;
+            }
+        }
+
+        // CPUID hypervisor bit and vendor leaf
+        if cpuid_hypervisor_bit {
+            evidence.push("CPUID hypervisor present bit is set".to_string());
+            match HYPERVISORS.iter().find(|h| h.cpuid_vendors.iter().any(|v| cpuid_vendor.starts_with(v))) {
+                // Windows hosts with Hyper-V, WSL2 or VBS run as the root partition
+                Some(hv) if hv.name == "Hyper-V" && hyperv_root => {
+                    evidence.push(format!("CPUID vendor \"{}\" from the Hyper-V root partition (physical host)", cpuid_vendor));
+                }
+                // Without the root flag "Microsoft Hv" still does not prove a guest
+                Some(hv) if hv.name == "Hyper-V" => {
+                    evidence.push(format!("CPUID vendor \"{}\" (may be a VBS-enabled host)", cpuid_vendor));
+                    score += 1;
+                }
+                Some(hv) => {
+                    evidence.push(format!("CPUID vendor \"{}\" matches {}", cpuid_vendor, hv.name));
+                    hypervisor.get_or_insert(hv.name);
+                    score += 2;
+                }
+                None => {
+                    evidence.push(format!("CPUID vendor \"{}\" is not a known hypervisor", cpuid_vendor));
+                    score += 1;
+                }
+            }
+        }
+
+        // NIC OUIs, counted once per hypervisor since a host can have many virtual switches
+        let mut nic_hypervisors: Vec<&str> = Vec::new();
+        for iface in &network.interfaces {
+            let oui = Self::mac_oui(&iface.mac_address);
+            if let Some(hv) = HYPERVISORS.iter().find(|h| h.mac_ouis.contains(&oui.as_str())) {
+                // vEthernet adapters of a Hyper-V host carry the Hyper-V OUI too
+                if hv.name == "Hyper-V" && hyperv_root {
+                    evidence.push(format!("NIC \"{}\" OUI {} is a Hyper-V host virtual switch", iface.name, oui));
+                    continue;
+                }
+                evidence.push(format!("NIC \"{}\" OUI {} belongs to {}", iface.name, oui, hv.name));
+                hypervisor.get_or_insert(hv.name);
+                if !nic_hypervisors.contains(&hv.name) {
+                    nic_hypervisors.push(hv.name);
+                    score += 1;
+                }
+            }
+        }
+
+        // Disk model strings, likewise counted once per hypervisor
+        let mut disk_hypervisors: Vec<&str> = Vec::new();
+        for disk in &disk.disks {
+            if let Some(hv) = Self::match_pattern(&disk.model, |h| h.disk_patterns) {
+                evidence.push(format!("Disk model \"{}\" matches {}", disk.model, hv.name));
+                hypervisor.get_or_insert(hv.name);
+                if !disk_hypervisors.contains(&hv.name) {
+                    disk_hypervisors.push(hv.name);
+                    score += 1;
+                }
+            }
+        }
+
+        let verdict = match score {
+            0 => VmVerdict::Physical,
+            1 | 2 => VmVerdict::PossiblyVirtual,
+            _ => VmVerdict::Virtual,
+        };
+
+        // The Hyper-V CPUID vendor names the hypervisor even when it is not conclusive,
+        // but the root partition is the host itself
+        let hypervisor = hypervisor
+            .or_else(|| {
+                HYPERVISORS.iter()
+                    .filter(|_| cpuid_hypervisor_bit && !hyperv_root)
+                    .find(|h| h.cpuid_vendors.iter().any(|v| cpuid_vendor.starts_with(v)))
+                    .map(|h| h.name)
+            })
+            .unwrap_or("None")
+            .to_string();
+
+        Self {
+            verdict,
+            hypervisor,
+            cpuid_hypervisor_bit,
+            cpuid_vendor,
+            evidence,
+        }
+    }
+
+    fn match_pattern(
+        value: &str,
+        patterns: fn(&Hypervisor) -> &'static [&'static str],
+    ) -> Option<&'static Hypervisor> {
+        let lower = value.to_lowercase();
+        if lower.is_empty() || lower == "n/a" {
+            return None;
+        }
+        HYPERVISORS.iter().find(|h| patterns(h).iter().any(|p| lower.contains(p)))
+    }
+
+    /// Normalize a MAC address to its "XX:XX:XX" OUI prefix
+    fn mac_oui(mac: &str) -> String {
+        let hex: String = mac.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+        if hex.len() < 6 {
+            return String::new();
+        }
+        let hex = hex.to_uppercase();
+        format!("{}:{}:{}", &hex[0..2], &hex[2..4], &hex[4..6])
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn read_cpuid() -> Cpuid {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::__cpuid;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::__cpuid;
+
+        // Leaf 1, ECX bit 31 is reserved for hypervisors to announce themselves
+        let present = (__cpuid(1).ecx >> 31) & 1 == 1;
+        if !present {
+            return Cpuid {
+                hypervisor_bit: false,
+                vendor: "N/A".to_string(),
+                hyperv_root: false,
+            };
+        }
+
+        // Leaf 0x40000000 returns the 12-byte hypervisor vendor signature in EBX, ECX, EDX
+        let leaf = __cpuid(0x4000_0000);
+        let bytes: Vec<u8> = [leaf.ebx, leaf.ecx, leaf.edx]
+            .iter()
+            .flat_map(|r| r.to_le_bytes())
+            .collect();
+        let vendor = String::from_utf8_lossy(&bytes)
+            .trim_end_matches('\0')
+            .to_string();
+
+        // Leaf 0x40000003 EBX bit 0 is the CreatePartitions privilege of the Hyper-V TLFS
+        let hyperv_root = vendor.starts_with("Microsoft Hv") && leaf.eax >= 0x4000_0003 && __cpuid(0x4000_0003).ebx & 1 == 1;
+
+        Cpuid {
+            hypervisor_bit: true,
+            vendor: if vendor.is_empty() { "N/A".to_string() } else { vendor },
+            hyperv_root,
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    fn read_cpuid() -> Cpuid {
+        Cpuid {
+            hypervisor_bit: false,
+            vendor: "N/A".to_string(),
+            hyperv_root: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::network::NetworkInterface;
+
+    fn hyperv_cpuid(hyperv_root: bool) -> Cpuid {
+        Cpuid {
+            hypervisor_bit: true,
+            vendor: "Microsoft Hv".to_string(),
+            hyperv_root,
+        }
+    }
+
+    fn nics(macs: &[&str]) -> NetworkInfo {
+        NetworkInfo {
+            interfaces: macs
+                .iter()
+                .enumerate()
+                .map(|(i, mac)| NetworkInterface {
+                    name: format!("vEthernet {}", i),
+                    mac_address: mac.to_string(),
+                    ip_address: "N/A".to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn assess(network: &NetworkInfo, cpuid: Cpuid) -> VirtualizationInfo {
+        VirtualizationInfo::assess_with(
+            &SystemInfo::default(),
+            &BiosInfo::default(),
+            &BaseboardInfo::default(),
+            network,
+            &DiskInfo::default(),
+            cpuid,
+        )
+    }
+
+    #[test]
+    fn hyperv_host_with_virtual_switches_is_physical() {
+        let info = assess(&nics(&["00:15:5D:01:02:03", "00:15:5D:04:05:06"]), hyperv_cpuid(true));
+        assert_eq!(info.verdict, VmVerdict::Physical);
+        assert_eq!(info.hypervisor, "None");
+    }
+
+    #[test]
+    fn many_hyperv_nics_count_once() {
+        let info = assess(
+            &nics(&["00:15:5D:01:02:03", "00:15:5D:04:05:06", "00:15:5D:07:08:09"]),
+            hyperv_cpuid(false),
+        );
+        // Inconclusive Hyper-V vendor and one NIC, not one point per adapter
+        assert_eq!(info.verdict, VmVerdict::PossiblyVirtual);
+        assert_eq!(info.hypervisor, "Hyper-V");
+    }
+
+    #[test]
+    fn guest_fingerprints_still_add_up() {
+        let system = SystemInfo {
+            manufacturer: "QEMU".to_string(),
+            ..SystemInfo::default()
+        };
+        let info = VirtualizationInfo::assess_with(
+            &system,
+            &BiosInfo::default(),
+            &BaseboardInfo::default(),
+            &nics(&["52:54:00:12:34:56"]),
+            &DiskInfo::default(),
+            Cpuid {
+                hypervisor_bit: true,
+                vendor: "KVMKVMKVM".to_string(),
+                hyperv_root: false,
+            },
+        );
+        assert_eq!(info.verdict, VmVerdict::Virtual);
+        assert_eq!(info.hypervisor, "QEMU");
+    }
+}
@@ -0,0 +1,661 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Partitioning scheme and the volumes found on a disk or image
+#[derive(Debug, Clone, Serialize)]
+pub struct PartitionTable {
+    pub scheme: String,
+    pub disk_id: String,
+    pub sector_size: u64,
+    pub partitions: Vec<VolumeEntry>,
+}
+
+/// A partition and the filesystem identifiers inside it
+#[derive(Debug, Clone, Serialize)]
+pub struct VolumeEntry {
+    pub index: usize,
+    pub type_name: String,
+    pub type_id: String,
+    pub partition_guid: String,
+    pub name: String,
+    pub start_lba: u64,
+    pub size_bytes: u64,
+    pub filesystem: String,
+    pub fs_uuid: String,
+    pub volume_serial: String,
+    pub label: String,
+}
+
+/// Aligned reads from a block device or image (raw Windows disks reject unaligned I/O)
+struct BlockReader {
+    file: File,
+    /// Logical sector size reads are padded to
+    align: u64,
+}
+
+/// Alignment until the sector size is known, a multiple of both 512-byte and 4Kn sectors
+const PROBE_ALIGN: u64 = 4096;
+
+/// Largest GPT entry array read, far above the 128 x 128 bytes every tool writes
+const MAX_GPT_TABLE: usize = 1024 * 1024;
+
+/// Most EBRs followed in one extended partition, far above what any partitioning tool creates
+const MAX_EBRS: usize = 128;
+
+/// Bytes needed to see every supported superblock (btrfs lives at 64 KiB)
+const PROBE_LEN: usize = 0x11000;
+
+const GPT_TYPES: &[(&str, &str)] = &[
+    ("C12A7328-F81F-11D2-BA4B-00A0C93EC93B", "EFI System"),
+    ("21686148-6449-6E6F-744E-656564454649", "BIOS Boot"),
+    ("E3C9E316-0B5C-4DB8-817D-F92DF00215AE", "Microsoft Reserved"),
+    ("EBD0A0A2-B9E5-4433-87C0-68B6B72699C7", "Basic Data"),
+    ("DE94BBA4-06D1-4D40-A16A-BFD50179D6AC", "Windows Recovery"),
+    ("0FC63DAF-8483-4772-8E79-3D69D8477DE4", "Linux Filesystem"),
+    ("0657FD6D-A4AB-43C4-84E5-0933C84B4F4F", "Linux Swap"),
+    ("E6D6D379-F507-44C2-A23C-238F2A3DF928", "Linux LVM"),
+    ("A19D880F-05FC-4D3B-A006-743F0F84911E", "Linux RAID"),
+    ("4F68BCE3-E8CD-4DB1-96E7-FBCAF984B709", "Linux Root (x86-64)"),
+    ("BC13C2FF-59E6-4262-A352-B275FD6F7172", "Linux Extended Boot"),
+    ("48465300-0000-11AA-AA11-00306543ECAC", "Apple HFS+"),
+    ("7C3457EF-0000-11AA-AA11-00306543ECAC", "Apple APFS"),
+];
+
+const MBR_TYPES: &[(u8, &str)] = &[
+    (0x01, "FAT12"),
+    (0x04, "FAT16 (<32M)"),
+    (0x05, "Extended"),
+    (0x06, "FAT16"),
+    (0x07, "NTFS/exFAT"),
+    (0x0B, "FAT32 (CHS)"),
+    (0x0C, "FAT32 (LBA)"),
+    (0x0E, "FAT16 (LBA)"),
+    (0x0F, "Extended (LBA)"),
+    (0x27, "Windows Recovery"),
+    (0x82, "Linux Swap"),
+    (0x83, "Linux"),
+    (0x85, "Linux Extended"),
+    (0x8E, "Linux LVM"),
+    (0xEE, "GPT Protective"),
+    (0xEF, "EFI System"),
+    (0xFD, "Linux RAID"),
+];
+
+impl BlockReader {
+    fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: File::open(path)?,
+            align: PROBE_ALIGN,
+        })
+    }
+
+    /// Read `len` bytes at `offset`, padding the request out to sector boundaries
+    fn read_at(&mut self, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+        let start = offset / self.align * self.align;
+        let end = offset
+            .checked_add(len as u64)
+            .and_then(|end| end.div_ceil(self.align).checked_mul(self.align))
+            .ok_or_else(|| corrupt("read past the end of the addressable range"))?;
+        let mut buf = vec![0u8; (end - start) as usize];
+
+        self.file.seek(SeekFrom::Start(start))?;
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.file.read(&mut buf[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+        buf.truncate(filled);
+
+        let skip = (offset - start) as usize;
+        Ok(buf.get(skip..).map(|b| b[..b.len().min(len)].to_vec()).unwrap_or_default())
+    }
+}
+
+impl PartitionTable {
+    /// Parse the partition table of a block device or disk image
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let mut reader = BlockReader::open(path)?;
+        let mbr = reader.read_at(0, 512)?;
+
+        // GPT header sits in LBA 1, which is byte 512 or 4096 depending on the logical sector size
+        for sector_size in [512u64, 4096] {
+            let header = reader.read_at(sector_size, 92)?;
+            if header.len() == 92 && header.starts_with(b"EFI PART") {
+                return Self::read_gpt(&mut reader, &header, sector_size);
+            }
+        }
+
+        if mbr.len() == 512 && mbr[510..512] == [0x55, 0xAA] {
+            // A bare filesystem (no partition table) also ends its boot sector with 55 AA
+            if let Some(volume) = Self::probe_whole_device(&mut reader)? {
+                return Ok(volume);
+            }
+            return Self::read_mbr(&mut reader, &mbr);
+        }
+
+        match Self::probe_whole_device(&mut reader)? {
+            Some(volume) => Ok(volume),
+            None => Ok(Self {
+                scheme: "None".to_string(),
+                disk_id: "N/A".to_string(),
+                sector_size: 512,
+                partitions: Vec::new(),
+            }),
+        }
+    }
+
+    fn read_gpt(reader: &mut BlockReader, header: &[u8], sector_size: u64) -> io::Result<Self> {
+        let disk_guid = format_guid(&header[56..72]);
+        let entries_lba = le_u64(header, 72);
+        let entry_count = le_u32(header, 80) as usize;
+        let entry_size = le_u32(header, 84) as usize;
+
+        let table_len = match entry_count.checked_mul(entry_size) {
+            Some(len) if len <= MAX_GPT_TABLE && (128..=4096).contains(&entry_size) && entry_size.is_multiple_of(8) => len,
+            _ => return Err(corrupt("corrupt GPT header")),
+        };
+
+        reader.align = sector_size;
+        let table_offset = entries_lba.checked_mul(sector_size).ok_or_else(|| corrupt("corrupt GPT header"))?;
+        let table = reader.read_at(table_offset, table_len)?;
+        let mut partitions = Vec::new();
+
+        for (i, entry) in table.chunks_exact(entry_size).enumerate() {
+            let type_guid = &entry[0..16];
+            if type_guid.iter().all(|&b| b == 0) {
+                continue;
+            }
+
+            let type_id = format_guid(type_guid);
+            let first_lba = le_u64(entry, 32);
+            let last_lba = le_u64(entry, 40);
+            if last_lba < first_lba {
+                continue;
+            }
+            let name = utf16_name(&entry[56..128]);
+            let offset = first_lba.checked_mul(sector_size);
+            let size = (last_lba - first_lba).checked_add(1).and_then(|sectors| sectors.checked_mul(sector_size));
+            let (Some(offset), Some(size)) = (offset, size) else {
+                return Err(corrupt("corrupt GPT partition entry"));
+            };
+
+            let mut volume = VolumeEntry::new(i + 1, first_lba, size);
+            volume.type_name = GPT_TYPES
+                .iter()
+                .find(|(guid, _)| *guid == type_id)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+            volume.type_id = type_id;
+            volume.partition_guid = format_guid(&entry[16..32]);
+            volume.name = if name.is_empty() { "N/A".to_string() } else { name };
+            volume.probe(reader, offset)?;
+            partitions.push(volume);
+        }
+
+        Ok(Self {
+            scheme: "GPT".to_string(),
+            disk_id: disk_guid,
+            sector_size,
+            partitions,
+        })
+    }
+
+    fn read_mbr(reader: &mut BlockReader, mbr: &[u8]) -> io::Result<Self> {
+        reader.align = 512;
+        let signature = le_u32(mbr, 440);
+        let mut partitions = Vec::new();
+
+        for slot in 0..4 {
+            let entry = &mbr[446 + slot * 16..446 + (slot + 1) * 16];
+            let part_type = entry[4];
+            if part_type == 0 {
+                continue;
+            }
+            let start = le_u32(entry, 8) as u64;
+            let sectors = le_u32(entry, 12) as u64;
+
+            if matches!(part_type, 0x05 | 0x0F | 0x85) {
+                Self::read_logical(reader, start, &mut partitions)?;
+                continue;
+            }
+
+            let mut volume = VolumeEntry::mbr(slot + 1, part_type, start, sectors);
+            volume.probe(reader, start * 512)?;
+            partitions.push(volume);
+        }
+
+        Ok(Self {
+            scheme: "MBR".to_string(),
+            disk_id: format!("{:08X}", signature),
+            sector_size: 512,
+            partitions,
+        })
+    }
+
+    /// Walk the EBR chain of an extended partition (logical partitions are numbered from 5)
+    fn read_logical(reader: &mut BlockReader, extended_start: u64, partitions: &mut Vec<VolumeEntry>) -> io::Result<()> {
+        let mut ebr_lba = extended_start;
+        let mut index = 5;
+        let mut visited = HashSet::new();
+
+        // A link back to an EBR already read would loop forever, and empty EBRs add no partition
+        // to count, so bound the walk by EBRs read as well
+        for _ in 0..MAX_EBRS {
+            if !visited.insert(ebr_lba) {
+                break;
+            }
+            let ebr = reader.read_at(ebr_lba * 512, 512)?;
+            if ebr.len() < 512 || ebr[510..512] != [0x55, 0xAA] {
+                break;
+            }
+
+            let entry = &ebr[446..462];
+            if entry[4] != 0 {
+                let start = ebr_lba + le_u32(entry, 8) as u64;
+                let mut volume = VolumeEntry::mbr(index, entry[4], start, le_u32(entry, 12) as u64);
+                volume.probe(reader, start * 512)?;
+                partitions.push(volume);
+                index += 1;
+            }
+
+            let next = &ebr[462..478];
+            if next[4] == 0 {
+                break;
+            }
+            ebr_lba = extended_start + le_u32(next, 8) as u64;
+        }
+
+        Ok(())
+    }
+
+    /// Treat a device holding a filesystem directly (e.g. a partition image) as a single volume
+    fn probe_whole_device(reader: &mut BlockReader) -> io::Result<Option<Self>> {
+        // Block devices report a zero length here, images report their real size
+        let size = reader.file.seek(SeekFrom::End(0))?;
+        let mut volume = VolumeEntry::new(1, 0, size);
+        volume.type_name = "Whole Device".to_string();
+        volume.probe(reader, 0)?;
+
+        if volume.filesystem == "Unknown" {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            scheme: "None".to_string(),
+            disk_id: "N/A".to_string(),
+            sector_size: 512,
+            partitions: vec![volume],
+        }))
+    }
+}
+
+impl VolumeEntry {
+    fn new(index: usize, start_lba: u64, size_bytes: u64) -> Self {
+        Self {
+            index,
+            type_name: "N/A".to_string(),
+            type_id: "N/A".to_string(),
+            partition_guid: "N/A".to_string(),
+            name: "N/A".to_string(),
+            start_lba,
+            size_bytes,
+            filesystem: "Unknown".to_string(),
+            fs_uuid: "N/A".to_string(),
+            volume_serial: "N/A".to_string(),
+            label: "N/A".to_string(),
+        }
+    }
+
+    fn mbr(index: usize, part_type: u8, start_lba: u64, sectors: u64) -> Self {
+        let mut volume = Self::new(index, start_lba, sectors * 512);
+        volume.type_id = format!("0x{:02X}", part_type);
+        volume.type_name = MBR_TYPES
+            .iter()
+            .find(|(t, _)| *t == part_type)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        volume
+    }
+
+    /// Identify the filesystem at `offset` and pull out its UUID, serial and label
+    fn probe(&mut self, reader: &mut BlockReader, offset: u64) -> io::Result<()> {
+        let mut data = reader.read_at(offset, PROBE_LEN)?;
+        // Short reads (tiny images, end of device) are treated as zero-filled
+        data.resize(PROBE_LEN, 0);
+        let at = |start: usize, len: usize| data.get(start..start + len);
+
+        if at(3, 8) == Some(b"NTFS    ".as_slice()) {
+            let serial = le_u64(&data, 0x48);
+            self.filesystem = "NTFS".to_string();
+            self.fs_uuid = format!("{:016X}", serial);
+            self.volume_serial = format_short_serial(serial as u32);
+        } else if at(3, 8) == Some(b"EXFAT   ".as_slice()) {
+            let serial = le_u32(&data, 0x64);
+            self.filesystem = "exFAT".to_string();
+            self.fs_uuid = format_short_serial(serial);
+            self.volume_serial = self.fs_uuid.clone();
+        } else if at(0x52, 8) == Some(b"FAT32   ".as_slice()) {
+            let serial = le_u32(&data, 0x43);
+            self.filesystem = "FAT32".to_string();
+            self.fs_uuid = format_short_serial(serial);
+            self.volume_serial = self.fs_uuid.clone();
+            self.label = fixed_label(&data[0x47..0x52]);
+        } else if at(0x36, 4) == Some(b"FAT1".as_slice()) {
+            let serial = le_u32(&data, 0x27);
+            self.filesystem = String::from_utf8_lossy(&data[0x36..0x3E]).trim().to_string();
+            self.fs_uuid = format_short_serial(serial);
+            self.volume_serial = self.fs_uuid.clone();
+            self.label = fixed_label(&data[0x2B..0x36]);
+        } else if at(0, 4) == Some(b"XFSB".as_slice()) {
+            self.filesystem = "XFS".to_string();
+            self.fs_uuid = format_uuid(&data[32..48]);
+            self.label = fixed_label(&data[108..120]);
+        } else if at(0x438, 2) == Some([0x53, 0xEF].as_slice()) {
+            // ext superblock starts at 1024; feature flags separate ext2/3/4
+            let sb = &data[1024..];
+            let compat = le_u32(sb, 0x5C);
+            let incompat = le_u32(sb, 0x60);
+            self.filesystem = if incompat & 0x40 != 0 {
+                "ext4"
+            } else if compat & 0x4 != 0 {
+                "ext3"
+            } else {
+                "ext2"
+            }
+            .to_string();
+            self.fs_uuid = format_uuid(&sb[0x68..0x78]);
+            self.label = fixed_label(&sb[0x78..0x88]);
+        } else if at(0x10040, 8) == Some(b"_BHRfS_M".as_slice()) {
+            self.filesystem = "btrfs".to_string();
+            self.fs_uuid = format_uuid(&data[0x10020..0x10030]);
+            self.label = fixed_label(&data[0x1012B..0x1022B]);
+        }
+
+        if self.label.is_empty() {
+            self.label = "N/A".to_string();
+        }
+
+        Ok(())
+    }
+}
+
+fn le_u32(data: &[u8], offset: usize) -> u32 {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .unwrap_or(0)
+}
+
+fn corrupt(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn le_u64(data: &[u8], offset: usize) -> u64 {
+    data.get(offset..offset + 8)
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .unwrap_or(0)
+}
+
+/// GPT GUIDs store the first three groups little-endian
+fn format_guid(b: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+        le_u32(b, 0),
+        u16::from_le_bytes([b[4], b[5]]),
+        u16::from_le_bytes([b[6], b[7]]),
+        b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15],
+    )
+}
+
+/// Filesystem UUIDs (ext, XFS, btrfs) are stored in big-endian byte order
+fn format_uuid(b: &[u8]) -> String {
+    let hex: String = b.iter().map(|x| format!("{:02x}", x)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+/// FAT/NTFS volume serial as shown by `vol` ("1234-ABCD")
+fn format_short_serial(serial: u32) -> String {
+    format!("{:04X}-{:04X}", serial >> 16, serial & 0xFFFF)
+}
+
+fn fixed_label(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let label = String::from_utf8_lossy(&bytes[..end]).trim().to_string();
+    if label == "NO NAME" { String::new() } else { label }
+}
+
+fn utf16_name(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_DATA: [u8; 16] = [
+        0xA2, 0xA0, 0xD0, 0xEB, 0xE5, 0xB9, 0x33, 0x44, 0x87, 0xC0, 0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7,
+    ];
+
+    /// Disk image with a GPT header in LBA 1 and the entry array at `entries_lba`
+    fn gpt_image(sector: usize, entry_size: u32, entries_lba: u64, entries: &[(u64, u64)]) -> Vec<u8> {
+        let mut image = vec![0u8; sector * 8];
+        let header = &mut image[sector..sector + 92];
+        header[..8].copy_from_slice(b"EFI PART");
+        header[56..72].copy_from_slice(&[0x11; 16]);
+        header[72..80].copy_from_slice(&entries_lba.to_le_bytes());
+        header[80..84].copy_from_slice(&(entries.len() as u32).to_le_bytes());
+        header[84..88].copy_from_slice(&entry_size.to_le_bytes());
+
+        for (i, (first, last)) in entries.iter().enumerate() {
+            let at = sector * 2 + i * 128;
+            image[at..at + 16].copy_from_slice(&BASIC_DATA);
+            image[at + 16..at + 32].copy_from_slice(&[0x22; 16]);
+            image[at + 32..at + 40].copy_from_slice(&first.to_le_bytes());
+            image[at + 40..at + 48].copy_from_slice(&last.to_le_bytes());
+        }
+        image
+    }
+
+    fn read_image(name: &str, image: &[u8]) -> io::Result<PartitionTable> {
+        let path = std::env::temp_dir().join(format!("serial-checker-{}-{}.img", name, std::process::id()));
+        std::fs::write(&path, image).unwrap();
+        let table = PartitionTable::read(&path);
+        std::fs::remove_file(&path).unwrap();
+        table
+    }
+
+    #[test]
+    fn reads_gpt_entries() {
+        let table = read_image("gpt", &gpt_image(512, 128, 2, &[(4, 7)])).unwrap();
+        assert_eq!(table.scheme, "GPT");
+        assert_eq!(table.sector_size, 512);
+        assert_eq!(table.partitions.len(), 1);
+        assert_eq!(table.partitions[0].type_name, "Basic Data");
+        assert_eq!(table.partitions[0].size_bytes, 4 * 512);
+    }
+
+    #[test]
+    fn reads_4kn_gpt() {
+        let table = read_image("gpt-4kn", &gpt_image(4096, 128, 2, &[(4, 5)])).unwrap();
+        assert_eq!(table.sector_size, 4096);
+        assert_eq!(table.partitions[0].size_bytes, 2 * 4096);
+    }
+
+    #[test]
+    fn rejects_oversized_entry_array() {
+        for entry_size in [u32::MAX, 4104, 136 + 4] {
+            let error = read_image("gpt-size", &gpt_image(512, entry_size, 2, &[(4, 7)])).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+
+        let mut image = gpt_image(512, 128, 2, &[(4, 7)]);
+        put(&mut image, 512 + 80, &u32::MAX.to_le_bytes());
+        let error = read_image("gpt-count", &image).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_overflowing_offsets() {
+        let error = read_image("gpt-lba", &gpt_image(512, 128, u64::MAX / 256, &[(4, 7)])).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let error = read_image("gpt-entry", &gpt_image(512, 128, 2, &[(u64::MAX / 2, u64::MAX)])).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    fn put(image: &mut [u8], offset: usize, bytes: &[u8]) {
+        image[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Write a 16-byte MBR/EBR partition entry and the boot signature of its sector
+    fn mbr_entry(image: &mut [u8], sector: usize, slot: usize, part_type: u8, start: u32, sectors: u32) {
+        let at = sector * 512 + 446 + slot * 16;
+        image[at + 4] = part_type;
+        put(image, at + 8, &start.to_le_bytes());
+        put(image, at + 12, &sectors.to_le_bytes());
+        put(image, sector * 512 + 510, &[0x55, 0xAA]);
+    }
+
+    /// MBR with a primary at LBA 8 and an extended partition at LBA 16 holding EBRs at 16 and 24
+    fn mbr_image() -> Vec<u8> {
+        let mut image = vec![0u8; 64 * 512];
+        put(&mut image, 440, &0xDEADBEEFu32.to_le_bytes());
+        mbr_entry(&mut image, 0, 0, 0x83, 8, 8);
+        mbr_entry(&mut image, 0, 1, 0x05, 16, 40);
+        // EBR starts are relative to the EBR, next links to the extended partition
+        mbr_entry(&mut image, 16, 0, 0x83, 1, 4);
+        mbr_entry(&mut image, 16, 1, 0x05, 8, 8);
+        mbr_entry(&mut image, 24, 0, 0x07, 1, 4);
+        image
+    }
+
+    #[test]
+    fn reads_mbr_and_logical_partitions() {
+        let mut image = mbr_image();
+        put(&mut image, 8 * 512 + 0x438, &[0x53, 0xEF]);
+
+        let table = read_image("mbr", &image).unwrap();
+        assert_eq!((table.scheme.as_str(), table.disk_id.as_str()), ("MBR", "DEADBEEF"));
+        let found: Vec<(usize, &str, u64)> = table
+            .partitions
+            .iter()
+            .map(|p| (p.index, p.type_name.as_str(), p.start_lba))
+            .collect();
+        assert_eq!(found, [(1, "Linux", 8), (5, "Linux", 17), (6, "NTFS/exFAT", 25)]);
+        assert_eq!(table.partitions[0].filesystem, "ext2");
+        assert_eq!(table.partitions[1].size_bytes, 4 * 512);
+    }
+
+    #[test]
+    fn stops_on_cyclic_ebr_chains() {
+        // An empty EBR linking to itself never adds a partition
+        let mut image = mbr_image();
+        put(&mut image, 16 * 512 + 446, &[0; 16]);
+        mbr_entry(&mut image, 16, 1, 0x05, 0, 8);
+        let table = read_image("ebr-self", &image).unwrap();
+        assert_eq!(table.partitions.len(), 1);
+
+        // A chain looping back to its first EBR lists each partition once
+        let mut image = mbr_image();
+        mbr_entry(&mut image, 24, 1, 0x05, 0, 8);
+        let table = read_image("ebr-loop", &image).unwrap();
+        let indices: Vec<usize> = table.partitions.iter().map(|p| p.index).collect();
+        assert_eq!(indices, [1, 5, 6]);
+    }
+
+    const FS_UUID: [u8; 16] = [
+        0x3f, 0x2a, 0x1c, 0x9e, 0x5b, 0x7d, 0x4e, 0x21, 0x8a, 0x6f, 0x0c, 0x93, 0xd4, 0xe5, 0xb7, 0x12,
+    ];
+
+    /// Probe an image holding a single filesystem and no partition table
+    fn probe_image(name: &str, image: &[u8]) -> VolumeEntry {
+        let table = read_image(name, image).unwrap();
+        assert_eq!(table.scheme, "None");
+        table.partitions.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn probes_ext4() {
+        let mut image = vec![0u8; 4096];
+        put(&mut image, 1024 + 0x38, &[0x53, 0xEF]);
+        put(&mut image, 1024 + 0x60, &0x2C2u32.to_le_bytes());
+        put(&mut image, 1024 + 0x68, &FS_UUID);
+        put(&mut image, 1024 + 0x78, b"rootfs");
+        let volume = probe_image("ext4", &image);
+        assert_eq!(volume.filesystem, "ext4");
+        assert_eq!(volume.fs_uuid, "3f2a1c9e-5b7d-4e21-8a6f-0c93d4e5b712");
+        assert_eq!(volume.label, "rootfs");
+    }
+
+    #[test]
+    fn probes_ntfs() {
+        let mut image = vec![0u8; 4096];
+        put(&mut image, 3, b"NTFS    ");
+        put(&mut image, 0x48, &0x1A2B3C4D5E6F7081u64.to_le_bytes());
+        put(&mut image, 510, &[0x55, 0xAA]);
+        let volume = probe_image("ntfs", &image);
+        assert_eq!(volume.filesystem, "NTFS");
+        assert_eq!(volume.fs_uuid, "1A2B3C4D5E6F7081");
+        assert_eq!(volume.volume_serial, "5E6F-7081");
+        assert_eq!(volume.label, "N/A");
+    }
+
+    #[test]
+    fn probes_fat32() {
+        let mut image = vec![0u8; 4096];
+        put(&mut image, 3, b"MSDOS5.0");
+        put(&mut image, 0x43, &0xC0FFEE42u32.to_le_bytes());
+        put(&mut image, 0x47, b"EFI        ");
+        put(&mut image, 0x52, b"FAT32   ");
+        put(&mut image, 510, &[0x55, 0xAA]);
+        let volume = probe_image("fat32", &image);
+        assert_eq!(volume.filesystem, "FAT32");
+        assert_eq!(volume.volume_serial, "C0FF-EE42");
+        assert_eq!(volume.label, "EFI");
+
+        // mkfs.fat writes "NO NAME" for an unlabeled volume
+        put(&mut image, 0x47, b"NO NAME    ");
+        assert_eq!(probe_image("fat32-unlabeled", &image).label, "N/A");
+    }
+
+    #[test]
+    fn probes_xfs() {
+        let mut image = vec![0u8; 4096];
+        put(&mut image, 0, b"XFSB");
+        put(&mut image, 32, &FS_UUID);
+        put(&mut image, 108, b"data\0\0\0\0\0\0\0\0");
+        let volume = probe_image("xfs", &image);
+        assert_eq!(volume.filesystem, "XFS");
+        assert_eq!(volume.fs_uuid, "3f2a1c9e-5b7d-4e21-8a6f-0c93d4e5b712");
+        assert_eq!(volume.label, "data");
+    }
+
+    #[test]
+    fn probes_btrfs() {
+        let mut image = vec![0u8; PROBE_LEN];
+        put(&mut image, 0x10020, &FS_UUID);
+        put(&mut image, 0x10040, b"_BHRfS_M");
+        put(&mut image, 0x1012B, b"pool");
+        let volume = probe_image("btrfs", &image);
+        assert_eq!(volume.filesystem, "btrfs");
+        assert_eq!(volume.fs_uuid, "3f2a1c9e-5b7d-4e21-8a6f-0c93d4e5b712");
+        assert_eq!(volume.label, "pool");
+    }
+
+    #[test]
+    fn skips_inverted_partitions() {
+        let table = read_image("gpt-inverted", &gpt_image(512, 128, 2, &[(7, 4), (4, 7)])).unwrap();
+        assert_eq!(table.partitions.len(), 1);
+        assert_eq!(table.partitions[0].index, 2);
+    }
+}
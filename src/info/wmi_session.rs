@@ -0,0 +1,39 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use wmi::{COMLibrary, WMIConnection};
+
+pub const CIMV2: &str = "ROOT\\CIMV2";
+
+thread_local! {
+    static COM: RefCell<Option<COMLibrary>> = const { RefCell::new(None) };
+    static CONNECTIONS: RefCell<HashMap<String, Rc<WMIConnection>>> = RefCell::new(HashMap::new());
+}
+
+/// WMI connection to `namespace`, opened once per thread and reused by every collector on it
+///
+/// COM objects are bound to the thread that created them, so sessions cannot be shared
+/// across worker threads, only between the collectors a worker runs.
+pub fn connection(namespace: &str) -> Option<Rc<WMIConnection>> {
+    if let Some(con) = CONNECTIONS.with(|c| c.borrow().get(namespace).cloned()) {
+        return Some(con);
+    }
+
+    let com = com_library()?;
+    let con = Rc::new(WMIConnection::with_namespace_path(namespace, com).ok()?);
+    CONNECTIONS.with(|c| c.borrow_mut().insert(namespace.to_string(), con.clone()));
+    Some(con)
+}
+
+/// Initialize COM for this thread
+fn com_library() -> Option<COMLibrary> {
+    COM.with(|com| {
+        let mut com = com.borrow_mut();
+        if com.is_none() {
+            // Process-wide security can only be set once, later threads just join COM
+            *com = COMLibrary::new().or_else(|_| COMLibrary::without_security()).ok();
+        }
+        *com
+    })
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Something a key press can do in the TUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    PreviousTab,
+    NextTab,
+    ScrollUp,
+    ScrollDown,
+    RefreshTab,
+    RefreshAll,
+    ToggleWatch,
+    ToggleUnchanged,
+    ToggleMask,
+    SaveSnapshot,
+    Advanced,
+    Export,
+}
+
+impl Action {
+    pub const ALL: [Action; 13] = [
+        Action::Quit,
+        Action::PreviousTab,
+        Action::NextTab,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::RefreshTab,
+        Action::RefreshAll,
+        Action::ToggleWatch,
+        Action::ToggleUnchanged,
+        Action::ToggleMask,
+        Action::SaveSnapshot,
+        Action::Advanced,
+        Action::Export,
+    ];
+
+    /// Name used in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::PreviousTab => "previous_tab",
+            Action::NextTab => "next_tab",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::RefreshTab => "refresh_tab",
+            Action::RefreshAll => "refresh_all",
+            Action::ToggleWatch => "toggle_watch",
+            Action::ToggleUnchanged => "toggle_unchanged",
+            Action::ToggleMask => "toggle_mask",
+            Action::SaveSnapshot => "save_snapshot",
+            Action::Advanced => "advanced",
+            Action::Export => "export",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "Esc"],
+            Action::PreviousTab => &["Up", "k"],
+            Action::NextTab => &["Down", "j"],
+            Action::ScrollUp => &["Left", "h"],
+            Action::ScrollDown => &["Right", "l"],
+            Action::RefreshTab => &["r"],
+            Action::RefreshAll => &["R"],
+            Action::ToggleWatch => &["w", "W"],
+            Action::ToggleUnchanged => &["u"],
+            Action::ToggleMask => &["m", "M"],
+            Action::SaveSnapshot => &["S"],
+            Action::Advanced => &["A", "a"],
+            Action::Export => &["Tab"],
+        }
+    }
+}
+
+/// Named keys accepted besides single characters
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Enter", KeyCode::Enter),
+    ("Backspace", KeyCode::Backspace),
+    ("Space", KeyCode::Char(' ')),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Home", KeyCode::Home),
+];
+
+/// Parse "q", "Esc", "PageDown" or "F5"; single characters are case-sensitive
+pub fn parse_key(name: &str) -> Result<KeyCode, String> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+    if let Some((_, code)) = NAMED_KEYS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+        return Ok(*code);
+    }
+    if let Some(n) = name.strip_prefix(['F', 'f']).and_then(|n| n.parse::<u8>().ok()) {
+        if (1..=12).contains(&n) {
+            return Ok(KeyCode::F(n));
+        }
+    }
+    Err(format!("Unknown key \"{}\"", name))
+}
+
+/// Short label for the help bar
+fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => NAMED_KEYS
+            .iter()
+            .find(|(_, code)| *code == other)
+            .map_or_else(|| format!("{:?}", other), |(name, _)| name.to_string()),
+    }
+}
+
+/// Keys bound to each action
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = action.default_keys().iter().filter_map(|k| parse_key(k).ok()).collect();
+                (action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Replace the keys of one action
+    pub fn bind(&mut self, action: Action, keys: Vec<KeyCode>) {
+        self.bindings.insert(action, keys);
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key.code))
+            .map(|(action, _)| *action)
+    }
+
+    /// Keys bound to more than one action, as "key: action, action"
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut owners: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for (action, keys) in &self.bindings {
+            for key in keys {
+                owners.entry(key_label(*key)).or_default().push(action.name());
+            }
+        }
+        owners
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(key, actions)| format!("{}: {}", key, actions.join(", ")))
+            .collect()
+    }
+
+    /// Keys of an action for the help bar, e.g. "↑/k", upper and lower case shown once
+    pub fn hint(&self, action: Action) -> String {
+        let mut labels: Vec<String> = Vec::new();
+        for key in self.bindings.get(&action).into_iter().flatten() {
+            let label = key_label(*key);
+            if !labels.iter().any(|l| l.eq_ignore_ascii_case(&label)) {
+                labels.push(label);
+            }
+        }
+        if labels.is_empty() {
+            return "(unbound)".to_string();
+        }
+        labels.join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn parses_key_names() {
+        assert_eq!(parse_key("q"), Ok(KeyCode::Char('q')));
+        assert_eq!(parse_key("Q"), Ok(KeyCode::Char('Q')));
+        assert_eq!(parse_key("F"), Ok(KeyCode::Char('F')));
+        assert_eq!(parse_key("esc"), Ok(KeyCode::Esc));
+        assert_eq!(parse_key("PAGEDOWN"), Ok(KeyCode::PageDown));
+        assert_eq!(parse_key("space"), Ok(KeyCode::Char(' ')));
+        assert_eq!(parse_key("f5"), Ok(KeyCode::F(5)));
+        assert_eq!(parse_key("F12"), Ok(KeyCode::F(12)));
+        for bad in ["", "F0", "F13", "Escape", "Ctrl+C"] {
+            assert_eq!(parse_key(bad), Err(format!("Unknown key \"{}\"", bad)));
+        }
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        let keymap = Keymap::default();
+        assert!(keymap.conflicts().is_empty(), "{:?}", keymap.conflicts());
+        for action in Action::ALL {
+            assert_eq!(Action::from_name(action.name()), Some(action));
+            assert_ne!(keymap.hint(action), "(unbound)", "{}", action.name());
+        }
+    }
+
+    #[test]
+    fn reports_conflicts() {
+        let mut keymap = Keymap::default();
+        keymap.bind(Action::Export, vec![KeyCode::Char('q'), KeyCode::Up]);
+        assert_eq!(keymap.conflicts(), vec!["q: quit, export", "↑: previous_tab, export"]);
+        keymap.bind(Action::PreviousTab, vec![KeyCode::Char('k')]);
+        assert_eq!(keymap.conflicts(), vec!["q: quit, export"]);
+    }
+
+    #[test]
+    fn maps_keys_to_actions() {
+        let mut keymap = Keymap::default();
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(keymap.action(&press(KeyCode::Tab)), Some(Action::Export));
+        assert_eq!(keymap.action(&press(KeyCode::Char('x'))), None);
+
+        keymap.bind(Action::Export, Vec::new());
+        assert_eq!(keymap.action(&press(KeyCode::Tab)), None);
+        assert_eq!(keymap.hint(Action::Export), "(unbound)");
+        assert_eq!(keymap.hint(Action::Advanced), "A");
+        assert_eq!(keymap.hint(Action::PreviousTab), "↑/k");
+    }
+}
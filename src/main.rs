@@ -1,20 +1,121 @@
+mod agent;
 mod app;
+mod cli;
+mod collector;
+mod config;
+mod export;
+mod fleet;
+mod keys;
 mod ui;
 mod info;
+mod policy;
+mod redact;
+mod server;
+mod sign;
+mod snapshot;
+mod watch;
 
 use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 
-use app::App;
+use agent::PushUrl;
+use app::{App, Tab};
+use cli::{Command, Options, EXIT_USAGE, USAGE};
+use collector::Collector;
+use config::Config;
+use export::ExportTarget;
+use fleet::{Fleet, EXIT_FLEET_ERROR};
+use info::firmware::{BiosCatalog, FirmwareOptions};
+use info::nvme::NvmeIdentity;
+use info::CollectOptions;
+use keys::Action;
+use policy::{Policy, EXIT_POLICY_ERROR};
+use redact::{Redactor, SALT_FILE};
+use server::{Inventory, Server, DEFAULT_DATA_DIR, DEFAULT_LISTEN};
+use sign::{ExportSigner, TrustedKey, Verification, EXIT_VERIFY_ERROR, KEY_FILE};
+use snapshot::Snapshot;
 use ui::draw_ui;
+use watch::{WatchOptions, Watcher};
+
+/// How often the UI redraws while waiting for input
+const TICK: Duration = Duration::from_millis(100);
 
 fn main() -> io::Result<()> {
+    let (command, options) = match Command::parse(std::env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(EXIT_USAGE);
+        }
+    };
+
+    let (config, mut load_errors) = load_config(&options);
+    let (collect_options, collect_errors) = collect_options(&options, &config);
+    load_errors.extend(collect_errors);
+
+    match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Command::ConfigCheck => std::process::exit(run_config_check(&options)),
+        Command::Fleet { dir } => {
+            for e in &load_errors {
+                eprintln!("Warning: {}", e);
+            }
+            std::process::exit(run_fleet(&dir, &config));
+        }
+        Command::Verify { path } => {
+            for e in &load_errors {
+                eprintln!("Warning: {}", e);
+            }
+            std::process::exit(run_verify(&path, &options, &config));
+        }
+        Command::Policy { path } => {
+            for e in &load_errors {
+                eprintln!("Warning: {}", e);
+            }
+            std::process::exit(run_policy(&path, &collect_options));
+        }
+        Command::Watch => {
+            for e in &load_errors {
+                eprintln!("Warning: {}", e);
+            }
+            run_watch(&collect_options, watch_options(&options));
+            return Ok(());
+        }
+        Command::Serve => {
+            for e in &load_errors {
+                eprintln!("Warning: {}", e);
+            }
+            std::process::exit(run_serve(&options, &config));
+        }
+        Command::Agent => {
+            for e in &load_errors {
+                eprintln!("Warning: {}", e);
+            }
+            let Some(url) = options.push_url.clone().or_else(|| config.push_url.clone()) else {
+                eprintln!("agent needs a server: pass --push-url or set agent.url in the config\n\n{}", USAGE);
+                std::process::exit(2);
+            };
+            let interval = (!options.once).then(|| Duration::from_secs(options.watch_interval_secs));
+            std::process::exit(run_agent(&collect_options, &url, interval));
+        }
+        Command::Tui => {}
+    }
+
+    let policy_path = options.policy.clone().or_else(|| default_file("policy.json"));
+
     // Setup terminal
+    collector::quiet_worker_panics();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -22,7 +123,36 @@ fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
-    let mut app = App::new();
+    let mut app = App::new(&collect_options);
+    if !load_errors.is_empty() {
+        app.set_status(load_errors.join("; "));
+    }
+    if let Some(path) = policy_path {
+        app.load_policy(&path);
+    }
+    app.watch_options = watch_options(&options);
+    app.set_export_target(export_target(&options, &config));
+    match redactor(&options, &config) {
+        Ok(redactor) => app.set_redactor(redactor),
+        Err(e) => {
+            load_errors.push(e);
+            app.set_status(load_errors.join("; "));
+        }
+    }
+    match signer(&options, &config) {
+        Ok(signer) => app.signer = signer,
+        Err(e) => {
+            load_errors.push(e);
+            app.set_status(load_errors.join("; "));
+        }
+    }
+    app.masked = options.mask;
+    app.set_tabs(config.tabs.clone());
+    app.keymap = config.keymap.clone();
+    app.theme = config.theme.clone();
+    if let Some(path) = &options.baseline {
+        app.load_baseline(path);
+    }
     let res = run_app(&mut terminal, &mut app);
 
     // Restore terminal
@@ -41,33 +171,323 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Use a file from the working directory when the user did not name one
+fn default_file(name: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(name);
+    path.exists().then_some(path)
+}
+
+/// Path given with --config, or the default one when that file exists
+fn config_path(options: &Options) -> Option<PathBuf> {
+    options
+        .config
+        .clone()
+        .or_else(|| Config::default_path().filter(|path| path.exists()))
+}
+
+/// Read the config file, falling back to the defaults when it has problems
+fn load_config(options: &Options) -> (Config, Vec<String>) {
+    match config_path(options).map(|path| Config::load(&path)) {
+        Some(Ok(config)) => (config, Vec::new()),
+        Some(Err(errors)) => (Config::default(), errors),
+        None => (Config::default(), Vec::new()),
+    }
+}
+
+/// Validate the config file and return the process exit code
+fn run_config_check(options: &Options) -> i32 {
+    let Some(path) = config_path(options) else {
+        match Config::default_path() {
+            Some(path) => println!("No config file at {}, using built-in defaults", path.display()),
+            None => println!("No config directory found, using built-in defaults"),
+        }
+        if let Some(signer) = local_signer() {
+            println!("  Public key:    {}", signer.public_key());
+        }
+        return 0;
+    };
+
+    match Config::load(&path) {
+        Ok(config) => {
+            println!("Config {} is valid", path.display());
+            println!("  Placeholders:  {} patterns", config.placeholders.patterns().len());
+            println!("  OEM vendors:   {}", config.oem_vendors.len());
+            let tabs: Vec<&str> = config.tabs.iter().map(|t| t.label()).collect();
+            println!("  Tabs:          {}", tabs.join(", "));
+            let target = export_target(&Options::default(), &config);
+            println!("  Export:        {}", target.dir.join(&target.template).display());
+            println!("  Redaction:     {}", if config.redact { "on" } else { "off" });
+            println!("  Signing:       {}", if config.sign { "on" } else { "off" });
+            println!("  Trusted keys:  {}", config.trusted_keys.len());
+            if let Some(signer) = local_signer() {
+                println!("  Public key:    {}", signer.public_key());
+            }
+            0
+        }
+        Err(errors) => {
+            println!("Config {} has {} problem(s):", path.display(), errors.len());
+            for e in errors {
+                println!("  - {}", e);
+            }
+            1
+        }
+    }
+}
+
+/// Export location from the config, with the command line taking precedence
+fn export_target(options: &Options, config: &Config) -> ExportTarget {
+    let mut target = ExportTarget::default();
+    if let Some(dir) = options.export_dir.clone().or_else(|| config.export_dir.clone()) {
+        target.dir = dir;
+    }
+    if let Some(template) = options.export_name.clone().or_else(|| config.export_name.clone()) {
+        target.template = template;
+    }
+    target
+}
+
+/// Salted-hash redaction for exports when --redact or the config asks for it
+///
+/// The salt comes from the config so a fleet can share one, or else from a file in the
+/// config directory that is generated on first use.
+fn redactor(options: &Options, config: &Config) -> Result<Option<Redactor>, String> {
+    if !options.redact && !config.redact {
+        return Ok(None);
+    }
+    if let Some(salt) = &config.redaction_salt {
+        return Ok(Some(Redactor::new(salt.clone())));
+    }
+    let dir = Config::dir().ok_or("No config directory for the redaction salt, set export.salt in the config")?;
+    Redactor::load_or_create(&dir.join(SALT_FILE)).map(Some)
+}
+
+/// Export signing when --sign or the config asks for it, generating the key on first use
+fn signer(options: &Options, config: &Config) -> Result<Option<ExportSigner>, String> {
+    if !options.sign && !config.sign {
+        return Ok(None);
+    }
+    let dir = Config::dir().ok_or("No config directory for the signing key")?;
+    ExportSigner::load_or_create(&dir.join(KEY_FILE)).map(Some)
+}
+
+/// This machine's signing key, when one has been generated
+fn local_signer() -> Option<ExportSigner> {
+    let path = Config::dir()?.join(KEY_FILE);
+    path.exists().then(|| ExportSigner::load(&path).ok()).flatten()
+}
+
+/// Build the collector settings, returning any catalog or identify file load errors separately
+fn collect_options(options: &Options, config: &Config) -> (CollectOptions, Vec<String>) {
+    let mut firmware = FirmwareOptions {
+        max_age_days: options.bios_max_age_days,
+        catalog: None,
+    };
+
+    let catalog_path = options.bios_catalog.clone().or_else(|| default_file("bios_catalog.json"));
+    let mut errors = Vec::new();
+    match catalog_path.map(|path| BiosCatalog::load(&path)) {
+        Some(Ok(catalog)) => firmware.catalog = Some(catalog),
+        Some(Err(e)) => errors.push(e),
+        None => {}
+    }
+
+    let mut nvme_identity = None;
+    if options.nvme_identify.is_some() || options.nvme_namespace.is_some() {
+        match NvmeIdentity::from_files(options.nvme_identify.as_deref(), options.nvme_namespace.as_deref()) {
+            Ok(identity) => nvme_identity = Some(identity),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    let collect = CollectOptions {
+        firmware,
+        tpm_ek_cert: options.tpm_ek_cert.clone(),
+        disk_images: options.disk_images.clone(),
+        nvme_identity,
+        timeout: options.timeout_secs.map(Duration::from_secs),
+        placeholders: config.placeholders.clone(),
+        oem_vendors: config.oem_vendors.clone(),
+    };
+
+    (collect, errors)
+}
+
+fn watch_options(options: &Options) -> WatchOptions {
+    WatchOptions {
+        interval: Duration::from_secs(options.watch_interval_secs),
+        log_path: options.watch_log.clone(),
+    }
+}
+
+/// Collect on an interval until interrupted, printing and logging every change
+fn run_watch(collect_options: &CollectOptions, watch_options: WatchOptions) {
+    println!(
+        "Watching every {}s, logging changes to {} (Ctrl+C to stop)",
+        watch_options.interval.as_secs(),
+        watch_options.log_path.display()
+    );
+
+    let mut watcher = Watcher::new(watch_options);
+    loop {
+        match watcher.observe(Snapshot::collect(collect_options)) {
+            Ok(_) if watcher.runs == 1 => println!("Baseline collected"),
+            Ok(events) => {
+                for event in events {
+                    println!("{}", event);
+                }
+            }
+            Err(e) => eprintln!("Warning: {}", e),
+        }
+        std::thread::sleep(watcher.next_run_in());
+    }
+}
+
+/// Evaluate a policy without the TUI and return the process exit code
+fn run_policy(path: &Path, collect_options: &CollectOptions) -> i32 {
+    let policy = match Policy::load(path) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_POLICY_ERROR;
+        }
+    };
+
+    let report = policy.evaluate(&Snapshot::collect(collect_options));
+    println!("{}", report);
+    report.exit_code()
+}
+
+/// Report identifiers shared across a directory of exports and return the process exit code
+fn run_fleet(dir: &Path, config: &Config) -> i32 {
+    let fleet = match Fleet::load(dir) {
+        Ok(fleet) => fleet,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_FLEET_ERROR;
+        }
+    };
+
+    let report = fleet.report(&config.placeholders);
+    println!("{}", report);
+    report.exit_code()
+}
+
+/// Check the signature of an export and return the process exit code
+fn run_verify(path: &Path, options: &Options, config: &Config) -> i32 {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", path.display(), e);
+            return EXIT_VERIFY_ERROR;
+        }
+    };
+
+    // --public-key names the one signer expected, otherwise this machine and the config are trusted
+    let trusted: Vec<TrustedKey> = match &options.public_key {
+        Some(key) => vec![TrustedKey::new(*key, "--public-key")],
+        None => local_signer()
+            .map(|signer| TrustedKey::new(signer.verifying_key(), "this machine"))
+            .into_iter()
+            .chain(config.trusted_keys.iter().map(|key| TrustedKey::new(*key, "export.trusted_keys")))
+            .collect(),
+    };
+    let verification = Verification::check(path, &content, &trusted);
+    println!("{}", verification);
+    verification.exit_code()
+}
+
+/// Serve the inventory API until interrupted, returning an exit code only when startup fails
+fn run_serve(options: &Options, config: &Config) -> i32 {
+    let listen = options
+        .listen
+        .or(config.listen)
+        .unwrap_or_else(|| DEFAULT_LISTEN.parse::<SocketAddr>().expect("default address is valid"));
+    let data_dir = options
+        .data_dir
+        .clone()
+        .or_else(|| config.data_dir.clone())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR));
+
+    let server = match Inventory::open(&data_dir).and_then(|inventory| Server::bind(listen, inventory)) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    println!(
+        "Inventory server on http://{}, storing snapshots in {} (Ctrl+C to stop)",
+        server.local_addr().unwrap_or(listen),
+        data_dir.display()
+    );
+    server.run();
+    0
+}
+
+/// Push a snapshot once, or on every interval until interrupted
+fn run_agent(collect_options: &CollectOptions, url: &PushUrl, interval: Option<Duration>) -> i32 {
+    loop {
+        let result = agent::push(url, &Snapshot::collect(collect_options));
+        match &result {
+            Ok(id) => println!("{} Pushed snapshot {} to {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), id, url),
+            Err(e) => eprintln!("{} {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), e),
+        }
+        let Some(interval) = interval else {
+            return if result.is_ok() { 0 } else { 1 };
+        };
+        std::thread::sleep(interval);
+    }
+}
+
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
     loop {
+        app.poll_collection(Duration::ZERO);
+        app.poll_watch();
         terminal.draw(|f| draw_ui(f, app))?;
 
+        // Wake up regularly so finished collectors show up without a key press
+        if !event::poll(TICK)? {
+            app.tick = app.tick.wrapping_add(1);
+            continue;
+        }
+
         if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                    KeyCode::Up | KeyCode::Char('k') => app.previous_tab(),
-                    KeyCode::Down | KeyCode::Char('j') => app.next_tab(),
-                    KeyCode::Left | KeyCode::Char('h') => app.scroll_up(),
-                    KeyCode::Right | KeyCode::Char('l') => app.scroll_down(),
-                    KeyCode::Char('a') | KeyCode::Char('A') => {
-                        app.goto_advanced();
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match app.keymap.action(&key) {
+                Some(Action::Quit) => return Ok(()),
+                Some(Action::PreviousTab) => app.previous_tab(),
+                Some(Action::NextTab) => app.next_tab(),
+                Some(Action::ScrollUp) => app.scroll_up(),
+                Some(Action::ScrollDown) => app.scroll_down(),
+                Some(Action::RefreshTab) => {
+                    let tab = app.current_tab();
+                    app.refresh(tab.collectors());
+                }
+                Some(Action::RefreshAll) => app.refresh(&Collector::ALL),
+                Some(Action::ToggleWatch) => app.toggle_watch(),
+                Some(Action::ToggleMask) => app.toggle_mask(),
+                Some(Action::ToggleUnchanged) if app.current_tab() == Tab::Diff => app.toggle_diff_unchanged(),
+                Some(Action::SaveSnapshot) => app.save_snapshot(),
+                Some(Action::Advanced) => {
+                    if app.goto_advanced() {
                         app.set_status("Advanced mode - Serial comparison & spoofing advice".to_string());
+                    } else {
+                        app.set_status("The Advanced tab is disabled in the config".to_string());
                     }
-                    KeyCode::Tab => {
-                        match app.export_serials() {
-                            Ok(filename) => {
-                                app.set_status(format!("Exported to {}", filename));
-                                app.reload_previous_serials();
-                            }
-                            Err(e) => app.set_status(format!("Export failed: {}", e)),
+                }
+                Some(Action::Export) => {
+                    match app.export_serials() {
+                        Ok(path) => {
+                            let signed = if app.signer.is_some() { " and signed" } else { "" };
+                            app.set_status(format!("Exported{} to {}", signed, app.display_path(&path)));
+                            app.reload_previous_serials();
                         }
+                        Err(e) => app.set_status(format!("Export failed: {}", e)),
                     }
-                    _ => {}
                 }
+                Some(Action::ToggleUnchanged) | None => {}
             }
         }
     }
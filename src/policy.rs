@@ -0,0 +1,297 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::snapshot::Snapshot;
+
+/// How much a failed check matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// Condition a snapshot field must satisfy
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    Equals(Value),
+    NotEquals(Value),
+    OneOf(Vec<Value>),
+    Contains(String),
+    Min(f64),
+    Max(f64),
+    MaxAgeDays(i64),
+}
+
+/// A named check from the policy file
+#[derive(Debug, Clone, Deserialize)]
+pub struct Check {
+    pub name: String,
+    pub severity: Severity,
+    pub field: String,
+    #[serde(flatten)]
+    pub rule: Rule,
+}
+
+/// Posture policy loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+pub struct Policy {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub checks: Vec<Check>,
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub severity: Severity,
+    pub field: String,
+    pub passed: bool,
+    pub actual: String,
+    pub detail: String,
+}
+
+/// Outcome of evaluating a whole policy against a snapshot
+#[derive(Debug, Clone)]
+pub struct ComplianceReport {
+    pub policy_name: String,
+    pub results: Vec<CheckResult>,
+}
+
+/// Exit codes for `serial-checker policy`
+pub const EXIT_COMPLIANT: i32 = 0;
+pub const EXIT_WARNINGS: i32 = 1;
+pub const EXIT_NON_COMPLIANT: i32 = 2;
+pub const EXIT_POLICY_ERROR: i32 = 3;
+
+impl Policy {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid policy {}: {}", path.display(), e))
+    }
+
+    pub fn evaluate(&self, snapshot: &Snapshot) -> ComplianceReport {
+        let today = chrono::Local::now().date_naive();
+        let results = self.checks
+            .iter()
+            .map(|check| check.evaluate(snapshot, today))
+            .collect();
+
+        ComplianceReport {
+            policy_name: self.name.clone().unwrap_or_else(|| "Unnamed policy".to_string()),
+            results,
+        }
+    }
+}
+
+impl Check {
+    fn evaluate(&self, snapshot: &Snapshot, today: NaiveDate) -> CheckResult {
+        let (passed, actual, detail) = match snapshot.field(&self.field) {
+            Some(value) => {
+                let actual = display_value(&value);
+                let (passed, detail) = self.rule.evaluate(&value, today);
+                (passed, actual, detail)
+            }
+            None => (false, "N/A".to_string(), format!("field \"{}\" not found", self.field)),
+        };
+
+        CheckResult {
+            name: self.name.clone(),
+            severity: self.severity,
+            field: self.field.clone(),
+            passed,
+            actual,
+            detail,
+        }
+    }
+}
+
+impl Rule {
+    fn evaluate(&self, value: &Value, today: NaiveDate) -> (bool, String) {
+        match self {
+            Rule::Equals(expected) => (
+                loose_eq(value, expected),
+                format!("expected {}", display_value(expected)),
+            ),
+            Rule::NotEquals(expected) => (
+                !loose_eq(value, expected),
+                format!("must not be {}", display_value(expected)),
+            ),
+            Rule::OneOf(allowed) => (
+                allowed.iter().any(|a| loose_eq(value, a)),
+                format!(
+                    "expected one of {}",
+                    allowed.iter().map(display_value).collect::<Vec<_>>().join(", ")
+                ),
+            ),
+            Rule::Contains(needle) => (
+                display_value(value).to_lowercase().contains(&needle.to_lowercase()),
+                format!("expected to contain \"{}\"", needle),
+            ),
+            Rule::Min(min) => match leading_number(value) {
+                Some(n) => (n >= *min, format!("expected at least {}", min)),
+                None => (false, "value is not numeric".to_string()),
+            },
+            Rule::Max(max) => match leading_number(value) {
+                Some(n) => (n <= *max, format!("expected at most {}", max)),
+                None => (false, "value is not numeric".to_string()),
+            },
+            Rule::MaxAgeDays(max_days) => match parse_date(&display_value(value)) {
+                Some(date) => {
+                    let age = (today - date).num_days();
+                    (age <= *max_days, format!("{} days old, limit {}", age, max_days))
+                }
+                None => (false, "value is not a date".to_string()),
+            },
+        }
+    }
+}
+
+impl ComplianceReport {
+    pub fn failed(&self, severity: Severity) -> usize {
+        self.results
+            .iter()
+            .filter(|r| !r.passed && r.severity == severity)
+            .count()
+    }
+
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed).count()
+    }
+
+    /// Compliant unless an error-severity check failed
+    pub fn compliant(&self) -> bool {
+        self.failed(Severity::Error) == 0
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        if !self.compliant() {
+            EXIT_NON_COMPLIANT
+        } else if self.failed(Severity::Warning) > 0 {
+            EXIT_WARNINGS
+        } else {
+            EXIT_COMPLIANT
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let verdict = if self.compliant() { "COMPLIANT" } else { "NON-COMPLIANT" };
+        format!(
+            "{} ({}/{} passed, {} errors, {} warnings)",
+            verdict,
+            self.passed(),
+            self.results.len(),
+            self.failed(Severity::Error),
+            self.failed(Severity::Warning),
+        )
+    }
+}
+
+impl fmt::Display for ComplianceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Policy: {}", self.policy_name)?;
+        for result in &self.results {
+            writeln!(
+                f,
+                "[{}] [{:<7}] {} ({} = {}, {})",
+                if result.passed { "PASS" } else { "FAIL" },
+                result.severity.label(),
+                result.name,
+                result.field,
+                result.actual,
+                result.detail,
+            )?;
+        }
+        write!(f, "Result: {}", self.summary())
+    }
+}
+
+/// Render a JSON value the way the TUI shows it (strings without quotes)
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "N/A".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Compare values, treating "true"/true and "2.0"/2.0 as equal
+fn loose_eq(actual: &Value, expected: &Value) -> bool {
+    if actual == expected {
+        return true;
+    }
+    if let (Some(a), Some(b)) = (as_number(actual), as_number(expected)) {
+        return a == b;
+    }
+    display_value(actual).eq_ignore_ascii_case(&display_value(expected))
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Number at the start of a value, versions carry detail after it: "2.0 (rev 1.38)"
+fn leading_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(s) => s.split_whitespace().next()?.parse().ok(),
+        _ => as_number(value),
+    }
+}
+
+/// Parse the date formats the collectors produce
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .ok()
+        .or_else(|| value.get(0..8).and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_tpm_check_reads_spec_version() {
+        let policy: Policy = serde_json::from_str(include_str!("../policy.example.json")).unwrap();
+        let check = policy.checks.iter().find(|c| c.name == "TPM 2.0 required").unwrap();
+        assert_eq!(check.field, "tpm.spec_version");
+
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let passes = |spec: &str| check.rule.evaluate(&Value::from(spec), today).0;
+        assert!(passes("2.0 (rev 1.38)"));
+        assert!(passes("2.0"));
+        assert!(!passes("1.2"));
+        assert!(!passes("N/A"));
+    }
+
+    #[test]
+    fn equality_still_needs_the_whole_number() {
+        assert!(loose_eq(&Value::from("2.0"), &Value::from(2)));
+        assert!(!loose_eq(&Value::from("2.0 (rev 1.38)"), &Value::from(2)));
+    }
+}
@@ -0,0 +1,224 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+/// Letters and digits left visible at the end of a masked identifier
+const VISIBLE: usize = 4;
+
+/// Salt file in the user config directory, created the first time an export is redacted
+pub const SALT_FILE: &str = "redaction.salt";
+
+/// Marks a value replaced by its salted hash
+const REDACTED_PREFIX: &str = "redacted:";
+
+/// Random bytes in a generated salt
+const SALT_BYTES: usize = 32;
+
+/// Last field of every snapshot path that holds a machine or device identifier
+const IDENTIFIER_FIELDS: [&str; 20] = [
+    "serial_number",
+    "uuid",
+    "asset_tag",
+    "storage_query",
+    "smart_data",
+    "wwn",
+    "scsi",
+    "ata",
+    "serial",
+    "nguid",
+    "eui64",
+    "subsystem_nqn",
+    "disk_id",
+    "partition_guid",
+    "fs_uuid",
+    "volume_serial",
+    "mac_address",
+    "id_serial",
+    "board_serial",
+    "ek_cert_fingerprint",
+];
+
+/// Values the collectors write when a field is missing, never worth hiding,
+/// including notes such as "N/A (VirtIO)"
+fn is_missing(value: &str) -> bool {
+    let value = value.trim();
+    value.is_empty() || value == "Unknown" || value.starts_with("N/A")
+}
+
+/// Hide all but the last four letters and digits, keeping separators so the shape stays readable
+///
+/// Short values keep at most half their characters, "AB12" becomes "••12".
+pub fn mask(value: &str) -> String {
+    if is_missing(value) {
+        return value.to_string();
+    }
+    let total = value.chars().filter(|c| c.is_alphanumeric()).count();
+    let hidden = total - VISIBLE.min(total / 2);
+
+    let mut seen = 0;
+    value
+        .chars()
+        .map(|c| {
+            if !c.is_alphanumeric() {
+                return c;
+            }
+            seen += 1;
+            if seen <= hidden { '•' } else { c }
+        })
+        .collect()
+}
+
+/// An export path as drawn while masking, its name may carry the host and UUID
+///
+/// Only the file name is masked, the directory comes from the config and the extension
+/// stays readable: "exports/•••••••-••••••••-...-••••3732.txt".
+pub fn mask_file_name(path: &Path) -> String {
+    let Some(name) = path.file_name().map(|n| n.to_string_lossy()) else {
+        return path.display().to_string();
+    };
+    let masked = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{}.{}", mask(stem), extension),
+        _ => mask(&name),
+    };
+    path.with_file_name(masked).display().to_string()
+}
+
+/// Whether a snapshot path such as "disks.0.nvme.serial" holds an identifier
+pub fn is_identifier(path: &str) -> bool {
+    let field = path.rsplit(['.', ']']).next().unwrap_or_default();
+    IDENTIFIER_FIELDS.contains(&field)
+}
+
+/// Mask the identifier in a path as well, network entries are keyed by MAC address
+pub fn mask_path(path: &str) -> String {
+    match path.strip_prefix("network[").and_then(|rest| rest.split_once(']')) {
+        Some((mac, rest)) => format!("network[{}]{}", mask(mac), rest),
+        None => path.to_string(),
+    }
+}
+
+/// Replaces identifiers with salted hashes for exports
+///
+/// The same salt always gives the same hash, so two redacted exports of one machine
+/// still compare equal, and machines sharing a salt can be compared with `fleet`.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    salt: String,
+}
+
+impl Redactor {
+    pub fn new(salt: String) -> Self {
+        Self { salt }
+    }
+
+    /// Read the salt from `path`, generating and saving a random one when the file does not exist
+    pub fn load_or_create(path: &Path) -> Result<Self, String> {
+        if path.exists() {
+            let salt = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+            let salt = salt.trim();
+            if salt.is_empty() {
+                return Err(format!("Redaction salt {} is empty", path.display()));
+            }
+            return Ok(Self::new(salt.to_string()));
+        }
+
+        let mut bytes = [0u8; SALT_BYTES];
+        getrandom::getrandom(&mut bytes).map_err(|e| format!("Cannot generate a redaction salt: {}", e))?;
+        let salt: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        // Anyone holding the salt can confirm a guessed serial against a redacted export
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+        file.write_all(salt.as_bytes())
+            .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+        Ok(Self::new(salt))
+    }
+
+    /// "redacted:" and the first 128 bits of SHA-256 over the salt and the value
+    pub fn redact(&self, value: &str) -> String {
+        if is_missing(value) {
+            return value.to_string();
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update([0]);
+        hasher.update(value.trim().as_bytes());
+        let hash: String = hasher.finalize()[..16].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}{}", REDACTED_PREFIX, hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_only_the_export_file_name() {
+        let path = Path::new("exports").join("serials-4C4C4544-0042-3510-8048-B4C04F4E3732.txt");
+        let masked = mask_file_name(&path);
+        assert!(masked.starts_with("exports"), "{}", masked);
+        assert!(masked.ends_with("••••3732.txt"), "{}", masked);
+        assert!(!masked.contains("4C4C4544"), "{}", masked);
+        assert_eq!(mask_file_name(Path::new("serials")), mask("serials"));
+    }
+
+    #[test]
+    fn masks_all_but_the_last_four() {
+        assert_eq!(mask("5CG1234XYZ"), "••••••4XYZ");
+        assert_eq!(mask("00:1A:2B:3C:4D:5E"), "••:••:••:••:4D:5E");
+        assert_eq!(mask("WD-WCC6Y0ABCDEF"), "••-••••••••CDEF");
+        // Short values keep at most half their characters
+        assert_eq!(mask("AB12"), "••12");
+        assert_eq!(mask("ABC"), "••C");
+        assert_eq!(mask("A"), "•");
+        for missing in ["N/A", "N/A (VirtIO)", "Unknown", "", "  "] {
+            assert_eq!(mask(missing), missing);
+        }
+    }
+
+    #[test]
+    fn redacts_with_a_salted_hash() {
+        let redactor = Redactor::new("pepper".to_string());
+        let hash = redactor.redact("5CG1234XYZ");
+        assert!(hash.starts_with(REDACTED_PREFIX), "{}", hash);
+        assert_eq!(hash.len(), REDACTED_PREFIX.len() + 32);
+        assert!(!hash.contains("5CG1234XYZ"));
+
+        assert_eq!(redactor.redact(" 5CG1234XYZ "), hash);
+        assert_ne!(redactor.redact("5CG1234XYY"), hash);
+        assert_eq!(Redactor::new("pepper".to_string()).redact("5CG1234XYZ"), hash);
+        assert_ne!(Redactor::new("salt".to_string()).redact("5CG1234XYZ"), hash);
+        for missing in ["N/A", "N/A (VirtIO)", "Unknown", ""] {
+            assert_eq!(redactor.redact(missing), missing);
+        }
+    }
+
+    #[test]
+    fn creates_the_salt_once() {
+        let dir = std::env::temp_dir().join(format!("serial-checker-salt-{}", std::process::id()));
+        let path = dir.join(SALT_FILE);
+        let _ = fs::remove_dir_all(&dir);
+
+        let created = Redactor::load_or_create(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().len(), SALT_BYTES * 2);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let loaded = Redactor::load_or_create(&path).unwrap();
+        assert_eq!(loaded.redact("5CG1234XYZ"), created.redact("5CG1234XYZ"));
+
+        fs::write(&path, "\n").unwrap();
+        assert!(Redactor::load_or_create(&path).unwrap_err().contains("is empty"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,600 @@
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::snapshot::diff_values;
+
+/// Address `serve` listens on when none is given, reachable from this machine only
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+
+/// Directory snapshots are stored in when none is given
+pub const DEFAULT_DATA_DIR: &str = "inventory";
+
+/// Largest snapshot accepted, far above what any real machine produces
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// Largest request line plus headers
+const MAX_HEAD: usize = 64 * 1024;
+
+/// A client that stops sending is dropped after this long
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connections served at once, later ones are turned away with 503 until one finishes
+const MAX_CONNECTIONS: usize = 64;
+
+/// Body buffer allocated up front, it grows as data actually arrives
+const BODY_CHUNK: usize = 64 * 1024;
+
+/// One stored snapshot of a host
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotEntry {
+    pub id: String,
+    /// When the server received it, UTC
+    pub received: String,
+    /// When the agent collected it, agent local time
+    pub generated: String,
+}
+
+/// A host and its most recent snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct HostSummary {
+    pub host: String,
+    pub snapshots: usize,
+    pub latest: SnapshotEntry,
+}
+
+/// Snapshots on disk, one directory per host and one JSON file per snapshot
+///
+/// Files are the snapshots exactly as received, so any of them can be passed to
+/// `--baseline`. Ids are the UTC receive time and sort chronologically.
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    dir: PathBuf,
+}
+
+impl Inventory {
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        Ok(Self { dir: dir.to_path_buf() })
+    }
+
+    /// Store a snapshot under the host it names, returning the host key and snapshot id
+    pub fn store(&self, snapshot: &Value) -> Result<(String, String), String> {
+        if !snapshot.is_object() {
+            return Err("snapshot must be a JSON object".to_string());
+        }
+        if snapshot.get("generated").and_then(Value::as_str).is_none() {
+            return Err("snapshot has no \"generated\" timestamp".to_string());
+        }
+        let name = snapshot
+            .get("host")
+            .and_then(Value::as_str)
+            .ok_or("snapshot has no \"host\" field, it was saved by an older build")?;
+        let host = host_key(name).ok_or_else(|| format!("invalid host name \"{}\"", name))?;
+
+        let host_dir = self.dir.join(&host);
+        fs::create_dir_all(&host_dir).map_err(|e| format!("Cannot create {}: {}", host_dir.display(), e))?;
+        let content = serde_json::to_string_pretty(snapshot).map_err(|e| e.to_string())?;
+
+        // Two snapshots of one host in the same millisecond get a numbered suffix
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string();
+        for n in 1.. {
+            let id = if n == 1 { stamp.clone() } else { format!("{}-{}", stamp, n) };
+            let path = host_dir.join(format!("{}.json", id));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(content.as_bytes())
+                        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+                    return Ok((host, id));
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(format!("Cannot write {}: {}", path.display(), e)),
+            }
+        }
+        unreachable!("the id loop only ends by returning")
+    }
+
+    /// Every host with at least one snapshot, by name
+    pub fn hosts(&self) -> Vec<HostSummary> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut hosts: Vec<HostSummary> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let host = entry.file_name().to_str()?.to_string();
+                let history = self.history(&host)?;
+                Some(HostSummary {
+                    snapshots: history.len(),
+                    latest: history.last()?.clone(),
+                    host,
+                })
+            })
+            .collect();
+        hosts.sort_by(|a, b| a.host.cmp(&b.host));
+        hosts
+    }
+
+    /// Snapshots of `host`, oldest first, `None` for an unknown host
+    pub fn history(&self, host: &str) -> Option<Vec<SnapshotEntry>> {
+        let ids = self.ids(host)?;
+        Some(
+            ids.into_iter()
+                .map(|id| {
+                    let generated = self
+                        .snapshot(host, &id)
+                        .and_then(|s| s.get("generated").and_then(Value::as_str).map(str::to_string))
+                        .unwrap_or_default();
+                    SnapshotEntry {
+                        received: received_time(&id),
+                        generated,
+                        id,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn snapshot(&self, host: &str, id: &str) -> Option<Value> {
+        if !is_id(id) {
+            return None;
+        }
+        let path = self.dir.join(host_key(host)?).join(format!("{}.json", id));
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// Snapshot ids of `host`, oldest first
+    fn ids(&self, host: &str) -> Option<Vec<String>> {
+        let entries = fs::read_dir(self.dir.join(host_key(host)?)).ok()?;
+        let mut ids: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".json").map(str::to_string))
+            .filter(|id| is_id(id))
+            .collect();
+        ids.sort();
+        (!ids.is_empty()).then_some(ids)
+    }
+}
+
+/// Directory name for a host: lowercase, with anything but letters, digits, `-`, `_` and `.` replaced
+fn host_key(name: &str) -> Option<String> {
+    let key: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect();
+    (!key.is_empty() && !key.starts_with('.')).then_some(key)
+}
+
+/// Ids are generated here, so anything else in a request is rejected before touching the disk
+fn is_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'))
+}
+
+/// RFC 3339 receive time encoded in a snapshot id
+fn received_time(id: &str) -> String {
+    let stamp = id.split('-').next().unwrap_or_default();
+    chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S%.3fZ")
+        .map(|time| time.and_utc().to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn query(&self, key: &str) -> Option<&str> {
+        self.query.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+
+    fn write(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let body = serde_json::to_string_pretty(&self.body).unwrap_or_default();
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            body.len(),
+            body
+        )?;
+        stream.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Read one HTTP/1.1 request, answering malformed ones with the error response
+fn read_request(stream: &TcpStream) -> Result<Request, Response> {
+    let bad = |message: &str| Response::error(400, message);
+    let mut reader = BufReader::new(stream.take((MAX_HEAD + MAX_BODY) as u64));
+
+    let mut head = 0;
+    let mut line = String::new();
+    let mut read_line = |line: &mut String| -> Result<(), Response> {
+        line.clear();
+        let n = reader.read_line(line).map_err(|_| bad("cannot read request"))?;
+        head += n;
+        if n == 0 || head > MAX_HEAD {
+            return Err(bad("incomplete or oversized request head"));
+        }
+        Ok(())
+    };
+
+    read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(bad("malformed request line"));
+    };
+    let method = method.to_string();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.trim_end_matches('/').to_string();
+    let query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    let mut length = None;
+    loop {
+        read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = Some(value.trim().parse::<usize>().map_err(|_| bad("invalid Content-Length"))?);
+            }
+        }
+    }
+
+    let body = match (method.as_str(), length) {
+        ("POST", None) => return Err(Response::error(411, "POST requires Content-Length")),
+        (_, Some(n)) if n > MAX_BODY => {
+            return Err(Response::error(413, format!("body is larger than {} bytes", MAX_BODY)))
+        }
+        (_, Some(n)) => {
+            // Grow with the data instead of trusting Content-Length with a 16 MiB allocation
+            let mut body = Vec::with_capacity(n.min(BODY_CHUNK));
+            reader.by_ref().take(n as u64).read_to_end(&mut body).map_err(|_| bad("cannot read body"))?;
+            if body.len() < n {
+                return Err(bad("body shorter than Content-Length"));
+            }
+            body
+        }
+        (_, None) => Vec::new(),
+    };
+
+    Ok(Request { method, path, query, body })
+}
+
+fn route(inventory: &Inventory, request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+    let method = request.method.as_str();
+
+    match (method, segments.as_slice()) {
+        ("GET", [] | ["api"]) => Response::ok(json!({
+            "endpoints": [
+                "POST /api/snapshots",
+                "GET /api/hosts",
+                "GET /api/hosts/{host}",
+                "GET /api/hosts/{host}/latest",
+                "GET /api/hosts/{host}/snapshots/{id}",
+                "GET /api/hosts/{host}/diff?from={id}&to={id}",
+            ]
+        })),
+        ("POST", ["api", "snapshots"]) => {
+            let snapshot: Value = match serde_json::from_slice(&request.body) {
+                Ok(snapshot) => snapshot,
+                Err(e) => return Response::error(400, format!("invalid JSON: {}", e)),
+            };
+            match inventory.store(&snapshot) {
+                Ok((host, id)) => Response {
+                    status: 201,
+                    body: json!({ "host": host, "id": id }),
+                },
+                Err(e) => Response::error(400, e),
+            }
+        }
+        ("GET", ["api", "hosts"]) => Response::ok(json!(inventory.hosts())),
+        ("GET", ["api", "hosts", host]) => match inventory.history(host) {
+            Some(history) => Response::ok(json!({ "host": host_key(host), "snapshots": history })),
+            None => unknown_host(host),
+        },
+        ("GET", ["api", "hosts", host, "latest"]) => {
+            let latest = inventory.ids(host).and_then(|ids| inventory.snapshot(host, ids.last()?));
+            match latest {
+                Some(snapshot) => Response::ok(snapshot),
+                None => unknown_host(host),
+            }
+        }
+        ("GET", ["api", "hosts", host, "snapshots", id]) => match inventory.snapshot(host, id) {
+            Some(snapshot) => Response::ok(snapshot),
+            None => Response::error(404, format!("no snapshot {} for host {}", id, host)),
+        },
+        ("GET", ["api", "hosts", host, "diff"]) => diff(inventory, host, request),
+        (_, ["api", "snapshots"] | ["api", "hosts", ..]) => Response::error(405, format!("{} is not allowed here", method)),
+        _ => Response::error(404, format!("no endpoint {}", request.path)),
+    }
+}
+
+fn unknown_host(host: &str) -> Response {
+    Response::error(404, format!("no snapshots for host {}", host))
+}
+
+/// Changes between two snapshots of a host, the last two when no ids are given
+fn diff(inventory: &Inventory, host: &str, request: &Request) -> Response {
+    let Some(ids) = inventory.ids(host) else {
+        return unknown_host(host);
+    };
+    let to = request.query("to").unwrap_or(&ids[ids.len() - 1]);
+    let from = match request.query("from") {
+        Some(from) => from,
+        None => match ids.iter().position(|id| id == to) {
+            Some(i) if i > 0 => &ids[i - 1],
+            Some(_) => return Response::error(404, format!("{} is the first snapshot of host {}", to, host)),
+            None => return Response::error(404, format!("no snapshot {} for host {}", to, host)),
+        },
+    };
+
+    let (Some(old), Some(new)) = (inventory.snapshot(host, from), inventory.snapshot(host, to)) else {
+        return Response::error(404, format!("no snapshot {} or {} for host {}", from, to, host));
+    };
+    let changes: Vec<Value> = diff_values(old, new)
+        .into_iter()
+        .map(|change| json!({ "kind": change.kind(), "path": change.path, "old": change.old, "new": change.new }))
+        .collect();
+    Response::ok(json!({ "host": host_key(host), "from": from, "to": to, "changes": changes }))
+}
+
+/// HTTP server collecting snapshots pushed by agents
+pub struct Server {
+    listener: TcpListener,
+    inventory: Arc<Inventory>,
+    active: Arc<AtomicUsize>,
+}
+
+/// Holds one of the MAX_CONNECTIONS slots until the connection thread ends
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Server {
+    pub fn bind(addr: SocketAddr, inventory: Inventory) -> Result<Self, String> {
+        let listener = TcpListener::bind(addr).map_err(|e| format!("Cannot listen on {}: {}", addr, e))?;
+        Ok(Self {
+            listener,
+            inventory: Arc::new(inventory),
+            active: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+
+    /// Serve requests until the process ends, one thread per connection up to MAX_CONNECTIONS
+    pub fn run(&self) {
+        for stream in self.listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+            if self.active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                self.active.fetch_sub(1, Ordering::SeqCst);
+                let _ = Response::error(503, "too many connections, retry later").write(&mut stream);
+                continue;
+            }
+            let slot = Slot(Arc::clone(&self.active));
+            let inventory = Arc::clone(&self.inventory);
+            std::thread::spawn(move || {
+                let _slot = slot;
+                let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+                let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+                let (line, response) = match read_request(&stream) {
+                    Ok(request) => (format!("{} {}", request.method, request.path), route(&inventory, &request)),
+                    Err(response) => ("-".to_string(), response),
+                };
+                println!(
+                    "{} {} {} {}",
+                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                    peer,
+                    line,
+                    response.status
+                );
+                let _ = response.write(&mut stream);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve a fresh inventory on an ephemeral localhost port
+    fn start(name: &str) -> (SocketAddr, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("serial-checker-server-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let server = Server::bind("127.0.0.1:0".parse().unwrap(), Inventory::open(&dir).unwrap()).unwrap();
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+        (addr, dir)
+    }
+
+    fn request(addr: SocketAddr, method: &str, path: &str, body: Option<&Value>) -> (u16, Value) {
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "{} {} HTTP/1.1\r\nHost: test\r\n", method, path).unwrap();
+        if method == "POST" {
+            write!(stream, "Content-Length: {}\r\n", body.len()).unwrap();
+        }
+        write!(stream, "\r\n{}", body).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn snapshot(host: &str, serial: &str) -> Value {
+        json!({
+            "generated": "2026-10-18 14:25:00",
+            "host": host,
+            "system": { "serial_number": serial, "uuid": "4C4C4544-0042-3510-8048-B4C04F4E3732" },
+        })
+    }
+
+    fn post(addr: SocketAddr, snapshot: &Value) -> String {
+        let (status, body) = request(addr, "POST", "/api/snapshots", Some(snapshot));
+        assert_eq!(status, 201, "{}", body);
+        body["id"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn stores_and_serves_snapshots() {
+        let (addr, dir) = start("store");
+        let first = post(addr, &snapshot("WS-042", "ABC123"));
+        let second = post(addr, &snapshot("WS-042", "XYZ789"));
+        post(addr, &snapshot("ws-007", "DEF456"));
+
+        let (status, hosts) = request(addr, "GET", "/api/hosts", None);
+        assert_eq!(status, 200);
+        let names: Vec<&str> = hosts.as_array().unwrap().iter().map(|h| h["host"].as_str().unwrap()).collect();
+        assert_eq!(names, ["ws-007", "ws-042"]);
+        assert_eq!(hosts[1]["snapshots"], 2);
+
+        let (status, history) = request(addr, "GET", "/api/hosts/WS-042", None);
+        assert_eq!(status, 200);
+        assert_eq!(history["snapshots"][0]["id"], first.as_str());
+
+        let (status, latest) = request(addr, "GET", "/api/hosts/ws-042/latest", None);
+        assert_eq!(status, 200);
+        assert_eq!(latest["system"]["serial_number"], "XYZ789");
+
+        let (status, stored) = request(addr, "GET", &format!("/api/hosts/ws-042/snapshots/{}", first), None);
+        assert_eq!(status, 200);
+        assert_eq!(stored["system"]["serial_number"], "ABC123");
+
+        let (status, diff) = request(addr, "GET", "/api/hosts/ws-042/diff", None);
+        assert_eq!(status, 200);
+        assert_eq!((diff["from"].as_str(), diff["to"].as_str()), (Some(first.as_str()), Some(second.as_str())));
+        let changes = diff["changes"].as_array().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0]["path"], "system.serial_number");
+        assert_eq!((changes[0]["old"].as_str(), changes[0]["new"].as_str()), (Some("ABC123"), Some("XYZ789")));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unknown_hosts_and_ids_are_not_found() {
+        let (addr, dir) = start("missing");
+        let id = post(addr, &snapshot("ws-042", "ABC123"));
+
+        assert_eq!(request(addr, "GET", "/api/hosts/nobody", None).0, 404);
+        assert_eq!(request(addr, "GET", "/api/hosts/nobody/latest", None).0, 404);
+        assert_eq!(request(addr, "GET", "/api/hosts/ws-042/snapshots/20200101T000000.000Z", None).0, 404);
+        // Only one snapshot, nothing to compare it with
+        assert_eq!(request(addr, "GET", "/api/hosts/ws-042/diff", None).0, 404);
+        let (status, _) = request(addr, "GET", &format!("/api/hosts/ws-042/diff?from={}&to=nope", id), None);
+        assert_eq!(status, 404);
+        assert_eq!(request(addr, "DELETE", "/api/hosts/ws-042", None).0, 405);
+        assert_eq!(request(addr, "GET", "/nowhere", None).0, 404);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_path_traversal() {
+        let (addr, dir) = start("traversal");
+        post(addr, &snapshot("ws-042", "ABC123"));
+
+        for host in ["..", "../..", "..\\\\..", ".hidden"] {
+            let (status, body) = request(addr, "POST", "/api/snapshots", Some(&snapshot(host, "ABC123")));
+            assert_eq!(status, 400, "{} {}", host, body);
+        }
+        // A "/" in a stored host name becomes "_", it can never leave the data directory
+        let (status, body) = request(addr, "POST", "/api/snapshots", Some(&snapshot("a/../../b", "ABC123")));
+        assert_eq!((status, body["host"].as_str()), (201, Some("a_.._.._b")));
+
+        assert_eq!(request(addr, "GET", "/api/hosts/../latest", None).0, 404);
+        assert_eq!(request(addr, "GET", "/api/hosts/ws-042/snapshots/..%2f..%2fsecret", None).0, 404);
+        assert_eq!(request(addr, "GET", "/api/hosts/%2e%2e/latest", None).0, 404);
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(entries.len(), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_bodies() {
+        let (addr, dir) = start("bodies");
+        assert_eq!(request(addr, "POST", "/api/snapshots", Some(&json!([1, 2]))).0, 400);
+        assert_eq!(request(addr, "POST", "/api/snapshots", Some(&json!({ "host": "x" }))).0, 400);
+
+        // A huge Content-Length is refused without reading or allocating it
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "POST /api/snapshots HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+
+        // A body cut short is an error, not a hang
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "POST /api/snapshots HTTP/1.1\r\nContent-Length: 1000\r\n\r\n{{\"host\"").unwrap();
+        stream.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// Exit codes for `serial-checker verify`
+pub const EXIT_VERIFIED: i32 = 0;
+pub const EXIT_TAMPERED: i32 = 1;
+pub const EXIT_UNSIGNED: i32 = 2;
+pub const EXIT_VERIFY_ERROR: i32 = 3;
+pub const EXIT_UNTRUSTED: i32 = 4;
+
+/// Signing key file in the user config directory, created the first time an export is signed
+pub const KEY_FILE: &str = "signing.key";
+
+/// Section appended to a signed export, everything before it is what was signed
+const SECTION: &str = "[signature]";
+
+const ALGORITHM: &str = "ed25519";
+
+/// Fields of the signature section, in the order they are written
+const FIELDS: [&str; 4] = ["algorithm", "sha256", "public_key", "signature"];
+
+/// Signs exports with an Ed25519 key kept on this machine
+#[derive(Debug, Clone)]
+pub struct ExportSigner {
+    key: SigningKey,
+}
+
+impl ExportSigner {
+    /// Read the hex-encoded key seed from `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let seed = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let seed = from_hex::<32>(seed.trim()).ok_or_else(|| format!("Signing key {} is not 64 hex digits", path.display()))?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    /// Read the key from `path`, generating and saving a new one when the file does not exist
+    pub fn load_or_create(path: &Path) -> Result<Self, String> {
+        if path.exists() {
+            return Self::load(path);
+        }
+
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed).map_err(|e| format!("Cannot generate a signing key: {}", e))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        // Only the owner may read the private key
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+        file.write_all(to_hex(&seed).as_bytes())
+            .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    /// Hex public key, what `verify --public-key` expects
+    pub fn public_key(&self) -> String {
+        to_hex(self.key.verifying_key().as_bytes())
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// Append a signature section covering every byte of `content`
+    pub fn sign(&self, mut content: String) -> String {
+        let hash = to_hex(&Sha256::digest(content.as_bytes()));
+        let signature = to_hex(&self.key.sign(content.as_bytes()).to_bytes());
+        content.push_str(&format!(
+            "\n{}\nalgorithm = {}\nsha256 = {}\npublic_key = {}\nsignature = {}\n",
+            SECTION,
+            ALGORITHM,
+            hash,
+            self.public_key(),
+            signature
+        ));
+        content
+    }
+}
+
+/// Parse a hex Ed25519 public key as printed by `config check` and `verify`
+pub fn parse_public_key(hex: &str) -> Result<VerifyingKey, String> {
+    let bytes = from_hex::<32>(hex.trim()).ok_or_else(|| format!("Invalid public key {}: expected 64 hex digits", hex))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| format!("Invalid public key {}: not an Ed25519 key", hex))
+}
+
+/// A public key `verify` accepts, and where it came from
+#[derive(Debug, Clone)]
+pub struct TrustedKey {
+    pub key: VerifyingKey,
+    pub source: &'static str,
+}
+
+impl TrustedKey {
+    pub fn new(key: VerifyingKey, source: &'static str) -> Self {
+        Self { key, source }
+    }
+}
+
+/// Outcome of checking one export
+#[derive(Debug, Clone)]
+pub struct Verification {
+    pub path: String,
+    /// Whether the file has a signature section at all
+    pub signed: bool,
+    /// Hex public key the export claims to be signed with
+    pub public_key: Option<String>,
+    /// Where the signing key is trusted from, `None` when it is not trusted
+    pub trusted_by: Option<&'static str>,
+    /// Every sign of tampering found, empty when the export is intact
+    pub problems: Vec<String>,
+}
+
+impl Verification {
+    /// Check the signature section of `content` against the bytes before it
+    ///
+    /// Anyone can sign an edited file with a key of their own, so the content only counts
+    /// as verified when the signing key is one of `trusted`.
+    pub fn check(path: &Path, content: &str, trusted: &[TrustedKey]) -> Self {
+        let mut verification = Self {
+            path: path.display().to_string(),
+            signed: false,
+            public_key: None,
+            trusted_by: None,
+            problems: Vec::new(),
+        };
+        let Some((signed, block)) = split(content) else {
+            return verification;
+        };
+        verification.signed = true;
+        let problems = &mut verification.problems;
+
+        let mut fields: Vec<(&str, &str)> = Vec::new();
+        for line in block.lines().map(|line| line.trim_end_matches('\r')) {
+            match line.split_once('=').map(|(key, value)| (key.trim(), value.trim())) {
+                Some((key, value)) if FIELDS.contains(&key) && !fields.iter().any(|(k, _)| *k == key) => {
+                    fields.push((key, value))
+                }
+                _ if line.trim().is_empty() => {}
+                _ => problems.push(format!("Content was added after the signature: \"{}\"", line)),
+            }
+        }
+        let field = |key: &str| fields.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        for key in FIELDS {
+            if field(key).is_none() {
+                problems.push(format!("The signature section has no {}", key));
+            }
+        }
+
+        if let Some(algorithm) = field("algorithm").filter(|a| *a != ALGORITHM) {
+            problems.push(format!("Unsupported signature algorithm {}", algorithm));
+        }
+
+        if let Some(hash) = field("sha256") {
+            if !hash.eq_ignore_ascii_case(&to_hex(&Sha256::digest(signed.as_bytes()))) {
+                // Copying through Windows tools often converts line endings and nothing else
+                let unix = signed.replace("\r\n", "\n");
+                if unix != signed && hash.eq_ignore_ascii_case(&to_hex(&Sha256::digest(unix.as_bytes()))) {
+                    problems.push("Line endings were converted after signing, the text is otherwise unchanged".to_string());
+                } else {
+                    problems.push("The content was modified after signing (SHA-256 mismatch)".to_string());
+                }
+            }
+        }
+
+        let public_key = field("public_key").map(|hex| (hex, parse_public_key(hex)));
+        verification.public_key = public_key.as_ref().map(|(hex, _)| hex.to_lowercase());
+        let signature = field("signature").map(|hex| from_hex::<64>(hex).map(|bytes| Signature::from_bytes(&bytes)));
+        match (public_key, signature) {
+            (Some((_, Err(e))), _) => problems.push(e),
+            (_, Some(None)) => problems.push("The signature is not 128 hex digits".to_string()),
+            (Some((_, Ok(key))), Some(Some(signature))) => {
+                if key.verify_strict(signed.as_bytes(), &signature).is_err() {
+                    problems.push("The signature does not match the content".to_string());
+                }
+                verification.trusted_by = trusted.iter().find(|t| t.key == key).map(|t| t.source);
+            }
+            _ => {}
+        }
+
+        verification
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        if !self.signed {
+            EXIT_UNSIGNED
+        } else if !self.problems.is_empty() {
+            EXIT_TAMPERED
+        } else if self.trusted_by.is_none() {
+            EXIT_UNTRUSTED
+        } else {
+            EXIT_VERIFIED
+        }
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Export: {}", self.path)?;
+        if !self.signed {
+            return write!(f, "Result: not signed, export with --sign to add a signature");
+        }
+
+        if let Some(key) = &self.public_key {
+            match self.trusted_by {
+                Some(source) => writeln!(f, "Signed by:  {} (trusted: {})", key, source)?,
+                None => writeln!(f, "Signed by:  {} (not trusted)", key)?,
+            }
+        }
+
+        if self.problems.is_empty() {
+            if self.trusted_by.is_none() {
+                return write!(
+                    f,
+                    "Result: UNTRUSTED SIGNER, the content matches its signature but anyone can sign with \
+                     a new key; pass --public-key or add the key to export.trusted_keys"
+                );
+            }
+            return write!(f, "Result: valid, the export is unchanged since it was signed");
+        }
+        writeln!(f, "Problems:")?;
+        for problem in &self.problems {
+            writeln!(f, "  - {}", problem)?;
+        }
+        write!(f, "Result: TAMPERED, {} problem(s)", self.problems.len())
+    }
+}
+
+/// Signed bytes and the lines of the last signature section
+fn split(content: &str) -> Option<(&str, &str)> {
+    let mut search = content;
+    while let Some(i) = search.rfind(SECTION) {
+        let rest = &content[i + SECTION.len()..];
+        // The section header must be a whole line preceded by the blank separator line
+        let before = &content[..i];
+        if let (Some(signed), Some(block)) = (
+            before.strip_suffix("\r\n").or_else(|| before.strip_suffix('\n')),
+            rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n')).or_else(|| rest.is_empty().then_some("")),
+        ) {
+            return Some((signed, block));
+        }
+        search = &content[..i];
+    }
+    None
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = "# serial-checker export v2\n\n[system]\nserial_number = ABC123\n";
+
+    fn signer(seed: u8) -> ExportSigner {
+        ExportSigner {
+            key: SigningKey::from_bytes(&[seed; 32]),
+        }
+    }
+
+    fn check(content: &str, trusted: &ExportSigner) -> Verification {
+        let trusted = [TrustedKey::new(trusted.verifying_key(), "test")];
+        Verification::check(Path::new("export.txt"), content, &trusted)
+    }
+
+    #[test]
+    fn accepts_export_signed_by_trusted_key() {
+        let verification = check(&signer(1).sign(EXPORT.to_string()), &signer(1));
+        assert!(verification.problems.is_empty(), "{:?}", verification.problems);
+        assert_eq!(verification.exit_code(), EXIT_VERIFIED);
+    }
+
+    #[test]
+    fn rejects_export_resigned_with_another_key() {
+        let edited = EXPORT.replace("ABC123", "XYZ789");
+        let verification = check(&signer(2).sign(edited), &signer(1));
+        assert!(verification.problems.is_empty());
+        assert_eq!(verification.exit_code(), EXIT_UNTRUSTED);
+    }
+
+    #[test]
+    fn reports_edited_content() {
+        let edited = signer(1).sign(EXPORT.to_string()).replace("ABC123", "XYZ789");
+        let verification = check(&edited, &signer(1));
+        assert_eq!(verification.exit_code(), EXIT_TAMPERED);
+        assert!(verification.problems.iter().any(|p| p.contains("SHA-256 mismatch")));
+    }
+
+    #[test]
+    fn reports_content_after_signature() {
+        let appended = signer(1).sign(EXPORT.to_string()) + "[extra]\n";
+        assert_eq!(check(&appended, &signer(1)).exit_code(), EXIT_TAMPERED);
+    }
+
+    #[test]
+    fn reports_unsigned_export() {
+        let verification = check(EXPORT, &signer(1));
+        assert!(!verification.signed);
+        assert_eq!(verification.exit_code(), EXIT_UNSIGNED);
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        assert_eq!(from_hex::<3>(&to_hex(&[0, 0x7f, 0xff])), Some([0, 0x7f, 0xff]));
+        assert_eq!(from_hex::<2>("abc"), None);
+        assert_eq!(from_hex::<2>("zz00"), None);
+    }
+}
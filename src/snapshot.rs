@@ -0,0 +1,494 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app::App;
+use crate::collector::CollectorReport;
+use crate::export::host_name;
+use crate::info::{
+    system::SystemInfo,
+    bios::BiosInfo,
+    baseboard::BaseboardInfo,
+    disk::DiskEntry,
+    processor::ProcessorInfo,
+    chassis::ChassisInfo,
+    network::NetworkInterface,
+    monitor::MonitorEntry,
+    gpu::GpuEntry,
+    advanced::LockedMotherboardInfo,
+    firmware::FirmwareStatus,
+    tpm::TpmInfo,
+    virtualization::VirtualizationInfo,
+    CollectOptions,
+};
+
+/// Top-level fields that change on every collection and say nothing about the hardware
+const VOLATILE_FIELDS: [&str; 2] = ["generated", "collection"];
+
+/// Fields that change while the hardware stays the same: wear and temperature readings,
+/// the firmware's age, display modes and leased addresses. A field ending in "." covers
+/// everything under it.
+const VOLATILE_PATHS: [(&str, &str); 7] = [
+    ("disks", "smart."),
+    ("disks", "smart_data"),
+    ("firmware", "age_days"),
+    ("firmware", "too_old"),
+    ("monitors", "current_mode"),
+    ("monitors", "active"),
+    ("network", "ip_address"),
+];
+
+/// List sections whose entries are keyed by an identity field rather than by position,
+/// so a hot-plugged device shows up as added instead of shifting every entry after it
+const ENTRY_IDENTITY: [(&str, &str); 4] = [
+    ("disks", "device_path"),
+    ("network", "mac_address"),
+    ("monitors", "instance_path"),
+    ("gpus", "pci_device"),
+];
+
+/// Whether a value appeared, disappeared or changed between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Changed => "Changed",
+            ChangeKind::Unchanged => "Unchanged",
+        }
+    }
+}
+
+/// One leaf value that differs between two snapshots, `None` when the field is absent on that side
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl Change {
+    /// Component the value belongs to and the field within it,
+    /// e.g. ("disks[/dev/sda]", "smart.temperature_c") or ("system", "uuid")
+    pub fn component(&self) -> (&str, &str) {
+        let section_end = self.path.find(['.', '[']).unwrap_or(self.path.len());
+        let rest = &self.path[section_end..];
+
+        let component_end = if rest.starts_with('[') {
+            // Identities may contain dots (PCI addresses), so skip to the closing bracket
+            section_end + rest.find("].").map_or(rest.len(), |i| i + 1)
+        } else {
+            // Positional entries ("disks.2.model") keep their index in the component
+            let mut parts = rest.trim_start_matches('.').splitn(2, '.');
+            match (parts.next(), parts.next()) {
+                (Some(index), Some(_)) if index.parse::<usize>().is_ok() => section_end + 1 + index.len(),
+                _ => section_end,
+            }
+        };
+
+        let field = self.path[component_end..].trim_start_matches('.');
+        (&self.path[..component_end], field)
+    }
+
+    /// Whether the value changes on its own, without any hardware being swapped
+    pub fn is_volatile(&self) -> bool {
+        let (_, field) = self.component();
+        VOLATILE_PATHS.iter().any(|(section, path)| {
+            *section == self.section()
+                && match path.strip_suffix('.') {
+                    Some(prefix) => field.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('.')),
+                    None => field == *path,
+                }
+        })
+    }
+
+    /// Top-level snapshot field the change belongs to, e.g. "disks"
+    pub fn section(&self) -> &str {
+        self.path.split(['.', '[']).next().unwrap_or_default()
+    }
+
+    pub fn kind(&self) -> ChangeKind {
+        match (&self.old, &self.new) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            (old, new) if old == new => ChangeKind::Unchanged,
+            _ => ChangeKind::Changed,
+        }
+    }
+
+    pub fn old_text(&self) -> String {
+        value_text(self.old.as_ref())
+    }
+
+    pub fn new_text(&self) -> String {
+        value_text(self.new.as_ref())
+    }
+}
+
+/// Point-in-time copy of everything the checker collected
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub generated: String,
+    pub host: String,
+    pub system: SystemInfo,
+    pub bios: BiosInfo,
+    pub firmware: FirmwareStatus,
+    pub tpm: TpmInfo,
+    pub baseboard: BaseboardInfo,
+    pub disks: Vec<DiskEntry>,
+    pub processor: ProcessorInfo,
+    pub chassis: ChassisInfo,
+    pub network: Vec<NetworkInterface>,
+    pub monitors: Vec<MonitorEntry>,
+    pub gpus: Vec<GpuEntry>,
+    pub locked: LockedMotherboardInfo,
+    pub virtualization: VirtualizationInfo,
+    pub collection: Vec<CollectorReport>,
+}
+
+impl Snapshot {
+    /// Run every collector without starting the TUI
+    pub fn collect(options: &CollectOptions) -> Self {
+        Self::from_app(&App::collect(options))
+    }
+
+    /// Copy the data currently shown in the TUI
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            generated: Self::timestamp(),
+            host: host_name(),
+            system: app.system_info.clone(),
+            bios: app.bios_info.clone(),
+            firmware: app.firmware_status.clone(),
+            tpm: app.tpm_info.clone(),
+            baseboard: app.baseboard_info.clone(),
+            disks: app.disk_info.disks.clone(),
+            processor: app.processor_info.clone(),
+            chassis: app.chassis_info.clone(),
+            network: app.network_info.interfaces.clone(),
+            monitors: app.monitor_info.monitors.clone(),
+            gpus: app.gpu_info.gpus.clone(),
+            locked: app.locked_info.clone(),
+            virtualization: app.virtualization_info.clone(),
+            collection: app.collection.clone(),
+        }
+    }
+
+    /// Leaf values that differ from `earlier`, ignoring timestamps and collector timings
+    pub fn diff(&self, earlier: &Snapshot) -> Vec<Change> {
+        diff_values(
+            serde_json::to_value(earlier).unwrap_or_default(),
+            serde_json::to_value(self).unwrap_or_default(),
+        )
+    }
+
+    /// Every scalar value keyed by its dotted path
+    fn leaves(&self) -> BTreeMap<String, Value> {
+        leaves(serde_json::to_value(self).unwrap_or_default())
+    }
+
+    /// Write the snapshot as pretty-printed JSON, the format `Baseline::load` reads back
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    /// Look up a field by dotted path, e.g. "bios.secure_boot" or "disks.0.model"
+    pub fn field(&self, path: &str) -> Option<Value> {
+        let root = serde_json::to_value(self).ok()?;
+        let mut current = &root;
+        for part in path.split('.') {
+            current = match current {
+                Value::Object(map) => map.get(part)?,
+                Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current.clone())
+    }
+
+    fn timestamp() -> String {
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+}
+
+#[cfg(test)]
+impl Snapshot {
+    /// Nothing collected, for tests to fill in
+    pub fn empty() -> Self {
+        use crate::info::{firmware::UpdateStatus, virtualization::VmVerdict};
+
+        Self {
+            generated: Self::timestamp(),
+            host: "test-host".to_string(),
+            system: SystemInfo::default(),
+            bios: BiosInfo::default(),
+            firmware: FirmwareStatus {
+                age_days: Some(400),
+                max_age_days: 730,
+                too_old: false,
+                catalog_model: None,
+                latest_version: None,
+                latest_release_date: None,
+                update_status: UpdateStatus::NoCatalog,
+            },
+            tpm: TpmInfo::default(),
+            baseboard: BaseboardInfo::default(),
+            disks: Vec::new(),
+            processor: ProcessorInfo::default(),
+            chassis: ChassisInfo::default(),
+            network: Vec::new(),
+            monitors: Vec::new(),
+            gpus: Vec::new(),
+            locked: LockedMotherboardInfo::default(),
+            virtualization: VirtualizationInfo {
+                verdict: VmVerdict::Physical,
+                hypervisor: "None".to_string(),
+                cpuid_hypervisor_bit: false,
+                cpuid_vendor: "N/A".to_string(),
+                evidence: Vec::new(),
+            },
+            collection: Vec::new(),
+        }
+    }
+}
+
+/// Flattened snapshot that the current data is compared against in the Diff tab
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    pub label: String,
+    leaves: BTreeMap<String, Value>,
+}
+
+impl Baseline {
+    pub fn from_snapshot(snapshot: &Snapshot, label: String) -> Self {
+        Self {
+            label,
+            leaves: snapshot.leaves(),
+        }
+    }
+
+    /// Read a snapshot saved as JSON, possibly by an older or newer build
+    ///
+    /// Only the flattened values are kept, so fields this build does not know still compare.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read baseline {}: {}", path.display(), e))?;
+        let root: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid baseline {}: {}", path.display(), e))?;
+        if !root.is_object() {
+            return Err(format!("Invalid baseline {}: not a snapshot object", path.display()));
+        }
+
+        let generated = root.get("generated").and_then(Value::as_str).unwrap_or("unknown time");
+        Ok(Self {
+            label: format!("{} ({})", path.display(), generated),
+            leaves: leaves(root),
+        })
+    }
+
+    /// Every field of both sides, unchanged ones included
+    pub fn compare(&self, current: &Snapshot) -> Vec<Change> {
+        compare_leaves(&self.leaves, &current.leaves())
+    }
+}
+
+/// Leaf values that differ between two serialized snapshots, such as ones received from agents
+pub fn diff_values(earlier: Value, later: Value) -> Vec<Change> {
+    compare_leaves(&leaves(earlier), &leaves(later))
+        .into_iter()
+        .filter(|c| c.kind() != ChangeKind::Unchanged)
+        .collect()
+}
+
+/// Pair up the values of two flattened snapshots by path
+fn compare_leaves(old: &BTreeMap<String, Value>, new: &BTreeMap<String, Value>) -> Vec<Change> {
+    let mut paths: Vec<&String> = old.keys().chain(new.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .map(|path| Change {
+            path: path.clone(),
+            old: old.get(path).cloned(),
+            new: new.get(path).cloned(),
+        })
+        .collect()
+}
+
+/// Every scalar value of a serialized snapshot keyed by its dotted path
+fn leaves(root: Value) -> BTreeMap<String, Value> {
+    let mut leaves = BTreeMap::new();
+    let Value::Object(root) = root else {
+        return leaves;
+    };
+
+    for (key, value) in root {
+        if VOLATILE_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        match (ENTRY_IDENTITY.iter().find(|(section, _)| *section == key), value) {
+            (Some((_, field)), Value::Array(items)) => {
+                for (entry, item) in entry_paths(&key, field, &items).into_iter().zip(items) {
+                    flatten(entry, item, &mut leaves);
+                }
+            }
+            (_, value) => flatten(key, value, &mut leaves),
+        }
+    }
+    leaves
+}
+
+/// "disks[/dev/sda]" per entry, or "disks.2" when the identity is missing or not unique
+fn entry_paths(section: &str, field: &str, items: &[Value]) -> Vec<String> {
+    let ids: Vec<Option<&str>> = items
+        .iter()
+        .map(|item| item.get(field).and_then(Value::as_str))
+        .map(|id| id.map(str::trim).filter(|id| !id.is_empty() && *id != "N/A"))
+        .collect();
+
+    ids.iter()
+        .enumerate()
+        .map(|(i, id)| match id {
+            Some(id) if ids.iter().filter(|other| *other == &Some(*id)).count() == 1 => {
+                format!("{}[{}]", section, id)
+            }
+            _ => format!("{}.{}", section, i),
+        })
+        .collect()
+}
+
+fn flatten(path: String, value: Value, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(format!("{}.{}", path, key), value, leaves);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.into_iter().enumerate() {
+                flatten(format!("{}.{}", path, i), value, leaves);
+            }
+        }
+        leaf => {
+            leaves.insert(path, leaf);
+        }
+    }
+}
+
+/// Plain text for a leaf value, strings without their JSON quotes
+fn value_text(value: Option<&Value>) -> String {
+    match value {
+        None => "(none)".to_string(),
+        Some(Value::Null) => "null".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn nic(mac: &str, ip: &str) -> NetworkInterface {
+        NetworkInterface {
+            name: "eth0".to_string(),
+            mac_address: mac.to_string(),
+            ip_address: ip.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_skips_timestamps_and_unchanged_values() {
+        let earlier = Snapshot::empty();
+        let mut later = Snapshot::empty();
+        later.generated = "2099-01-01 00:00:00".to_string();
+        later.system.serial_number = "PF2ABC12".to_string();
+
+        let changes = later.diff(&earlier);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "system.serial_number");
+        assert_eq!(changes[0].kind(), ChangeKind::Changed);
+        assert_eq!((changes[0].old_text(), changes[0].new_text()), ("N/A".to_string(), "PF2ABC12".to_string()));
+    }
+
+    #[test]
+    fn diff_keys_entries_by_identity() {
+        let mut earlier = Snapshot::empty();
+        earlier.network = vec![nic("00:1A:2B:3C:4D:01", "10.0.0.5"), nic("00:1A:2B:3C:4D:02", "N/A")];
+        let mut later = Snapshot::empty();
+        later.network = vec![nic("00:1A:2B:3C:4D:02", "N/A")];
+
+        // Removing the first NIC does not shift the second onto its index
+        let changes = later.diff(&earlier);
+        assert!(changes.iter().all(|c| c.kind() == ChangeKind::Removed), "{:?}", changes);
+        assert!(changes.iter().all(|c| c.path.starts_with("network[00:1A:2B:3C:4D:01].")));
+        assert_eq!(changes[0].component(), ("network[00:1A:2B:3C:4D:01]", "ip_address"));
+    }
+
+    #[test]
+    fn entry_paths_fall_back_to_position() {
+        let items = [
+            json!({ "pci_device": "0000:01:00.0" }),
+            json!({ "pci_device": "N/A" }),
+            json!({ "pci_device": "0000:02:00.0" }),
+            json!({ "pci_device": "0000:02:00.0" }),
+            json!({}),
+        ];
+        assert_eq!(
+            entry_paths("gpus", "pci_device", &items),
+            ["gpus[0000:01:00.0]", "gpus.1", "gpus.2", "gpus.3", "gpus.4"]
+        );
+        let change = Change {
+            path: "gpus[0000:01:00.0].board_serial".to_string(),
+            old: None,
+            new: None,
+        };
+        assert_eq!(change.component(), ("gpus[0000:01:00.0]", "board_serial"));
+    }
+
+    #[test]
+    fn volatile_paths() {
+        let change = |path: &str| Change {
+            path: path.to_string(),
+            old: Some(json!(1)),
+            new: Some(json!(2)),
+        };
+        for path in [
+            "disks[/dev/sda].smart.temperature_c",
+            "disks.0.smart.power_on_hours",
+            "disks[/dev/nvme0n1].smart.attributes.3.raw",
+            "disks[/dev/sda].smart_data",
+            "firmware.age_days",
+            "monitors[card0-DP-1].current_mode",
+            "network[00:1A:2B:3C:4D:5E].ip_address",
+        ] {
+            assert!(change(path).is_volatile(), "{}", path);
+        }
+        for path in [
+            "disks[/dev/sda].storage_query",
+            "disks[/dev/sda].smart",
+            "disks[/dev/sda].smartctl",
+            "firmware.latest_version",
+            "monitors[card0-DP-1].serial_number",
+            "network[00:1A:2B:3C:4D:5E].mac_address",
+            "system.uuid",
+        ] {
+            assert!(!change(path).is_volatile(), "{}", path);
+        }
+    }
+}
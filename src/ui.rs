@@ -3,8 +3,66 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
 
-use crate::app::{App, Tab};
+use crate::app::{App, Tab, TabState};
+use crate::collector::{Collector, CollectorStatus};
 use crate::info::advanced::SerialStatus;
+use crate::info::firmware::{FirmwareStatus, UpdateStatus};
+use crate::info::smart::HealthStatus;
+use crate::info::tpm::TpmInfo;
+use crate::info::virtualization::VmVerdict;
+use crate::keys::Action;
+use crate::policy::Severity;
+use crate::redact::{is_identifier, mask, mask_path};
+use crate::snapshot::{Change, ChangeKind, Snapshot};
+
+/// Colors the UI is drawn with and the role each one plays, the names used in the config file
+pub const THEME_ROLES: [(&str, Color); 9] = [
+    ("label", Color::Yellow),
+    ("value", Color::White),
+    ("highlight", Color::Cyan),
+    ("muted", Color::DarkGray),
+    ("good", Color::Green),
+    ("bad", Color::Red),
+    ("accent", Color::Magenta),
+    ("info", Color::Blue),
+    ("inverse", Color::Black),
+];
+
+/// Replacement colors for the drawing roles, applied to each finished frame
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    colors: Vec<(Color, Color)>,
+}
+
+impl Theme {
+    /// Draw a role such as "label" in another color, e.g. "lightblue", "#ff8800" or "208"
+    pub fn set(&mut self, role: &str, color: &str) -> Result<(), String> {
+        let (_, drawn) = THEME_ROLES
+            .iter()
+            .find(|(name, _)| *name == role)
+            .ok_or_else(|| format!("Unknown color role \"{}\"", role))?;
+        let shown: Color = color
+            .parse()
+            .map_err(|_| format!("Invalid color \"{}\" for {}", color, role))?;
+        self.colors.retain(|(from, _)| from != drawn);
+        self.colors.push((*drawn, shown));
+        Ok(())
+    }
+
+    fn apply(&self, buffer: &mut Buffer) {
+        if self.colors.is_empty() {
+            return;
+        }
+        let shown = |color: Color| self.colors.iter().find(|(from, _)| *from == color).map_or(color, |(_, to)| *to);
+        for cell in buffer.content.iter_mut() {
+            cell.fg = shown(cell.fg);
+            cell.bg = shown(cell.bg);
+        }
+    }
+}
+
+/// Frames of the loading indicator, advanced once per UI tick
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 pub fn draw_ui(frame: &mut Frame, app: &App) {
     let size = frame.area();
@@ -20,10 +78,11 @@ pub fn draw_ui(frame: &mut Frame, app: &App) {
 
     draw_sidebar(frame, app, main_chunks[0]);
     draw_content(frame, app, main_chunks[1]);
+    app.theme.apply(frame.buffer_mut());
 }
 
 fn draw_sidebar(frame: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = Tab::all()
+    let items: Vec<ListItem> = app.tabs
         .iter()
         .enumerate()
         .map(|(i, tab)| {
@@ -36,7 +95,14 @@ fn draw_sidebar(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(Color::White)
             };
             
-            let content = format!(" {} {}", tab.icon(), tab.label());
+            let marker = match app.tab_state(*tab) {
+                TabState::Loading => SPINNER[app.tick % SPINNER.len()],
+                TabState::Failed(_) => "✗",
+                TabState::Degraded(_) => "!",
+                TabState::Ready if !app.changes_for(*tab).is_empty() => "●",
+                TabState::Ready => "",
+            };
+            let content = format!(" {} {} {}", tab.icon(), tab.label(), marker);
             ListItem::new(content).style(style)
         })
         .collect();
@@ -56,20 +122,21 @@ fn draw_sidebar(frame: &mut Frame, app: &App, area: Rect) {
 fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
     let current_tab = app.current_tab();
     
-    let content = match current_tab {
-        Tab::System => format_system_info(&app.system_info),
-        Tab::Bios => format_bios_info(&app.bios_info),
-        Tab::Baseboard => format_baseboard_info(&app.baseboard_info),
-        Tab::Disk => format_disk_info(&app.disk_info),
-        Tab::Processor => format_processor_info(&app.processor_info),
-        Tab::Chassis => format_chassis_info(&app.chassis_info),
-        Tab::Network => format_network_info(&app.network_info),
-        Tab::Monitor => format_monitor_info(&app.monitor_info),
-        Tab::Gpu => format_gpu_info(&app.gpu_info),
-        Tab::Advanced => format_advanced_info(app),
+    let content = match app.tab_state(current_tab) {
+        TabState::Loading => format_loading(app, current_tab),
+        TabState::Failed(errors) => format_collector_errors(&errors),
+        TabState::Degraded(errors) => {
+            let mut text = format_collector_errors(&errors);
+            text.lines.push(Line::from(""));
+            text.lines.extend(format_tab(app, current_tab).lines);
+            text
+        }
+        TabState::Ready => format_tab(app, current_tab),
     };
+    let content = with_changes(content, &app.changes_for(current_tab), app.masked);
 
-    let title = format!(" {} {} Information ", current_tab.icon(), current_tab.label());
+    let masked = if app.masked { " (identifiers masked)" } else { "" };
+    let title = format!(" {} {} Information{} ", current_tab.icon(), current_tab.label(), masked);
     
     let paragraph = Paragraph::new(content)
         .block(
@@ -85,11 +152,39 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 
     // Draw help bar at bottom
-    let help_text = if let Some(status) = &app.status_message {
-        format!(" {} │ A: Advanced │ Tab: Export │ q: Quit ", status)
+    let keys = &app.keymap;
+    let common = format!(
+        "{}/{}: Refresh{} │ {}: Watch │ {}: Mask │ {}: Advanced │ {}: Export │ {}: Quit ",
+        keys.hint(Action::RefreshTab),
+        keys.hint(Action::RefreshAll),
+        if app.status_message.is_some() { "" } else { " tab/all" },
+        keys.hint(Action::ToggleWatch),
+        keys.hint(Action::ToggleMask),
+        keys.hint(Action::Advanced),
+        keys.hint(Action::Export),
+        keys.hint(Action::Quit),
+    );
+    let mut help_text = if let Some(status) = &app.status_message {
+        format!(" {} │ {}", status, common)
     } else {
-        " ↑↓/jk: Navigate │ ←→/hl: Scroll │ A: Advanced │ Tab: Export │ q: Quit ".to_string()
+        format!(
+            " {} {}: Navigate │ {} {}: Scroll │ {}",
+            keys.hint(Action::PreviousTab),
+            keys.hint(Action::NextTab),
+            keys.hint(Action::ScrollUp),
+            keys.hint(Action::ScrollDown),
+            common
+        )
     };
+    if app.is_collecting() {
+        help_text = format!(
+            " {} Collecting {}/{} │{}",
+            SPINNER[app.tick % SPINNER.len()],
+            app.collection.len(),
+            Collector::ALL.len(),
+            help_text
+        );
+    }
     
     let help_area = Rect {
         x: area.x,
@@ -110,7 +205,124 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn format_system_info(info: &crate::info::system::SystemInfo) -> Text<'static> {
+fn format_tab(app: &App, tab: Tab) -> Text<'static> {
+    match tab {
+        Tab::System => format_system_info(&app.system_info, app.masked),
+        Tab::Bios => format_bios_info(&app.bios_info, &app.firmware_status, &app.tpm_info, app.masked),
+        Tab::Baseboard => format_baseboard_info(&app.baseboard_info, app.masked),
+        Tab::Disk => format_disk_info(&app.disk_info, app.masked),
+        Tab::Processor => format_processor_info(&app.processor_info, app.masked),
+        Tab::Chassis => format_chassis_info(&app.chassis_info, app.masked),
+        Tab::Network => format_network_info(&app.network_info, app.masked),
+        Tab::Monitor => format_monitor_info(&app.monitor_info, app.masked),
+        Tab::Gpu => format_gpu_info(&app.gpu_info, app.masked),
+        Tab::Watch => format_watch_info(app),
+        Tab::Diff => format_diff_info(app),
+        Tab::Advanced => format_advanced_info(app),
+    }
+}
+
+fn format_loading(app: &App, tab: Tab) -> Text<'static> {
+    let pending: Vec<&str> = tab
+        .collectors()
+        .iter()
+        .filter(|c| !app.collection.iter().any(|r| r.collector == **c))
+        .map(|c| c.label())
+        .collect();
+
+    Text::from(vec![
+        Line::from(vec![
+            Span::styled(format!("{} ", SPINNER[app.tick % SPINNER.len()]), Style::default().fg(Color::Cyan)),
+            Span::styled("Collecting...", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Waiting for:        ", Style::default().fg(Color::Yellow)),
+            Span::styled(pending.join(", "), Style::default().fg(Color::White)),
+        ]),
+    ])
+}
+
+/// List what changed since the previous collection above the tab and highlight the new values
+fn with_changes(content: Text<'static>, changes: &[&Change], masked: bool) -> Text<'static> {
+    if changes.is_empty() {
+        return content;
+    }
+
+    let changed = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let new_values: Vec<String> = changes
+        .iter()
+        .map(|c| change_text(c.new_text(), &c.path, masked))
+        // Placeholders appear all over a tab, the list above still names the field
+        .filter(|v| !v.is_empty() && v != "N/A")
+        .collect();
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("═══ CHANGED SINCE LAST COLLECTION ═══", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+    ];
+    for change in changes {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}: ", change_path(&change.path, masked)), Style::default().fg(Color::Yellow)),
+            Span::styled(change_text(change.old_text(), &change.path, masked), Style::default().fg(Color::DarkGray)),
+            Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+            Span::styled(change_text(change.new_text(), &change.path, masked), changed),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    for mut line in content.lines {
+        for span in line.spans.iter_mut() {
+            if new_values.iter().any(|v| v == span.content.trim()) {
+                span.style = span.style.patch(changed);
+            }
+        }
+        lines.push(line);
+    }
+    Text::from(lines)
+}
+
+/// An identifier as drawn, all but its last characters hidden while masking
+fn ident(value: &str, masked: bool) -> String {
+    if masked {
+        mask(value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// A changed value as drawn, masked when its field holds an identifier
+fn change_text(text: String, path: &str, masked: bool) -> String {
+    if masked && is_identifier(path) {
+        mask(&text)
+    } else {
+        text
+    }
+}
+
+/// A change path as drawn, network entries are named by their MAC address
+fn change_path(path: &str, masked: bool) -> String {
+    if masked {
+        mask_path(path)
+    } else {
+        path.to_string()
+    }
+}
+
+fn format_collector_errors(errors: &[String]) -> Text<'static> {
+    let lines = errors
+        .iter()
+        .map(|e| Line::from(vec![
+            Span::styled("✗ ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(e.clone(), Style::default().fg(Color::Red)),
+        ]))
+        .collect::<Vec<_>>();
+    Text::from(lines)
+}
+
+fn format_system_info(info: &crate::info::system::SystemInfo, masked: bool) -> Text<'static> {
     let lines = vec![
         Line::from(vec![
             Span::styled("Manufacturer:       ", Style::default().fg(Color::Yellow)),
@@ -127,12 +339,12 @@ fn format_system_info(info: &crate::info::system::SystemInfo) -> Text<'static> {
         Line::from(""),
         Line::from(vec![
             Span::styled("System Serial:      ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.serial_number.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(ident(&info.serial_number, masked), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("System UUID:        ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.uuid.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled(ident(&info.uuid, masked), Style::default().fg(Color::Cyan)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -149,7 +361,7 @@ fn format_system_info(info: &crate::info::system::SystemInfo) -> Text<'static> {
     Text::from(lines)
 }
 
-fn format_bios_info(info: &crate::info::bios::BiosInfo) -> Text<'static> {
+fn format_bios_info(info: &crate::info::bios::BiosInfo, firmware: &FirmwareStatus, tpm: &TpmInfo, masked: bool) -> Text<'static> {
     let status_style = |enabled: bool| {
         if enabled {
             Style::default().fg(Color::Green)
@@ -162,7 +374,25 @@ fn format_bios_info(info: &crate::info::bios::BiosInfo) -> Text<'static> {
         if enabled { "Enabled" } else { "Disabled" }
     };
 
-    let lines = vec![
+    let release_date = info.release_date
+        .map(|d| d.format("%m/%d/%Y").to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let (age_text, age_style) = match firmware.age_days {
+        Some(days) => (
+            format!("{} days ({:.1} years, limit {})", days, days as f64 / 365.25, firmware.max_age_days),
+            if firmware.too_old { Style::default().fg(Color::Red) } else { Style::default().fg(Color::Green) },
+        ),
+        None => ("N/A".to_string(), Style::default().fg(Color::White)),
+    };
+
+    let update_style = match firmware.update_status {
+        UpdateStatus::UpToDate | UpdateStatus::NewerThanCatalog => Style::default().fg(Color::Green),
+        UpdateStatus::UpdateAvailable => Style::default().fg(Color::Red),
+        UpdateStatus::NotInCatalog | UpdateStatus::NoCatalog => Style::default().fg(Color::DarkGray),
+    };
+
+    let mut lines = vec![
         Line::from(vec![
             Span::styled("BIOS Vendor:        ", Style::default().fg(Color::Yellow)),
             Span::styled(info.vendor.clone(), Style::default().fg(Color::White)),
@@ -173,7 +403,15 @@ fn format_bios_info(info: &crate::info::bios::BiosInfo) -> Text<'static> {
         ]),
         Line::from(vec![
             Span::styled("Release Date:       ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.release_date.clone(), Style::default().fg(Color::White)),
+            Span::styled(release_date, Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("Firmware Age:       ", Style::default().fg(Color::Yellow)),
+            Span::styled(age_text, age_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Update Status:      ", Style::default().fg(Color::Yellow)),
+            Span::styled(firmware.update_status.label().to_string(), update_style),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -192,12 +430,52 @@ fn format_bios_info(info: &crate::info::bios::BiosInfo) -> Text<'static> {
             Span::styled("TPM Status:         ", Style::default().fg(Color::Yellow)),
             Span::styled(status_text(info.tpm_enabled).to_string(), status_style(info.tpm_enabled)),
         ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("TPM Present:        ", Style::default().fg(Color::Yellow)),
+            Span::styled(if tpm.present { "Yes" } else { "No" }.to_string(), status_style(tpm.present)),
+        ]),
+        Line::from(vec![
+            Span::styled("TPM Spec Version:   ", Style::default().fg(Color::Yellow)),
+            Span::styled(tpm.spec_version.clone(), Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("TPM Manufacturer:   ", Style::default().fg(Color::Yellow)),
+            Span::styled(tpm.manufacturer.clone(), Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("TPM Vendor ID:      ", Style::default().fg(Color::Yellow)),
+            Span::styled(tpm.manufacturer_id.clone(), Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("TPM Firmware:       ", Style::default().fg(Color::Yellow)),
+            Span::styled(tpm.firmware_version.clone(), Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("EK Cert SHA-256:    ", Style::default().fg(Color::Yellow)),
+            Span::styled(ident(&tpm.ek_cert_fingerprint, masked), Style::default().fg(Color::Cyan)),
+        ]),
     ];
+
+    if let (Some(model), Some(latest)) = (&firmware.catalog_model, &firmware.latest_version) {
+        let latest_date = firmware.latest_release_date
+            .map(|d| format!(" ({})", d.format("%m/%d/%Y")))
+            .unwrap_or_default();
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Catalog Model:      ", Style::default().fg(Color::Yellow)),
+            Span::styled(model.clone(), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Latest Version:     ", Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{}{}", latest, latest_date), Style::default().fg(Color::Cyan)),
+        ]));
+    }
     
     Text::from(lines)
 }
 
-fn format_baseboard_info(info: &crate::info::baseboard::BaseboardInfo) -> Text<'static> {
+fn format_baseboard_info(info: &crate::info::baseboard::BaseboardInfo, masked: bool) -> Text<'static> {
     let lines = vec![
         Line::from(vec![
             Span::styled("Manufacturer:       ", Style::default().fg(Color::Yellow)),
@@ -214,12 +492,12 @@ fn format_baseboard_info(info: &crate::info::baseboard::BaseboardInfo) -> Text<'
         Line::from(""),
         Line::from(vec![
             Span::styled("Serial Number:      ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.serial_number.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(ident(&info.serial_number, masked), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Asset Number:       ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.asset_tag.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&info.asset_tag, masked), Style::default().fg(Color::White)),
         ]),
         Line::from(vec![
             Span::styled("(CS) Location:      ", Style::default().fg(Color::Yellow)),
@@ -230,7 +508,7 @@ fn format_baseboard_info(info: &crate::info::baseboard::BaseboardInfo) -> Text<'
     Text::from(lines)
 }
 
-fn format_disk_info(info: &crate::info::disk::DiskInfo) -> Text<'static> {
+fn format_disk_info(info: &crate::info::disk::DiskInfo, masked: bool) -> Text<'static> {
     let mut lines = vec![];
     
     for (i, disk) in info.disks.iter().enumerate() {
@@ -252,24 +530,150 @@ fn format_disk_info(info: &crate::info::disk::DiskInfo) -> Text<'static> {
         ]));
         lines.push(Line::from(vec![
             Span::styled("STORAGE_QUERY_PROPERTY: ", Style::default().fg(Color::Yellow)),
-            Span::styled(disk.storage_query.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&disk.storage_query, masked), Style::default().fg(Color::White)),
         ]));
         lines.push(Line::from(vec![
             Span::styled("SMART_RCV_DRIVE_DATA:   ", Style::default().fg(Color::Yellow)),
-            Span::styled(disk.smart_data.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&disk.smart_data, masked), Style::default().fg(Color::White)),
         ]));
         lines.push(Line::from(vec![
             Span::styled("STORAGE_QUERY_WWN:      ", Style::default().fg(Color::Yellow)),
-            Span::styled(disk.wwn.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&disk.wwn, masked), Style::default().fg(Color::White)),
         ]));
         lines.push(Line::from(vec![
             Span::styled("SCSI_PASS_THROUGH:      ", Style::default().fg(Color::Yellow)),
-            Span::styled(disk.scsi.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&disk.scsi, masked), Style::default().fg(Color::White)),
         ]));
         lines.push(Line::from(vec![
             Span::styled("ATA_PASS_THROUGH:       ", Style::default().fg(Color::Yellow)),
-            Span::styled(disk.ata.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&disk.ata, masked), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("DEVICE_PATH:            ", Style::default().fg(Color::Yellow)),
+            Span::styled(disk.device_path.clone(), Style::default().fg(Color::White)),
         ]));
+
+        if let Some(nvme) = &disk.nvme {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  NVMe Identify ({})", nvme.source), Style::default().fg(Color::Cyan)),
+            ]));
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "N/A".to_string());
+            let rows = [
+                ("    Serial:          ", ident(&nvme.serial, masked)),
+                ("    Model:           ", nvme.model.clone()),
+                ("    Firmware:        ", nvme.firmware.clone()),
+                ("    IEEE OUI:        ", nvme.ieee_oui.clone()),
+                ("    Controller ID:   ", optional(nvme.controller_id.map(|id| id.to_string()))),
+                ("    Subsystem NQN:   ", ident(&nvme.subsystem_nqn, masked)),
+                ("    Namespace ID:    ", optional(nvme.namespace_id.map(|id| id.to_string()))),
+                ("    NGUID:           ", ident(&nvme.nguid, masked)),
+                ("    EUI-64:          ", ident(&nvme.eui64, masked)),
+                ("    Capacity:        ", optional(nvme.capacity_bytes.map(format_size))),
+                ("    LBA Size:        ", optional(nvme.lba_size.map(|size| format!("{} bytes", size)))),
+            ];
+            for (label, value) in rows {
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Yellow)),
+                    Span::styled(value, Style::default().fg(Color::White)),
+                ]));
+            }
+        }
+
+        if let Some(smart) = &disk.smart {
+            let status_color = match smart.status {
+                HealthStatus::Good => Color::Green,
+                HealthStatus::Warning => Color::Yellow,
+                HealthStatus::Failing => Color::Red,
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  SMART Health ({}): ", smart.source), Style::default().fg(Color::Cyan)),
+                Span::styled(smart.status.label(), Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
+            ]));
+            let rows = [
+                ("    Temperature:     ", smart.temperature_c.map(|t| format!("{} C", t))),
+                ("    Power-On Hours:  ", smart.power_on_hours.map(|h| h.to_string())),
+                ("    Power Cycles:    ", smart.power_cycles.map(|c| c.to_string())),
+                ("    Endurance Used:  ", smart.percentage_used.map(|u| format!("{}%", u))),
+                ("    Reallocated:     ", smart.reallocated_sectors.map(|n| n.to_string())),
+                ("    Pending:         ", smart.pending_sectors.map(|n| n.to_string())),
+                ("    Uncorrectable:   ", smart.uncorrectable_sectors.map(|n| n.to_string())),
+                ("    Available Spare: ", smart.available_spare.map(|s| format!("{}%", s))),
+                ("    Media Errors:    ", smart.media_errors.map(|n| n.to_string())),
+                ("    Unsafe Shutdown: ", smart.unsafe_shutdowns.map(|n| n.to_string())),
+            ];
+            for (label, value) in rows {
+                let Some(value) = value else { continue };
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Yellow)),
+                    Span::styled(value, Style::default().fg(Color::White)),
+                ]));
+            }
+            for finding in &smart.findings {
+                lines.push(Line::from(vec![
+                    Span::styled("    ⚠ ", Style::default().fg(status_color)),
+                    Span::styled(finding.clone(), Style::default().fg(status_color)),
+                ]));
+            }
+            if !smart.attributes.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled("    ID  Attribute                 Cur Wst Thr  Raw", Style::default().fg(Color::DarkGray)),
+                ]));
+                for attr in &smart.attributes {
+                    let threshold = attr.threshold.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string());
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("    {:>3} {:<25} {:>3} {:>3} {:>3}  {}", attr.id, attr.name, attr.current, attr.worst, threshold, attr.raw),
+                            Style::default().fg(Color::White),
+                        ),
+                    ]));
+                }
+            }
+        }
+
+        if let Some(table) = &disk.partition_table {
+            lines.push(Line::from(vec![
+                Span::styled("PARTITION_SCHEME:       ", Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{} ({} byte sectors)", table.scheme, table.sector_size), Style::default().fg(Color::White)),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("DISK_IDENTIFIER:        ", Style::default().fg(Color::Yellow)),
+                Span::styled(ident(&table.disk_id, masked), Style::default().fg(Color::Cyan)),
+            ]));
+            for part in &table.partitions {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  Partition {}: ", part.index), Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        format!("{} ({})", part.type_name, format_size(part.size_bytes)),
+                        Style::default().fg(Color::White),
+                    ),
+                ]));
+                let rows = [
+                    ("    Partition GUID:  ", &part.partition_guid, true),
+                    ("    Name:            ", &part.name, false),
+                    ("    Filesystem:      ", &part.filesystem, false),
+                    ("    FS UUID:         ", &part.fs_uuid, true),
+                    ("    Volume Serial:   ", &part.volume_serial, true),
+                    ("    Label:           ", &part.label, false),
+                ];
+                for (label, value, identifier) in rows {
+                    if value.is_empty() || value == "N/A" {
+                        continue;
+                    }
+                    lines.push(Line::from(vec![
+                        Span::styled(label, Style::default().fg(Color::Yellow)),
+                        Span::styled(ident(value, masked && identifier), Style::default().fg(Color::White)),
+                    ]));
+                }
+            }
+        } else if let Some(error) = &disk.volume_error {
+            lines.push(Line::from(vec![
+                Span::styled("PARTITION_SCHEME:       ", Style::default().fg(Color::Yellow)),
+                Span::styled(format!("Unreadable ({})", error), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
     }
     
     if info.disks.is_empty() {
@@ -277,11 +681,35 @@ fn format_disk_info(info: &crate::info::disk::DiskInfo) -> Text<'static> {
             Span::styled("No disk information available", Style::default().fg(Color::DarkGray)),
         ]));
     }
+
+    if !info.unmatched.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("▸ Unmatched Records", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]));
+        for record in &info.unmatched {
+            lines.push(Line::from(vec![
+                Span::styled("  ⚠ ", Style::default().fg(Color::Yellow)),
+                Span::styled(record.clone(), Style::default().fg(Color::White)),
+            ]));
+        }
+    }
     
     Text::from(lines)
 }
 
-fn format_processor_info(info: &crate::info::processor::ProcessorInfo) -> Text<'static> {
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+fn format_processor_info(info: &crate::info::processor::ProcessorInfo, masked: bool) -> Text<'static> {
     let lines = vec![
         Line::from(vec![
             Span::styled("CPU Manufacturer:   ", Style::default().fg(Color::Yellow)),
@@ -294,7 +722,7 @@ fn format_processor_info(info: &crate::info::processor::ProcessorInfo) -> Text<'
         Line::from(""),
         Line::from(vec![
             Span::styled("Serial Number:      ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.serial_number.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&info.serial_number, masked), Style::default().fg(Color::White)),
         ]),
         Line::from(vec![
             Span::styled("Part Number:        ", Style::default().fg(Color::Yellow)),
@@ -302,7 +730,7 @@ fn format_processor_info(info: &crate::info::processor::ProcessorInfo) -> Text<'
         ]),
         Line::from(vec![
             Span::styled("Asset Number:       ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.asset_tag.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&info.asset_tag, masked), Style::default().fg(Color::White)),
         ]),
         Line::from(vec![
             Span::styled("Processor Socket:   ", Style::default().fg(Color::Yellow)),
@@ -322,7 +750,7 @@ fn format_processor_info(info: &crate::info::processor::ProcessorInfo) -> Text<'
     Text::from(lines)
 }
 
-fn format_chassis_info(info: &crate::info::chassis::ChassisInfo) -> Text<'static> {
+fn format_chassis_info(info: &crate::info::chassis::ChassisInfo, masked: bool) -> Text<'static> {
     let lines = vec![
         Line::from(vec![
             Span::styled("Manufacturer:       ", Style::default().fg(Color::Yellow)),
@@ -339,11 +767,11 @@ fn format_chassis_info(info: &crate::info::chassis::ChassisInfo) -> Text<'static
         ]),
         Line::from(vec![
             Span::styled("Serial Number:      ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.serial_number.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(ident(&info.serial_number, masked), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("Asset Number:       ", Style::default().fg(Color::Yellow)),
-            Span::styled(info.asset_tag.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&info.asset_tag, masked), Style::default().fg(Color::White)),
         ]),
         Line::from(vec![
             Span::styled("SKU Number:         ", Style::default().fg(Color::Yellow)),
@@ -354,7 +782,7 @@ fn format_chassis_info(info: &crate::info::chassis::ChassisInfo) -> Text<'static
     Text::from(lines)
 }
 
-fn format_network_info(info: &crate::info::network::NetworkInfo) -> Text<'static> {
+fn format_network_info(info: &crate::info::network::NetworkInfo, masked: bool) -> Text<'static> {
     let mut lines = vec![];
     
     if info.interfaces.is_empty() {
@@ -372,7 +800,7 @@ fn format_network_info(info: &crate::info::network::NetworkInfo) -> Text<'static
             ]));
             lines.push(Line::from(vec![
                 Span::styled("  MAC Address:      ", Style::default().fg(Color::Yellow)),
-                Span::styled(iface.mac_address.clone(), Style::default().fg(Color::White)),
+                Span::styled(ident(&iface.mac_address, masked), Style::default().fg(Color::White)),
             ]));
             if !iface.ip_address.is_empty() {
                 lines.push(Line::from(vec![
@@ -386,7 +814,7 @@ fn format_network_info(info: &crate::info::network::NetworkInfo) -> Text<'static
     Text::from(lines)
 }
 
-fn format_monitor_info(info: &crate::info::monitor::MonitorInfo) -> Text<'static> {
+fn format_monitor_info(info: &crate::info::monitor::MonitorInfo, masked: bool) -> Text<'static> {
     let mut lines = vec![];
     
     for (i, monitor) in info.monitors.iter().enumerate() {
@@ -398,8 +826,9 @@ fn format_monitor_info(info: &crate::info::monitor::MonitorInfo) -> Text<'static
             lines.push(Line::from(""));
         }
         
+        let state = if monitor.active { "Active" } else { "Inactive" };
         lines.push(Line::from(vec![
-            Span::styled(format!("Active Monitor: {}", monitor.display_name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} Monitor: {}", state, monitor.display_name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         ]));
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
@@ -412,15 +841,23 @@ fn format_monitor_info(info: &crate::info::monitor::MonitorInfo) -> Text<'static
         ]));
         lines.push(Line::from(vec![
             Span::styled("Monitor Serial:     ", Style::default().fg(Color::Yellow)),
-            Span::styled(monitor.serial_number.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled(ident(&monitor.serial_number, masked), Style::default().fg(Color::Cyan)),
         ]));
         lines.push(Line::from(vec![
             Span::styled("ID Serial Number:   ", Style::default().fg(Color::Yellow)),
-            Span::styled(monitor.id_serial.clone(), Style::default().fg(Color::White)),
+            Span::styled(ident(&monitor.id_serial, masked), Style::default().fg(Color::White)),
         ]));
         lines.push(Line::from(vec![
-            Span::styled("Resolution:         ", Style::default().fg(Color::Yellow)),
-            Span::styled(monitor.resolution.clone(), Style::default().fg(Color::Green)),
+            Span::styled("Current Mode:       ", Style::default().fg(Color::Yellow)),
+            Span::styled(monitor.current_mode.clone(), Style::default().fg(Color::Green)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Native Mode:        ", Style::default().fg(Color::Yellow)),
+            Span::styled(monitor.native_mode.clone(), Style::default().fg(Color::Green)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Instance Path:      ", Style::default().fg(Color::Yellow)),
+            Span::styled(monitor.instance_path.clone(), Style::default().fg(Color::DarkGray)),
         ]));
     }
     
@@ -433,7 +870,7 @@ fn format_monitor_info(info: &crate::info::monitor::MonitorInfo) -> Text<'static
     Text::from(lines)
 }
 
-fn format_gpu_info(info: &crate::info::gpu::GpuInfo) -> Text<'static> {
+fn format_gpu_info(info: &crate::info::gpu::GpuInfo, masked: bool) -> Text<'static> {
     let mut lines = vec![];
     
     for (i, gpu) in info.gpus.iter().enumerate() {
@@ -457,10 +894,21 @@ fn format_gpu_info(info: &crate::info::gpu::GpuInfo) -> Text<'static> {
             Span::styled("GPU Name:           ", Style::default().fg(Color::Yellow)),
             Span::styled(gpu.name.clone(), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
         ]));
-        lines.push(Line::from(vec![
-            Span::styled("GUID Serial:        ", Style::default().fg(Color::Yellow)),
-            Span::styled(gpu.guid.clone(), Style::default().fg(Color::White)),
-        ]));
+        let rows = [
+            ("PCI Location:       ", gpu.pci_location.clone()),
+            ("Vendor/Device ID:   ", gpu.vendor_device.clone()),
+            ("Subsystem ID:       ", gpu.subsystem.clone()),
+            ("Driver Key:         ", gpu.driver_key.clone()),
+            ("Adapter LUID:       ", gpu.luid.clone()),
+            ("Board Serial/UUID:  ", ident(&gpu.board_serial, masked)),
+            ("Device Class:       ", gpu.class_guid.clone()),
+        ];
+        for (label, value) in rows {
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::Yellow)),
+                Span::styled(value, Style::default().fg(Color::White)),
+            ]));
+        }
         lines.push(Line::from(vec![
             Span::styled("VRAM:               ", Style::default().fg(Color::Yellow)),
             Span::styled(gpu.vram.clone(), Style::default().fg(Color::Green)),
@@ -480,6 +928,210 @@ fn format_gpu_info(info: &crate::info::gpu::GpuInfo) -> Text<'static> {
     Text::from(lines)
 }
 
+/// Width of the field and baseline columns in the Diff tab
+const DIFF_FIELD_WIDTH: usize = 34;
+const DIFF_VALUE_WIDTH: usize = 36;
+
+fn format_diff_info(app: &App) -> Text<'static> {
+    let mut lines = vec![];
+
+    let Some(baseline) = &app.baseline else {
+        lines.push(Line::from(vec![
+            Span::styled("No baseline to compare against", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  Refresh ({}/{}) to compare with the previous collection, or start with --baseline <FILE>",
+                    app.keymap.hint(Action::RefreshTab),
+                    app.keymap.hint(Action::RefreshAll)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  Press {} to save the current data to {} for later use as a baseline",
+                    app.keymap.hint(Action::SaveSnapshot),
+                    crate::app::SNAPSHOT_FILE
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        return Text::from(lines);
+    };
+
+    let rows = baseline.compare(&Snapshot::from_app(app));
+    let count = |kind: ChangeKind| rows.iter().filter(|r| r.kind() == kind).count();
+
+    lines.push(Line::from(vec![
+        Span::styled("Baseline:           ", Style::default().fg(Color::Yellow)),
+        Span::styled(baseline.label.clone(), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Summary:            ", Style::default().fg(Color::Yellow)),
+        Span::styled(format!("{} changed  ", count(ChangeKind::Changed)), diff_style(ChangeKind::Changed)),
+        Span::styled(format!("{} added  ", count(ChangeKind::Added)), diff_style(ChangeKind::Added)),
+        Span::styled(format!("{} removed  ", count(ChangeKind::Removed)), diff_style(ChangeKind::Removed)),
+        Span::styled(format!("{} unchanged", count(ChangeKind::Unchanged)), diff_style(ChangeKind::Unchanged)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(
+                "{}: {} unchanged │ {}: save snapshot",
+                app.keymap.hint(Action::ToggleUnchanged),
+                if app.diff_hide_unchanged { "show" } else { "hide" },
+                app.keymap.hint(Action::SaveSnapshot)
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("  {:<w$} {:<v$} {}", "FIELD", "BASELINE", "CURRENT", w = DIFF_FIELD_WIDTH, v = DIFF_VALUE_WIDTH),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+
+    let mut current_component = None;
+    for row in &rows {
+        let kind = row.kind();
+        if app.diff_hide_unchanged && kind == ChangeKind::Unchanged {
+            continue;
+        }
+
+        let (component, field) = row.component();
+        if current_component != Some(component) {
+            current_component = Some(component);
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(format!("═══ {} ═══", change_path(component, app.masked)), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            ]));
+        }
+
+        let marker = match kind {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Changed => "~",
+            ChangeKind::Unchanged => " ",
+        };
+        let old = if row.old.is_some() { change_text(row.old_text(), &row.path, app.masked) } else { String::new() };
+        let new = if row.new.is_some() { change_text(row.new_text(), &row.path, app.masked) } else { String::new() };
+        let style = diff_style(kind);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", marker), style),
+            Span::styled(format!("{:<w$} ", truncate(field, DIFF_FIELD_WIDTH), w = DIFF_FIELD_WIDTH), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:<v$} ", truncate(&old, DIFF_VALUE_WIDTH), v = DIFF_VALUE_WIDTH), style),
+            Span::styled(new, style),
+        ]));
+    }
+
+    Text::from(lines)
+}
+
+fn diff_style(kind: ChangeKind) -> Style {
+    match kind {
+        ChangeKind::Added => Style::default().fg(Color::Green),
+        ChangeKind::Removed => Style::default().fg(Color::Red),
+        ChangeKind::Changed => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ChangeKind::Unchanged => Style::default().fg(Color::DarkGray),
+    }
+}
+
+/// Cut a value to `width` characters, marking the cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+fn format_watch_info(app: &App) -> Text<'static> {
+    let mut lines = vec![];
+
+    let Some(watcher) = &app.watch else {
+        lines.push(Line::from(vec![
+            Span::styled("Watch mode is off", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  Press {} to re-collect every {}s and log changes to {}",
+                    app.keymap.hint(Action::ToggleWatch),
+                    app.watch_options.interval.as_secs(),
+                    app.watch_options.log_path.display()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        return Text::from(lines);
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled("Status:             ", Style::default().fg(Color::Yellow)),
+        Span::styled(format!("Watching ({} to stop)", app.keymap.hint(Action::ToggleWatch)), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Interval:           ", Style::default().fg(Color::Yellow)),
+        Span::styled(format!("{}s", watcher.options.interval.as_secs()), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Log File:           ", Style::default().fg(Color::Yellow)),
+        Span::styled(watcher.options.log_path.display().to_string(), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Collections:        ", Style::default().fg(Color::Yellow)),
+        Span::styled(watcher.runs.to_string(), Style::default().fg(Color::White)),
+    ]));
+    let next = if app.is_collecting() {
+        "collecting now".to_string()
+    } else {
+        format!("in {}s", watcher.next_run_in().as_secs())
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Next Collection:    ", Style::default().fg(Color::Yellow)),
+        Span::styled(next, Style::default().fg(Color::White)),
+    ]));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("═══ CHANGE EVENTS ═══", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]));
+    lines.push(Line::from(""));
+
+    if watcher.events.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("No changes since watching started", Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    // Newest first
+    for event in watcher.events.iter().rev() {
+        let change = event.change();
+        let old = change_text(change.old_text(), &event.path, app.masked);
+        let new = change_text(change.new_text(), &event.path, app.masked);
+        let (style, detail) = match event.kind {
+            ChangeKind::Added => (Style::default().fg(Color::Green), new),
+            ChangeKind::Removed => (Style::default().fg(Color::Red), format!("was {}", old)),
+            ChangeKind::Changed | ChangeKind::Unchanged => (
+                Style::default().fg(Color::Yellow),
+                format!("{} → {}", old, new),
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", event.timestamp), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{:<8}", event.kind.label()), style),
+            Span::styled(format!("{}: ", change_path(&event.path, app.masked)), Style::default().fg(Color::Yellow)),
+            Span::styled(detail, Style::default().fg(Color::White)),
+        ]));
+    }
+
+    Text::from(lines)
+}
+
 fn format_advanced_info(app: &crate::app::App) -> Text<'static> {
     let mut lines = vec![];
     
@@ -529,7 +1181,27 @@ fn format_advanced_info(app: &crate::app::App) -> Text<'static> {
         Span::styled("BIOS Protected:     ", Style::default().fg(Color::Yellow)),
         Span::styled(bool_text(locked_info.bios_write_protected).to_string(), bool_style(locked_info.bios_write_protected)),
     ]));
-    
+    lines.push(Line::from(vec![
+        Span::styled("Boot Mode:          ", Style::default().fg(Color::Yellow)),
+        Span::styled(locked_info.boot_mode.clone(), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("TPM Version:        ", Style::default().fg(Color::Yellow)),
+        Span::styled(locked_info.tpm_version.clone(), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Kernel Lockdown:    ", Style::default().fg(Color::Yellow)),
+        Span::styled(locked_info.kernel_lockdown.clone(), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("IOMMU Enabled:      ", Style::default().fg(Color::Yellow)),
+        Span::styled(bool_text(locked_info.iommu_enabled).to_string(), bool_style(locked_info.iommu_enabled)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Module Sig Enforce: ", Style::default().fg(Color::Yellow)),
+        Span::styled(bool_text(locked_info.module_sig_enforced).to_string(), bool_style(locked_info.module_sig_enforced)),
+    ]));
+
     if !locked_info.lock_reasons.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
@@ -543,6 +1215,95 @@ fn format_advanced_info(app: &crate::app::App) -> Text<'static> {
         }
     }
     
+    // === VIRTUALIZATION ===
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("═══ VIRTUALIZATION ═══", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]));
+    lines.push(Line::from(""));
+
+    let vm_info = &app.virtualization_info;
+
+    let verdict_style = match vm_info.verdict {
+        VmVerdict::Physical => Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        VmVerdict::PossiblyVirtual => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        VmVerdict::Virtual => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled("Verdict:            ", Style::default().fg(Color::Yellow)),
+        Span::styled(vm_info.verdict.label().to_string(), verdict_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Hypervisor:         ", Style::default().fg(Color::Yellow)),
+        Span::styled(vm_info.hypervisor.clone(), Style::default().fg(Color::White)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("CPUID HV Bit:       ", Style::default().fg(Color::Yellow)),
+        Span::styled(bool_text(vm_info.cpuid_hypervisor_bit).to_string(), bool_style(vm_info.cpuid_hypervisor_bit)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("CPUID Vendor:       ", Style::default().fg(Color::Yellow)),
+        Span::styled(vm_info.cpuid_vendor.clone(), Style::default().fg(Color::White)),
+    ]));
+
+    if !vm_info.evidence.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Evidence:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ]));
+        for evidence in &vm_info.evidence {
+            lines.push(Line::from(vec![
+                Span::styled("  • ", Style::default().fg(Color::DarkGray)),
+                Span::styled(evidence.clone(), Style::default().fg(Color::White)),
+            ]));
+        }
+    }
+
+    // === POLICY COMPLIANCE ===
+    if let Some(report) = &app.compliance {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("═══ POLICY COMPLIANCE ═══", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]));
+        lines.push(Line::from(""));
+
+        let summary_style = if report.compliant() {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled("Policy:             ", Style::default().fg(Color::Yellow)),
+            Span::styled(report.policy_name.clone(), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Result:             ", Style::default().fg(Color::Yellow)),
+            Span::styled(report.summary(), summary_style),
+        ]));
+        lines.push(Line::from(""));
+
+        for result in &report.results {
+            let (icon, style) = match (result.passed, result.severity) {
+                (true, _) => ("✔", Style::default().fg(Color::Green)),
+                (false, Severity::Error) => ("✘", Style::default().fg(Color::Red)),
+                (false, Severity::Warning) => ("!", Style::default().fg(Color::Yellow)),
+                (false, Severity::Info) => ("i", Style::default().fg(Color::Blue)),
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", icon), style),
+                Span::styled(format!("[{}] ", result.severity.label()), Style::default().fg(Color::DarkGray)),
+                Span::styled(result.name.clone(), style),
+                Span::styled(
+                    format!(" ({}: {})", change_text(result.actual.clone(), &result.field, app.masked), result.detail),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
     // === SERIAL COMPARISON ===
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
@@ -552,43 +1313,106 @@ fn format_advanced_info(app: &crate::app::App) -> Text<'static> {
     
     if let Some(prev) = &app.previous_serials {
         lines.push(Line::from(vec![
-            Span::styled("Comparing with previous serials_export.txt", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)),
+            Span::styled(
+                format!(
+                    "Comparing with previous {}",
+                    app.previous_serials_path.as_deref().map(|p| app.display_path(p)).unwrap_or_default()
+                ),
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+            ),
         ]));
         lines.push(Line::from(vec![
             Span::styled("🟢 Unchanged  🔴 Changed  🟡 New", Style::default().fg(Color::DarkGray)),
         ]));
         lines.push(Line::from(""));
-        
-        // Compare key serials
-        let comparisons = [
-            ("System Serial", prev.compare("system_serial", &app.system_info.serial_number), app.system_info.serial_number.clone()),
-            ("System UUID", prev.compare("system_uuid", &app.system_info.uuid), app.system_info.uuid.clone()),
-            ("Baseboard Serial", prev.compare("baseboard_serial", &app.baseboard_info.serial_number), app.baseboard_info.serial_number.clone()),
-            ("Chassis Serial", prev.compare("chassis_serial", &app.chassis_info.serial_number), app.chassis_info.serial_number.clone()),
-        ];
-        
-        for (label, status, current) in comparisons {
-            let (icon, style, extra) = match &status {
-                SerialStatus::Unchanged => ("🟢", Style::default().fg(Color::Green), String::new()),
-                SerialStatus::Changed { old } => ("🔴", Style::default().fg(Color::Red), format!(" (was: {})", old)),
-                SerialStatus::New => ("🟡", Style::default().fg(Color::Yellow), " (new)".to_string()),
-            };
-            
+
+        if prev.redacted && app.redactor.is_none() {
             lines.push(Line::from(vec![
-                Span::styled(format!("{} ", icon), Style::default()),
-                Span::styled(format!("{}: ", label), Style::default().fg(Color::Yellow)),
-                Span::styled(current, style),
-                Span::styled(extra, Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    "⚠ The previous export is redacted, enable redaction to compare against it",
+                    Style::default().fg(Color::Yellow),
+                ),
             ]));
+        } else {
+            // A redacted export holds salted hashes, so hash the current values the same way
+            let compare = |category: &str, current: &str| {
+                if prev.redacted {
+                    prev.compare(category, &app.export_identifier(current))
+                } else {
+                    prev.compare(category, current)
+                }
+            };
+
+            // Compare key serials
+            let comparisons = [
+                ("System Serial", compare("system_serial", &app.system_info.serial_number), &app.system_info.serial_number),
+                ("System UUID", compare("system_uuid", &app.system_info.uuid), &app.system_info.uuid),
+                ("Baseboard Serial", compare("baseboard_serial", &app.baseboard_info.serial_number), &app.baseboard_info.serial_number),
+                ("Chassis Serial", compare("chassis_serial", &app.chassis_info.serial_number), &app.chassis_info.serial_number),
+            ];
+
+            for (label, status, current) in comparisons {
+                let (icon, style, extra) = match &status {
+                    SerialStatus::Unchanged => ("🟢", Style::default().fg(Color::Green), String::new()),
+                    // A hash says nothing more when masked, a plain old value is masked like the current one
+                    SerialStatus::Changed { old } => ("🔴", Style::default().fg(Color::Red), format!(" (was: {})", ident(old, app.masked && !prev.redacted))),
+                    SerialStatus::New => ("🟡", Style::default().fg(Color::Yellow), " (new)".to_string()),
+                };
+
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{} ", icon), Style::default()),
+                    Span::styled(format!("{}: ", label), Style::default().fg(Color::Yellow)),
+                    Span::styled(ident(current, app.masked), style),
+                    Span::styled(extra, Style::default().fg(Color::DarkGray)),
+                ]));
+            }
         }
     } else {
         lines.push(Line::from(vec![
             Span::styled("⚠ No previous export found", Style::default().fg(Color::Yellow)),
         ]));
         lines.push(Line::from(vec![
-            Span::styled("  Press Tab to export serials first", Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("  Press {} to export serials first", app.keymap.hint(Action::Export)), Style::default().fg(Color::DarkGray)),
         ]));
     }
-    
+
+
+    // === COLLECTION ===
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("═══ COLLECTION ═══", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]));
+    lines.push(Line::from(""));
+
+    for report in &app.collection {
+        let style = match report.status {
+            CollectorStatus::Ok => Style::default().fg(Color::Green),
+            CollectorStatus::TimedOut => Style::default().fg(Color::Yellow),
+            CollectorStatus::Panicked(_) => Style::default().fg(Color::Red),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<20}", format!("{}:", report.collector.label())), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:>6} ms  ", report.duration_ms), Style::default().fg(Color::White)),
+            Span::styled(report.status.label(), style),
+        ]));
+    }
+
+    // === PLACEHOLDER VALUES ===
+    let junk: Vec<_> = app.collection.iter().flat_map(|report| &report.junk).collect();
+    if !junk.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("═══ PLACEHOLDER VALUES (shown as N/A) ═══", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]));
+        lines.push(Line::from(""));
+        for value in junk {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}: ", value.path), Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{:?}", value.value), Style::default().fg(Color::White)),
+                Span::styled(format!("  ({})", value.reason.label()), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+
     Text::from(lines)
 }
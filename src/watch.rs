@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::snapshot::{Change, ChangeKind, Snapshot};
+
+/// Time between collections when no interval is given
+pub const DEFAULT_INTERVAL_SECS: u64 = 60;
+
+/// Log appended to when no file is given
+pub const DEFAULT_LOG: &str = "hardware_changes.jsonl";
+
+/// Events kept in memory for the Watch tab, the log file has all of them
+const RECENT_EVENTS: usize = 500;
+
+/// Interval and log file shared by the TUI panel and the headless command
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub interval: Duration,
+    pub log_path: PathBuf,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(DEFAULT_INTERVAL_SECS),
+            log_path: PathBuf::from(DEFAULT_LOG),
+        }
+    }
+}
+
+/// One line of the JSON-lines log
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeEvent {
+    pub timestamp: String,
+    pub kind: ChangeKind,
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl ChangeEvent {
+    fn new(timestamp: &str, change: Change) -> Self {
+        Self {
+            timestamp: timestamp.to_string(),
+            kind: change.kind(),
+            path: change.path,
+            old: change.old,
+            new: change.new,
+        }
+    }
+
+    pub fn change(&self) -> Change {
+        Change {
+            path: self.path.clone(),
+            old: self.old.clone(),
+            new: self.new.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ChangeEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let change = self.change();
+        match self.kind {
+            ChangeKind::Added => write!(f, "{} [added]   {} = {}", self.timestamp, self.path, change.new_text()),
+            ChangeKind::Removed => write!(f, "{} [removed] {} (was {})", self.timestamp, self.path, change.old_text()),
+            ChangeKind::Changed | ChangeKind::Unchanged => write!(
+                f,
+                "{} [changed] {}: {} -> {}",
+                self.timestamp,
+                self.path,
+                change.old_text(),
+                change.new_text()
+            ),
+        }
+    }
+}
+
+/// Diffs each collection against the previous one and logs what changed
+pub struct Watcher {
+    pub options: WatchOptions,
+    /// Most recent events, oldest first
+    pub events: VecDeque<ChangeEvent>,
+    pub runs: usize,
+    last: Option<Snapshot>,
+    last_run: Option<Instant>,
+}
+
+impl Watcher {
+    pub fn new(options: WatchOptions) -> Self {
+        Self {
+            options,
+            events: VecDeque::new(),
+            runs: 0,
+            last: None,
+            last_run: None,
+        }
+    }
+
+    /// Whether the interval has passed since the last observation
+    pub fn is_due(&self) -> bool {
+        self.last_run.is_none_or(|t| t.elapsed() >= self.options.interval)
+    }
+
+    /// Time left until the next collection
+    pub fn next_run_in(&self) -> Duration {
+        self.last_run
+            .map(|t| self.options.interval.saturating_sub(t.elapsed()))
+            .unwrap_or_default()
+    }
+
+    /// Diff against the previous observation and append the identity changes to the log
+    ///
+    /// The first observation is the baseline and produces no events. Readings that drift on
+    /// their own (temperatures, wear counters, firmware age) are left out.
+    pub fn observe(&mut self, snapshot: Snapshot) -> Result<Vec<ChangeEvent>, String> {
+        self.last_run = Some(Instant::now());
+        self.runs += 1;
+
+        let timestamp = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+        let events: Vec<ChangeEvent> = match &self.last {
+            Some(previous) => snapshot
+                .diff(previous)
+                .into_iter()
+                .filter(|change| !change.is_volatile())
+                .map(|change| ChangeEvent::new(&timestamp, change))
+                .collect(),
+            None => Vec::new(),
+        };
+        self.last = Some(snapshot);
+
+        self.events.extend(events.iter().cloned());
+        while self.events.len() > RECENT_EVENTS {
+            self.events.pop_front();
+        }
+
+        self.append(&events)?;
+        Ok(events)
+    }
+
+    fn append(&self, events: &[ChangeEvent]) -> Result<(), String> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let path = &self.options.log_path;
+        let error = |e: std::io::Error| format!("Cannot write {}: {}", path.display(), e);
+        let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(error)?;
+        for event in events {
+            let line = serde_json::to_string(event).map_err(|e| e.to_string())?;
+            writeln!(file, "{}", line).map_err(error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::network::NetworkInterface;
+
+    fn watcher(name: &str) -> Watcher {
+        let log_path = std::env::temp_dir().join(format!("serial-checker-watch-{}-{}.jsonl", name, std::process::id()));
+        let _ = std::fs::remove_file(&log_path);
+        Watcher::new(WatchOptions {
+            interval: Duration::from_secs(1),
+            log_path,
+        })
+    }
+
+    fn with_nics(macs: &[&str]) -> Snapshot {
+        let mut snapshot = Snapshot::empty();
+        snapshot.network = macs
+            .iter()
+            .map(|mac| NetworkInterface {
+                name: "eth0".to_string(),
+                mac_address: mac.to_string(),
+                ip_address: "10.0.0.5".to_string(),
+            })
+            .collect();
+        snapshot
+    }
+
+    #[test]
+    fn first_observation_is_the_baseline() {
+        let mut watcher = watcher("first");
+        assert!(watcher.is_due());
+        assert!(watcher.observe(with_nics(&["00:1A:2B:3C:4D:01"])).unwrap().is_empty());
+        assert_eq!(watcher.runs, 1);
+        assert!(!watcher.is_due());
+        assert!(!watcher.options.log_path.exists());
+    }
+
+    #[test]
+    fn logs_added_and_removed_entries() {
+        let mut watcher = watcher("hotplug");
+        watcher.observe(with_nics(&["00:1A:2B:3C:4D:01", "00:1A:2B:3C:4D:02"])).unwrap();
+        let events = watcher.observe(with_nics(&["00:1A:2B:3C:4D:02", "00:1A:2B:3C:4D:03"])).unwrap();
+
+        let removed: Vec<&str> = events.iter().filter(|e| e.kind == ChangeKind::Removed).map(|e| e.path.as_str()).collect();
+        let added: Vec<&str> = events.iter().filter(|e| e.kind == ChangeKind::Added).map(|e| e.path.as_str()).collect();
+        assert!(removed.contains(&"network[00:1A:2B:3C:4D:01].mac_address"), "{:?}", removed);
+        assert!(added.contains(&"network[00:1A:2B:3C:4D:03].mac_address"), "{:?}", added);
+        assert!(events.iter().all(|e| !e.path.contains("4D:02")));
+        assert_eq!(watcher.events.len(), events.len());
+
+        let log = std::fs::read_to_string(&watcher.options.log_path).unwrap();
+        std::fs::remove_file(&watcher.options.log_path).unwrap();
+        assert_eq!(log.lines().count(), events.len());
+        let first: Value = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        assert!(first["timestamp"].is_string());
+    }
+
+    #[test]
+    fn volatile_readings_are_not_logged() {
+        let mut watcher = watcher("volatile");
+        watcher.observe(with_nics(&["00:1A:2B:3C:4D:01"])).unwrap();
+
+        let mut later = with_nics(&["00:1A:2B:3C:4D:01"]);
+        later.firmware.age_days = Some(401);
+        later.network[0].ip_address = "10.0.0.6".to_string();
+        assert!(watcher.observe(later.clone()).unwrap().is_empty());
+
+        later.system.uuid = "4C4C4544-0042-3510-8048-B4C04F4E3732".to_string();
+        let events = watcher.observe(later).unwrap();
+        std::fs::remove_file(&watcher.options.log_path).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, "system.uuid");
+    }
+}